@@ -0,0 +1,219 @@
+/* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+//! Compares the current checksum implementation against the previous one,
+//! which copied the whole checked range into a `Vec<u32>` before processing it,
+//! and `calculate_all_checksums` against calculating every kind one by one.
+//!
+//! Run with `cargo bench`.
+
+use std::time::{Duration, Instant};
+
+use ipl3checksum::CICKind;
+
+/// `std::hint::black_box` is newer than our MSRV
+fn black_box<T>(x: T) -> T {
+    unsafe {
+        let ret = std::ptr::read_volatile(&x);
+        std::mem::forget(x);
+        ret
+    }
+}
+
+const ROM_SIZE: usize = 0x400000;
+
+/// The implementation used before the checksum loop read the words in place.
+mod reference {
+    use ipl3checksum::CICKind;
+
+    fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+        if offset % 4 != 0 || offset + 4 > bytes.len() {
+            return None;
+        }
+
+        Some(u32::from_be_bytes(
+            bytes[offset..offset + 4].try_into().ok()?,
+        ))
+    }
+
+    fn read_u32_vec(bytes: &[u8], offset: usize, len: usize) -> Vec<u32> {
+        let mut ret = Vec::with_capacity(len);
+
+        for i in 0..len {
+            ret.push(read_u32(bytes, offset + i * 4).unwrap());
+        }
+
+        ret
+    }
+
+    pub fn calculate_checksum(rom_bytes: &[u8], kind: CICKind) -> Option<(u32, u32)> {
+        let seed = kind.get_seed();
+        let magic = kind.get_magic();
+
+        let v0 = seed.wrapping_mul(magic).wrapping_add(1);
+
+        let mut a3 = v0;
+        let mut t2 = v0;
+        let mut t3 = v0;
+        let mut s0 = v0;
+        let mut a2 = v0;
+        let mut t4 = v0;
+
+        let bytes_to_check: u32 = if (kind == CICKind::CIC_5101)
+            && (kind.get_entrypoint(read_u32(rom_bytes, 8)?) == 0x80000400)
+        {
+            0x3FE000
+        } else {
+            0x100000
+        };
+
+        if rom_bytes.len() < bytes_to_check as usize + 0x1000 {
+            return None;
+        }
+
+        let rom_words = read_u32_vec(rom_bytes, 0, (bytes_to_check as usize + 0x1000) / 4);
+
+        let words_to_check = bytes_to_check.wrapping_div(4) as usize;
+        for i in 0..words_to_check {
+            let word = rom_words[i + (0x1000 / 4)];
+
+            let a1 = a3.wrapping_add(word);
+            if a1 < a3 {
+                t2 = t2.wrapping_add(0x1);
+            }
+            a3 = a1;
+
+            let a0 = word.rotate_left(word & 0x1F);
+
+            t3 ^= word;
+
+            s0 = s0.wrapping_add(a0);
+            if a2 < word {
+                a2 ^= a3 ^ word;
+            } else {
+                a2 ^= a0;
+            }
+
+            if kind == CICKind::CIC_X105 {
+                let temp = (i & 0x3F) | 0x80;
+                let t7 = rom_words[temp + 0x154];
+
+                t4 = t4.wrapping_add(word ^ t7);
+            } else {
+                t4 = t4.wrapping_add(word ^ s0);
+            }
+        }
+
+        match kind {
+            CICKind::CIC_X103 | CICKind::CIC_5101 => {
+                let t6 = a3 ^ t2;
+                a3 = t6.wrapping_add(t3);
+
+                let t8 = s0 ^ a2;
+                s0 = t8.wrapping_add(t4);
+            }
+            CICKind::CIC_X106 => {
+                let t6 = a3.wrapping_mul(t2);
+                a3 = t6.wrapping_add(t3);
+
+                let t8 = s0.wrapping_mul(a2);
+                s0 = t8.wrapping_add(t4);
+            }
+            _ => {
+                let t6 = a3 ^ t2;
+                a3 = t6 ^ t3;
+
+                let t8 = s0 ^ a2;
+                s0 = t8 ^ t4;
+            }
+        }
+
+        Some((a3, s0))
+    }
+}
+
+/// Deterministic pseudo random bytes, so every run benchmarks the same data.
+fn generate_rom(entrypoint: u32) -> Vec<u8> {
+    let mut state: u32 = 0x12345678;
+    let mut rom: Vec<u8> = (0..ROM_SIZE)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+
+    rom[8..12].copy_from_slice(&entrypoint.to_be_bytes());
+    rom
+}
+
+fn measure<T, F: FnMut() -> T>(mut f: F) -> (Duration, T) {
+    // Warm up
+    let result = f();
+
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(2) {
+        black_box(f());
+        iterations += 1;
+    }
+
+    (start.elapsed() / iterations, result)
+}
+
+fn main() {
+    let cases = [
+        (CICKind::CIC_6102_7101, 0x80000400),
+        (CICKind::CIC_X103, 0x80000400),
+        (CICKind::CIC_X105, 0x80000400),
+        (CICKind::CIC_X106, 0x80000400),
+        (CICKind::CIC_5101, 0x80100400),
+    ];
+
+    for (kind, entrypoint) in cases {
+        let rom = generate_rom(entrypoint);
+
+        let (reference_time, reference_result) =
+            measure(|| reference::calculate_checksum(black_box(&rom), black_box(kind)).unwrap());
+        let (current_time, current_result) =
+            measure(|| ipl3checksum::calculate_checksum(black_box(&rom), black_box(kind)).unwrap());
+
+        assert_eq!(current_result, reference_result);
+
+        println!(
+            "{:<14} reference: {:>10.3?}  current: {:>10.3?}  speedup: {:.2}x",
+            kind.get_name(),
+            reference_time,
+            current_time,
+            reference_time.as_secs_f64() / current_time.as_secs_f64(),
+        );
+    }
+
+    let rom = generate_rom(0x80000400);
+
+    let (one_by_one_time, one_by_one_result) = measure(|| {
+        [
+            CICKind::CIC_6101,
+            CICKind::CIC_6102_7101,
+            CICKind::CIC_7102,
+            CICKind::CIC_X103,
+            CICKind::CIC_X105,
+            CICKind::CIC_X106,
+            CICKind::CIC_5101,
+        ]
+        .map(|kind| ipl3checksum::calculate_checksum(black_box(&rom), kind))
+    });
+    let (all_time, all_result) =
+        measure(|| ipl3checksum::calculate_all_checksums(black_box(&rom)).map(|x| x.1));
+
+    assert_eq!(one_by_one_result, all_result);
+
+    println!(
+        "{:<14} one by one: {:>10.3?}  single pass: {:>10.3?}  speedup: {:.2}x",
+        "All kinds",
+        one_by_one_time,
+        all_time,
+        one_by_one_time.as_secs_f64() / all_time.as_secs_f64(),
+    );
+}
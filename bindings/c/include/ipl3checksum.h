@@ -4,8 +4,18 @@
 
 #include "ipl3checksum/error.h"
 #include "ipl3checksum/cickinds.h"
+#include "ipl3checksum/byte_order.h"
+#include "ipl3checksum/short_rom.h"
+#include "ipl3checksum/padding.h"
+#include "ipl3checksum/params.h"
 #include "ipl3checksum/checksum.h"
+#include "ipl3checksum/reader.h"
+#include "ipl3checksum/rom_header.h"
 #include "ipl3checksum/detect.h"
+#include "ipl3checksum/bootcode.h"
+#include "ipl3checksum/cic6105.h"
+#include "ipl3checksum/boot_state.h"
+#include "ipl3checksum/forge.h"
 #include "ipl3checksum/utils.h"
 #include "ipl3checksum/version.h"
 
@@ -0,0 +1,145 @@
+#ifndef IPL3CHECKSUM_BOOT_STATE_H
+#define IPL3CHECKSUM_BOOT_STATE_H
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+#include "cickinds.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/boot_state.rs` */
+/**
+ * The video standard of the console, as passed to the game on `osTvType`.
+ */
+typedef enum Ipl3Checksum_TvType {
+    Ipl3Checksum_TvType_Pal,
+    Ipl3Checksum_TvType_Ntsc,
+    Ipl3Checksum_TvType_Mpal,
+} Ipl3Checksum_TvType;
+
+/* This needs to be synced with the Rust equivalent in `src/rs/boot_state.rs` */
+/**
+ * A word written by the IPL3 outside of the copied game code.
+ */
+typedef struct Ipl3Checksum_MemoryWrite {
+    /* Virtual address of the word. */
+    uint32_t address;
+    /* The written word. */
+    uint32_t value;
+} Ipl3Checksum_MemoryWrite;
+
+/* This needs to be synced with the Rust equivalent in `src/rs/boot_state.rs` */
+/**
+ * The state the IPL3 leaves the console in when jumping to the entrypoint of
+ * the game.
+ *
+ * Meant for emulators which skip running the PIF ROM and the IPL3 (HLE boot).
+ * The IPL3 passes some values to the game on the `s3` to `s7` registers, which
+ * libultra stores on `osRomType`, `osTvType`, `osResetType`, `osCicId` and
+ * `osVersion` respectively.
+ */
+typedef struct Ipl3Checksum_BootState {
+    /* The CIC kind of the ROM. */
+    Ipl3Checksum_CICKind kind;
+    /* Kind of media the game booted from, stored on `s3`. 0 for cartridges. */
+    uint32_t rom_type;
+    /* The video standard of the console, stored on `s4`. */
+    Ipl3Checksum_TvType tv_type;
+    /* Kind of reset, stored on `s5`. 0 for a cold boot. */
+    uint32_t reset_type;
+    /* Seed of the CIC, stored on `s6`. */
+    uint32_t seed;
+    /* Stored on `s7`. 6 on PAL and MPAL consoles and 0 on NTSC ones, like mupen64plus's HLE boot. */
+    uint32_t version;
+    /* Address the game code is copied to, and where the IPL3 jumps to. */
+    uint32_t entrypoint;
+    /* Offset of the ROM where the copied game code starts. */
+    uint32_t rom_copy_offset;
+    /* Amount of bytes copied from the ROM to `entrypoint`. */
+    uint32_t rom_copy_size;
+    /* Address the 6105 IPL3 copies a table from its own ROM range to. 0 for every other kind. */
+    uint32_t table_copy_address;
+    /* Offset of the ROM where the table copied to `table_copy_address` starts. */
+    uint32_t table_copy_offset;
+    /* Amount of bytes copied from the ROM to `table_copy_address`. 0 for every kind but the 6105. */
+    uint32_t table_copy_size;
+    /* Size in bytes of the RDRAM, stored on `osMemSize`. 0x800000 with the Expansion Pak. */
+    uint32_t rdram_size;
+} Ipl3Checksum_BootState;
+
+/**
+ * The boot state for a cold boot of a cartridge with the given CIC kind.
+ *
+ * ## Arguments
+ *
+ * * `kind` - The CIC kind of the ROM.
+ * * `header_entrypoint` - The entrypoint specified on the ROM header.
+ * * `tv_type` - The video standard of the emulated console.
+ *
+ * The console is assumed to not have the Expansion Pak, see `rdram_size`.
+ */
+Ipl3Checksum_BootState ipl3checksum_boot_state_for_kind(
+    Ipl3Checksum_CICKind kind,
+    uint32_t header_entrypoint,
+    Ipl3Checksum_TvType tv_type
+);
+
+/**
+ * The boot state for a cold boot of a cartridge. The CIC kind is detected from
+ * the IPL3 of the ROM.
+ *
+ * ## Arguments
+ *
+ * * `dst_state` - Pointer where the boot state will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x1000 bytes.
+ * * `tv_type` - The video standard of the emulated console.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_state` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_boot_state_for_rom(
+    Ipl3Checksum_BootState *dst_state,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_TvType tv_type
+);
+
+/**
+ * Words written by the IPL3 besides the copied ROM ranges, which have to be
+ * applied after the copies.
+ *
+ * Every IPL3 stores `rdram_size` on `osMemSize`, at 0x80000318, or at
+ * 0x800003F0 for the 6105. The 6105 IPL3 also leaves a small routine on SP
+ * IMEM, starting at 0xA4001004.
+ *
+ * ## Arguments
+ *
+ * * `state` - The boot state.
+ * * `dst_writes` - Array where the writes will be copied to, in the order
+ *   they have to be applied. At most 9 writes are returned.
+ * * `dst_writes_len` - Pointer where the amount of writes will be set to.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ */
+Ipl3Checksum_Error ipl3checksum_boot_state_memory_writes(
+    Ipl3Checksum_BootState state,
+    Ipl3Checksum_MemoryWrite (*dst_writes)[9],
+    size_t *dst_writes_len
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
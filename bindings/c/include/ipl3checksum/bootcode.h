@@ -0,0 +1,74 @@
+#ifndef IPL3CHECKSUM_BOOTCODE_H
+#define IPL3CHECKSUM_BOOTCODE_H
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+#include "cickinds.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/**
+ * Calculates the boot code checksum the PIF's IPL2 computes over an IPL3
+ * before running it.
+ *
+ * The IPL2 compares this 48-bit value against the one sent by the CIC, and
+ * only boots the console if they match.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be set to.
+ * * `ipl3_bytes_len` - Bytes length of the input `ipl3_bytes`. It must be
+ *   exactly 0xFC0.
+ * * `ipl3_bytes` - Bytes of an IPL3 binary in big endian format.
+ * * `kind` - The CIC kind, which provides the seed.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause
+ *   for failing. If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_bootcode_checksum(
+    uint64_t *dst_checksum,
+    size_t ipl3_bytes_len,
+    const uint8_t *ipl3_bytes,
+    Ipl3Checksum_CICKind kind
+);
+
+/**
+ * Checks if an IPL3 would be accepted by the IPL2 when paired with the given
+ * CIC.
+ *
+ * ## Arguments
+ *
+ * * `dst_valid` - Pointer where the result of the check will be set to.
+ * * `ipl3_bytes_len` - Bytes length of the input `ipl3_bytes`. It must be
+ *   exactly 0xFC0.
+ * * `ipl3_bytes` - Bytes of an IPL3 binary in big endian format.
+ * * `kind` - The CIC kind to check against.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause
+ *   for failing. `Ipl3Checksum_Error_UnknownBootcodeChecksum` is returned if
+ *   the boot code checksum of `kind` is not known. If execution fails then
+ *   `dst_valid` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_validate_bootcode(
+    bool *dst_valid,
+    size_t ipl3_bytes_len,
+    const uint8_t *ipl3_bytes,
+    Ipl3Checksum_CICKind kind
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
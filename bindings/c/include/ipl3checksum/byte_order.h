@@ -0,0 +1,70 @@
+#ifndef IPL3CHECKSUM_BYTE_ORDER_H
+#define IPL3CHECKSUM_BYTE_ORDER_H
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/byte_order.rs` */
+/**
+ * The byte order of a N64 ROM dump.
+ */
+typedef enum Ipl3Checksum_RomByteOrder {
+    Ipl3Checksum_RomByteOrder_BigEndian, // Usually .z64 files
+    Ipl3Checksum_RomByteOrder_ByteSwapped, // Usually .v64 files
+    Ipl3Checksum_RomByteOrder_LittleEndian, // Usually .n64 files
+} Ipl3Checksum_RomByteOrder;
+
+/**
+ * Detects the byte order of a N64 ROM by looking at the PI configuration word at the start of its header.
+ *
+ * ## Arguments
+ *
+ * * `dst_order` - Pointer where the detected byte order will be set to.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in any byte order. It must have a minimum size of 0x4 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_order` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_detect_byte_order(
+    Ipl3Checksum_RomByteOrder *dst_order,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes
+);
+
+/**
+ * Converts the bytes of a N64 ROM from the given byte order to big endian, in place.
+ *
+ * The conversion is its own inverse, so calling this function on a big endian ROM converts it back to `order`.
+ *
+ * ## Arguments
+ *
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM to convert.
+ * * `order` - The current byte order of `rom_bytes`.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ */
+Ipl3Checksum_Error ipl3checksum_normalize_byte_order(
+    size_t rom_bytes_len,
+    uint8_t *rom_bytes,
+    Ipl3Checksum_RomByteOrder order
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -7,12 +7,83 @@
 
 #include "error.h"
 #include "cickinds.h"
+#include "params.h"
+#include "byte_order.h"
+#include "short_rom.h"
 
 #ifdef __cplusplus
 extern "C"
 {
 #endif
 
+/* This needs to be synced with the Rust equivalent in `src/rs/checksum_value.rs` */
+/**
+ * The checksum of a N64 ROM, made of two 32-bits words.
+ *
+ * It is stored on the ROM header at offset 0x10 in big endian.
+ */
+typedef struct Ipl3Checksum_Checksum {
+    uint32_t word0;
+    uint32_t word1;
+} Ipl3Checksum_Checksum;
+
+/**
+ * Reads the checksum stored on the header of a N64 ROM.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the checksum will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x18 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_checksum_from_header(
+    Ipl3Checksum_Checksum *dst_checksum,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes
+);
+
+/**
+ * Formats the checksum in the usual `XXXXXXXX XXXXXXXX` form.
+ *
+ * ## Arguments
+ *
+ * * `checksum` - The checksum to format.
+ * * `dst_str` - Pointer where the string will be placed. This string must be freed with `ipl3checksum_free_string`.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_str` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_checksum_to_string(
+    Ipl3Checksum_Checksum checksum,
+    char **dst_str
+);
+
+/**
+ * Parses a checksum from either two whitespace separated hexadecimal words (`XXXXXXXX XXXXXXXX`) or a single
+ * 64-bits hexadecimal value (`XXXXXXXXXXXXXXXX`).
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the parsed checksum will be placed.
+ * * `c_str` - The null terminated string to parse.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_checksum_from_string(
+    Ipl3Checksum_Checksum *dst_checksum,
+    const char *c_str
+);
+
 /**
  * Calculates the checksum required by an official CIC of a N64 ROM.
  *
@@ -62,6 +133,161 @@ Ipl3Checksum_Error ipl3checksum_calculate_checksum_autodetect(
     const uint8_t *rom_bytes
 );
 
+/**
+ * Calculates the checksum of a N64 ROM using custom parameters instead of the ones of an official CIC.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of
+ *   `0x1000 + params.bytes_to_check` bytes.
+ * * `params` - The parameters used to calculate the checksum.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_with_params(
+    Ipl3Checksum_Checksum *dst_checksum,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_CicParams params
+);
+
+/**
+ * Calculates the checksum required by an official CIC of a N64 ROM which may be smaller than the range checked by the
+ * CIC.
+ *
+ * The bytes past the end of the ROM are filled as `policy` says, including the header and the IPL3 if the ROM is
+ * smaller than them.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+ * * `kind` - The CIC kind variation used to calculate the checksum.
+ * * `policy` - How to fill the bytes past the end of the ROM.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_with_policy(
+    Ipl3Checksum_Checksum *dst_checksum,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind,
+    Ipl3Checksum_ShortRomPolicy policy
+);
+
+/**
+ * Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+ *
+ * The byte order is detected from the ROM header.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `dst_order` - Pointer where the detected byte order will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum
+ *   size of 0x101000 bytes.
+ * * `kind` - The CIC kind variation used to calculate the checksum.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` and `dst_order` are left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_any_order(
+    Ipl3Checksum_Checksum *dst_checksum,
+    Ipl3Checksum_RomByteOrder *dst_order,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind
+);
+
+/**
+ * Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+ *
+ * This function will try to autodetect both the byte order and the CIC kind automatically.
+ * If it fails to detect them then an error will be returned.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `dst_order` - Pointer where the detected byte order will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum
+ *   size of 0x101000 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_checksum` and `dst_order` are left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_autodetect_any_order(
+    Ipl3Checksum_Checksum *dst_checksum,
+    Ipl3Checksum_RomByteOrder *dst_order,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes
+);
+
+/**
+ * Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+ *
+ * ## Arguments
+ *
+ * * `dst_old_checksum` - Pointer where the checksum that was in the header will be placed.
+ * * `dst_new_checksum` - Pointer where the calculated checksum will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+ *   The calculated checksum is written to its header.
+ * * `kind` - The CIC kind variation used to calculate the checksum.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `rom_bytes` and every `dst_` pointer are left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_fix_checksum(
+    Ipl3Checksum_Checksum *dst_old_checksum,
+    Ipl3Checksum_Checksum *dst_new_checksum,
+    size_t rom_bytes_len,
+    uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind
+);
+
+/**
+ * Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+ *
+ * This function will try to autodetect the CIC kind automatically.
+ * If it fails to detect it then an error will be returned.
+ *
+ * ## Arguments
+ *
+ * * `dst_old_checksum` - Pointer where the checksum that was in the header will be placed.
+ * * `dst_new_checksum` - Pointer where the calculated checksum will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+ *   The calculated checksum is written to its header.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `rom_bytes` and every `dst_` pointer are left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_fix_checksum_autodetect(
+    Ipl3Checksum_Checksum *dst_old_checksum,
+    Ipl3Checksum_Checksum *dst_new_checksum,
+    size_t rom_bytes_len,
+    uint8_t *rom_bytes
+);
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,38 @@
+#ifndef IPL3CHECKSUM_CIC6105_H
+#define IPL3CHECKSUM_CIC6105_H
+#pragma once
+
+#include <stdint.h>
+
+#include "error.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/**
+ * Calculates the response of the CIC 6105 to a challenge.
+ *
+ * ## Arguments
+ *
+ * * `dst_response` - Array where the 30 nibbles of the response will be
+ *   written to, one per byte.
+ * * `challenge` - The 30 nibbles of the challenge, one per byte. Only the
+ *   lower 4 bits of each byte are used.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause
+ *   for failing. If execution fails then `dst_response` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_cic6105_respond(
+    uint8_t (*dst_response)[30],
+    const uint8_t (*challenge)[30]
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
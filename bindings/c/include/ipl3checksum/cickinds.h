@@ -31,6 +31,13 @@ uint32_t ipl3checksum_cickind_get_magic(Ipl3Checksum_CICKind self);
 
 uint32_t ipl3checksum_cickind_get_entrypoint(Ipl3Checksum_CICKind self, uint32_t header_entrypoint);
 
+/**
+ * The minimum size a ROM needs for this kind to checksum it, which is the header, the IPL3 and the checked range.
+ *
+ * The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+ */
+size_t ipl3checksum_cickind_get_min_rom_size(Ipl3Checksum_CICKind self, uint32_t header_entrypoint);
+
 /**
  * Returns the md5 hash for the specified CIC kind.
  *
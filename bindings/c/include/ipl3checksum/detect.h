@@ -7,6 +7,7 @@
 
 #include "error.h"
 #include "cickinds.h"
+#include "byte_order.h"
 
 #ifdef __cplusplus
 extern "C"
@@ -58,6 +59,31 @@ Ipl3Checksum_Error ipl3checksum_detect_cic(
     const uint8_t *rom_bytes
 );
 
+/**
+ * Tries to detect an IPL3 in a ROM of any byte order.
+ *
+ * The byte order is detected from the ROM header.
+ *
+ * ## Arguments
+ *
+ * * `dst_kind` - Pointer where the detected kind will be set to.
+ * * `dst_order` - Pointer where the detected byte order will be set to.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - ROM binary in big endian, byteswapped or little endian format.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause
+ *   for failing. If execution fails then `dst_kind` and `dst_order` are left
+ *   untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_detect_cic_any_order(
+    Ipl3Checksum_CICKind *dst_kind,
+    Ipl3Checksum_RomByteOrder *dst_order,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes
+);
+
 #ifdef __cplusplus
 }
 #endif
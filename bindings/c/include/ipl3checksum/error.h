@@ -21,6 +21,15 @@ typedef enum Ipl3Checksum_Error_Tag {
     Ipl3Checksum_Error_BufferNotBigEnough,
     Ipl3Checksum_Error_BufferSizeIsWrong,
     Ipl3Checksum_Error_UnableToDetectCIC,
+    Ipl3Checksum_Error_InvalidChecksumString,
+    Ipl3Checksum_Error_InvalidForgeRegion,
+    Ipl3Checksum_Error_ForgeRegionTooSmall,
+    Ipl3Checksum_Error_UnableToForgeChecksum,
+    Ipl3Checksum_Error_UnknownByteOrder,
+    Ipl3Checksum_Error_UnknownBootcodeChecksum,
+    Ipl3Checksum_Error_InvalidBootcodeSearch,
+    Ipl3Checksum_Error_UnknownCicRam,
+    Ipl3Checksum_Error_IoError,
 } Ipl3Checksum_Error_Tag;
 
 /**
@@ -60,6 +69,14 @@ typedef struct Ipl3Checksum_Error {
             size_t buffer_len;
             size_t expected_len;
         } BufferSizeIsWrong;
+        struct Ipl3Checksum_Error_Payload_InvalidForgeRegion {
+            size_t region_offset;
+            size_t region_size;
+        } InvalidForgeRegion;
+        struct Ipl3Checksum_Error_Payload_ForgeRegionTooSmall {
+            size_t region_size;
+            size_t expected_size;
+        } ForgeRegionTooSmall;
     } payload;
 } Ipl3Checksum_Error;
 
@@ -0,0 +1,58 @@
+#ifndef IPL3CHECKSUM_FORGE_H
+#define IPL3CHECKSUM_FORGE_H
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+#include "cickinds.h"
+#include "checksum.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/**
+ * Overwrites words of a N64 ROM so its calculated checksum becomes `target` for the given CIC kind.
+ *
+ * Only words inside the `region_size` bytes starting at `region_offset` are modified, which must be inside the
+ * range checked by the CIC kind. The ROM header is not modified.
+ *
+ * Not every target can be reached, since some bits of the result are tied to the data outside the region. Targets
+ * which break those bits are rejected before searching, and only the first 64 positions of the region are tried, so
+ * a failed search takes a few seconds at most.
+ *
+ * ## Arguments
+ *
+ * * `dst_written_start` - Pointer where the offset of the first overwritten byte will be placed.
+ * * `dst_written_end` - Pointer where the offset right after the last overwritten byte will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+ * * `kind` - The CIC kind variation used to calculate the checksum.
+ * * `target` - The checksum the ROM should produce.
+ * * `region_offset` - Offset of the writable region in the ROM. It must be a multiple of 4.
+ * * `region_size` - Size in bytes of the writable region. It must be at least 0x40 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `rom_bytes` and every `dst_` pointer are left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_forge_checksum(
+    size_t *dst_written_start,
+    size_t *dst_written_end,
+    size_t rom_bytes_len,
+    uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind,
+    Ipl3Checksum_Checksum target,
+    size_t region_offset,
+    size_t region_size
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
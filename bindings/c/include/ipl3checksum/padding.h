@@ -0,0 +1,85 @@
+#ifndef IPL3CHECKSUM_PADDING_H
+#define IPL3CHECKSUM_PADDING_H
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+#include "cickinds.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/padding.rs` */
+/**
+ * How the size of a padded ROM is rounded up.
+ */
+typedef enum Ipl3Checksum_PadRounding {
+    Ipl3Checksum_PadRounding_Exact, // No rounding
+    Ipl3Checksum_PadRounding_PowerOfTwo, // Round up to the next power of two
+    Ipl3Checksum_PadRounding_MultipleOfMiB, // Round up to the next multiple of 1 MiB
+} Ipl3Checksum_PadRounding;
+
+/**
+ * Calculates the size a N64 ROM should be padded to.
+ *
+ * The result is never smaller than the ROM itself nor than the minimum size needed by the CIC kind, see
+ * `ipl3checksum_cickind_get_min_rom_size`. If the ROM was smaller than said minimum then its checksum has to be fixed
+ * again after padding it.
+ *
+ * ## Arguments
+ *
+ * * `dst_size` - Pointer where the padded size will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+ * * `kind` - The CIC kind variation the ROM is meant for.
+ * * `target_size` - The desired size. Pass 0 to only pad to the minimum size needed by the CIC kind.
+ * * `rounding` - How the size is rounded up after applying the minimums.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_size` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_padded_rom_size(
+    size_t *dst_size,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind,
+    size_t target_size,
+    Ipl3Checksum_PadRounding rounding
+);
+
+/**
+ * Calculates the size a N64 ROM can be trimmed to.
+ *
+ * The trailing run of 0x00 or 0xFF bytes is removed, keeping the size a multiple of 4 and never trimming inside the
+ * range checked by the CIC kind, so the checksum is not affected.
+ *
+ * ## Arguments
+ *
+ * * `dst_size` - Pointer where the trimmed size will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+ * * `kind` - The CIC kind variation the ROM is meant for.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_size` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_trimmed_rom_size(
+    size_t *dst_size,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes,
+    Ipl3Checksum_CICKind kind
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
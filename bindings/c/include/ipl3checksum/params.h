@@ -0,0 +1,54 @@
+#ifndef IPL3CHECKSUM_PARAMS_H
+#define IPL3CHECKSUM_PARAMS_H
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#include "cickinds.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/params.rs` */
+/**
+ * How the registers are combined into the checksum once every word has been processed.
+ */
+typedef enum Ipl3Checksum_FinalizationVariant {
+    Ipl3Checksum_FinalizationVariant_Xor, // Used by most CIC kinds
+    Ipl3Checksum_FinalizationVariant_Add, // Used by CIC_X103 and CIC_5101
+    Ipl3Checksum_FinalizationVariant_Mul, // Used by CIC_X106
+} Ipl3Checksum_FinalizationVariant;
+
+/* This needs to be synced with the Rust equivalent in `src/rs/params.rs` */
+/**
+ * Everything that changes between the checksum algorithms of the different IPL3s.
+ */
+typedef struct Ipl3Checksum_CicParams {
+    /* Seed value set by the PIF ROM before the CPU (and the IPL3) is executed. */
+    uint32_t seed;
+    /* Magic value hardcoded inside the IPL3. */
+    uint32_t magic;
+    /* How many bytes after the IPL3 are checked. It should be a multiple of 4. */
+    size_t bytes_to_check;
+    /* How the registers are combined into the final checksum. */
+    Ipl3Checksum_FinalizationVariant finalization;
+    /* Whether each word is mixed with a word of the table at offset 0x750 of the IPL3, like CIC_X105 does. */
+    bool uses_x105_table;
+} Ipl3Checksum_CicParams;
+
+/**
+ * Every parameter used by the IPL3 of the given kind to calculate the checksum.
+ *
+ * The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+ */
+Ipl3Checksum_CicParams ipl3checksum_cickind_get_params(Ipl3Checksum_CICKind self, uint32_t header_entrypoint);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,63 @@
+#ifndef IPL3CHECKSUM_READER_H
+#define IPL3CHECKSUM_READER_H
+#pragma once
+
+#include "error.h"
+#include "cickinds.h"
+#include "checksum.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/**
+ * Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+ *
+ * Only the header, the IPL3 and the range checked by the CIC kind are read from the file.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `path` - The null terminated path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+ * * `kind` - The CIC kind variation used to calculate the checksum.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   `Ipl3Checksum_Error_IoError` is returned if the file could not be opened or read.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_from_path(
+    Ipl3Checksum_Checksum *dst_checksum,
+    const char *path,
+    Ipl3Checksum_CICKind kind
+);
+
+/**
+ * Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+ *
+ * This function will try to autodetect the CIC kind automatically.
+ * If it fails to detect it then an error will be returned.
+ *
+ * ## Arguments
+ *
+ * * `dst_checksum` - Pointer where the calculated checksum will be placed.
+ * * `path` - The null terminated path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   `Ipl3Checksum_Error_IoError` is returned if the file could not be opened or read.
+ *   If execution fails then `dst_checksum` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_calculate_checksum_autodetect_from_path(
+    Ipl3Checksum_Checksum *dst_checksum,
+    const char *path
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
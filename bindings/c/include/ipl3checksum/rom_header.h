@@ -0,0 +1,132 @@
+#ifndef IPL3CHECKSUM_ROM_HEADER_H
+#define IPL3CHECKSUM_ROM_HEADER_H
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "error.h"
+#include "checksum.h"
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/rom_header.rs` */
+/**
+ * The header of a N64 ROM, stored on its first 0x40 bytes.
+ */
+typedef struct Ipl3Checksum_RomHeader {
+    /* Configuration of the PI bus for the cartridge domain, its most significant byte is always 0x80. */
+    uint32_t pi_bsd_domain1_config;
+    /* Value used to compute the CPU clock rate, ignored on retail consoles. */
+    uint32_t clock_rate;
+    /* Address where the IPL3 copies the code of the ROM to, and jumps to once it is done. Also known as entrypoint. */
+    uint32_t boot_address;
+    /* The libultra release the ROM was built with. The last byte is the revision letter. */
+    uint32_t libultra_release;
+    /* The checksum checked by the CIC, also known as CRC1 and CRC2. */
+    Ipl3Checksum_Checksum checksum;
+    /* Unused. */
+    uint8_t reserved0[8];
+    /* Name of the game, in ASCII or Shift-JIS, padded with spaces or zeroes. Not null terminated. */
+    uint8_t image_name[20];
+    /* Unused. */
+    uint8_t reserved1[7];
+    /* The kind of media the game is distributed on, as an ASCII character. For example `N` for cartridges. */
+    uint8_t category_code;
+    /* Two ASCII characters that identify the game. */
+    uint8_t game_id[2];
+    /* The region the game was released on, as an ASCII character. For example `E` for North America. */
+    uint8_t destination_code;
+    /* Revision of the ROM, starting at 0. */
+    uint8_t rom_version;
+} Ipl3Checksum_RomHeader;
+
+/**
+ * Parses the header of a N64 ROM.
+ *
+ * ## Arguments
+ *
+ * * `dst_header` - Pointer where the parsed header will be placed.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_header` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_rom_header_from_bytes(
+    Ipl3Checksum_RomHeader *dst_header,
+    size_t rom_bytes_len,
+    const uint8_t *rom_bytes
+);
+
+/**
+ * Writes a header to a N64 ROM, replacing its first 0x40 bytes.
+ *
+ * Keep in mind the checksum is not recalculated.
+ *
+ * ## Arguments
+ *
+ * * `header` - The header to write.
+ * * `rom_bytes_len` - Bytes length of the input `rom_bytes`.
+ * * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `rom_bytes` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_rom_header_write_to_rom(
+    Ipl3Checksum_RomHeader header,
+    size_t rom_bytes_len,
+    uint8_t *rom_bytes
+);
+
+/**
+ * Formats a multiline summary of the fields of the header.
+ *
+ * ## Arguments
+ *
+ * * `header` - The header to format.
+ * * `dst_str` - Pointer where the string will be placed. This string must be freed with `ipl3checksum_free_string`.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_str` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_rom_header_to_string(
+    Ipl3Checksum_RomHeader header,
+    char **dst_str
+);
+
+/**
+ * Decodes the image name of the header as UTF-8, without its trailing padding.
+ *
+ * Besides ASCII, only the Shift-JIS characters used in practice by image names are supported. Any other character is
+ * decoded as U+FFFD.
+ *
+ * ## Arguments
+ *
+ * * `header` - The header whose image name will be decoded.
+ * * `dst_str` - Pointer where the string will be placed. This string must be freed with `ipl3checksum_free_string`.
+ *
+ * ## Return
+ *
+ * * `Ipl3Checksum_Error` indicating either a successful execution or the cause for failing.
+ *   If execution fails then `dst_str` is left untouched.
+ */
+Ipl3Checksum_Error ipl3checksum_rom_header_decode_image_name(
+    Ipl3Checksum_RomHeader header,
+    char **dst_str
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
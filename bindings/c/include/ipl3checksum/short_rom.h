@@ -0,0 +1,28 @@
+#ifndef IPL3CHECKSUM_SHORT_ROM_H
+#define IPL3CHECKSUM_SHORT_ROM_H
+#pragma once
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+/* This needs to be synced with the Rust equivalent in `src/rs/short_rom.rs` */
+/**
+ * What to do when a ROM is smaller than the range checked by the CIC.
+ *
+ * The IPL3 doesn't know the size of the cartridge, so on real hardware it checksums whatever the bus returns past the
+ * end of the ROM.
+ */
+typedef enum Ipl3Checksum_ShortRomPolicy {
+    Ipl3Checksum_ShortRomPolicy_Error, // Fail with `Ipl3Checksum_Error_BufferNotBigEnough`
+    Ipl3Checksum_ShortRomPolicy_ZeroFill, // Every byte past the end reads as 0x00
+    Ipl3Checksum_ShortRomPolicy_FfFill, // Every byte past the end reads as 0xFF
+    Ipl3Checksum_ShortRomPolicy_OpenBus, // Every halfword past the end reads as the low halfword of its own offset
+} Ipl3Checksum_ShortRomPolicy;
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
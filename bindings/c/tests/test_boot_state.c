@@ -0,0 +1,170 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#include "ipl3checksum.h"
+
+#include <assert.h>
+#include <stdio.h>
+
+#include "utils.h"
+
+bool get_cic_kind(Ipl3Checksum_CICKind *dst_kind, const char *cic_kind_name) {
+    fprintf(stderr, "Passed CIC kind: '%s'\n", cic_kind_name);
+
+    Ipl3Checksum_Error err = ipl3checksum_cickind_from_name(dst_kind, cic_kind_name);
+
+    if (err.tag != Ipl3Checksum_Error_Okay) {
+        fprintf(stderr, "Passed CIC kind was not valid: %s\n", get_ipl3checksum_error_str(err));
+        return false;
+    }
+
+    if (!eprint_cickind_name(*dst_kind)) {
+        return false;
+    }
+
+    return true;
+}
+
+void print_usage(int argc, char *argv[]) {
+    (void)argc;
+
+    fprintf(stderr, "Usage: %s bin_file cic_kind\n", argv[0]);
+    fprintf(stderr, "\n");
+    fprintf(stderr, "This programs checks the boot state of a big endian binary file with a given cic kind\n");
+}
+
+int main(int argc, char *argv[]) {
+    int ret = 0;
+
+    if (argc < 3) {
+        print_usage(argc, argv);
+        return -1;
+    }
+
+    fprintf(stderr, "Running ipl3checksum version %s\n", ipl3checksum_version_str);
+
+    const char *bin_path = argv[1];
+    const char *cic_kind_name = argv[2];
+
+    size_t bin_size = 0;
+    uint8_t *bin = read_binary_file(bin_path, &bin_size);
+    assert(bin_size > 0x1000);
+    assert(bin != NULL);
+
+    Ipl3Checksum_CICKind kind;
+    if (!get_cic_kind(&kind, cic_kind_name)) {
+        ret++;
+        goto cleanup;
+    }
+
+    uint32_t header_entrypoint = read_be_word(bin, 0x08);
+    Ipl3Checksum_BootState state = ipl3checksum_boot_state_for_kind(kind, header_entrypoint, Ipl3Checksum_TvType_Ntsc);
+
+    fprintf(stderr, "Entrypoint: %08X\n", state.entrypoint);
+    fprintf(stderr, "Registers: s3=%X s4=%X s5=%X s6=%X s7=%X\n", state.rom_type, state.tv_type, state.reset_type, state.seed, state.version);
+
+    {
+        if (state.kind != kind) {
+            fprintf(stderr, "Kind doesn't match\n");
+            ret++;
+        }
+        if ((state.rom_type != 0) || (state.tv_type != Ipl3Checksum_TvType_Ntsc) || (state.reset_type != 0) || (state.version != 0)) {
+            fprintf(stderr, "Unexpected registers for a cold boot of a cartridge\n");
+            ret++;
+        }
+        if (state.seed != ipl3checksum_cickind_get_seed(kind)) {
+            fprintf(stderr, "Seed doesn't match\n");
+            ret++;
+        }
+        if (state.entrypoint != ipl3checksum_cickind_get_entrypoint(kind, header_entrypoint)) {
+            fprintf(stderr, "Entrypoint doesn't match\n");
+            ret++;
+        }
+        if ((state.rom_copy_offset != 0x1000) || (state.rom_copy_size != 0x100000)) {
+            fprintf(stderr, "Unexpected copied range: %X %X\n", state.rom_copy_offset, state.rom_copy_size);
+            ret++;
+        }
+        if (kind == Ipl3Checksum_CICKind_CIC_X105) {
+            if ((state.table_copy_address != 0xA0000004) || (state.table_copy_offset != 0x554) || (state.table_copy_size != 0x330)) {
+                fprintf(stderr, "Unexpected table copy: %08X %X %X\n", state.table_copy_address, state.table_copy_offset, state.table_copy_size);
+                ret++;
+            }
+        } else if (state.table_copy_size != 0) {
+            fprintf(stderr, "Unexpected table copy size: %X\n", state.table_copy_size);
+            ret++;
+        }
+        if (state.rdram_size != 0x400000) {
+            fprintf(stderr, "Unexpected RDRAM size: %X\n", state.rdram_size);
+            ret++;
+        }
+    }
+
+    {
+        Ipl3Checksum_BootState pal = ipl3checksum_boot_state_for_kind(kind, header_entrypoint, Ipl3Checksum_TvType_Pal);
+
+        if ((pal.tv_type != Ipl3Checksum_TvType_Pal) || (pal.version != 6)) {
+            fprintf(stderr, "Unexpected registers for a PAL console: s4=%X s7=%X\n", pal.tv_type, pal.version);
+            ret++;
+        }
+    }
+
+    {
+        // Emulate the Expansion Pak
+        state.rdram_size = 0x800000;
+
+        Ipl3Checksum_MemoryWrite writes[9];
+        size_t writes_len = 0;
+        Ipl3Checksum_Error err = ipl3checksum_boot_state_memory_writes(state, &writes, &writes_len);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to get the memory writes: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        fprintf(stderr, "Memory writes: %zu\n", writes_len);
+
+        bool is_x105 = kind == Ipl3Checksum_CICKind_CIC_X105;
+        if (writes_len != (is_x105 ? 9 : 1)) {
+            fprintf(stderr, "Unexpected amount of memory writes\n");
+            ret++;
+        } else {
+            // osMemSize
+            if ((writes[0].address != (is_x105 ? 0x800003F0 : 0x80000318)) || (writes[0].value != 0x800000)) {
+                fprintf(stderr, "Unexpected osMemSize write: %08X %08X\n", writes[0].address, writes[0].value);
+                ret++;
+            }
+            if (is_x105 && ((writes[1].address != 0xA4001004) || (writes[8].address != 0xA4001020))) {
+                fprintf(stderr, "Unexpected SP IMEM writes\n");
+                ret++;
+            }
+        }
+    }
+
+    {
+        // The dummy files don't contain a real IPL3, so the CIC kind can't be detected
+        Ipl3Checksum_BootState detected;
+        Ipl3Checksum_Error err = ipl3checksum_boot_state_for_rom(&detected, bin_size, bin, Ipl3Checksum_TvType_Pal);
+
+        if (err.tag != Ipl3Checksum_Error_UnableToDetectCIC) {
+            fprintf(stderr, "Unexpected result trying to get the boot state of the ROM: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+
+        err = ipl3checksum_boot_state_for_rom(&detected, 0x800, bin, Ipl3Checksum_TvType_Pal);
+        if (err.tag != Ipl3Checksum_Error_BufferNotBigEnough) {
+            fprintf(stderr, "Unexpected result trying to get the boot state of a short ROM: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+
+        err = ipl3checksum_boot_state_for_rom(NULL, bin_size, bin, Ipl3Checksum_TvType_Pal);
+        if (err.tag != Ipl3Checksum_Error_NullPointer) {
+            fprintf(stderr, "Unexpected result passing a null pointer: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+    }
+
+cleanup:
+    free(bin);
+
+    return ret;
+}
@@ -0,0 +1,139 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#include "ipl3checksum.h"
+
+#include <assert.h>
+#include <inttypes.h>
+#include <stdio.h>
+
+#include "utils.h"
+
+bool get_cic_kind(Ipl3Checksum_CICKind *dst_kind, const char *cic_kind_name) {
+    fprintf(stderr, "Passed CIC kind: '%s'\n", cic_kind_name);
+
+    Ipl3Checksum_Error err = ipl3checksum_cickind_from_name(dst_kind, cic_kind_name);
+
+    if (err.tag != Ipl3Checksum_Error_Okay) {
+        fprintf(stderr, "Passed CIC kind was not valid: %s\n", get_ipl3checksum_error_str(err));
+        return false;
+    }
+
+    if (!eprint_cickind_name(*dst_kind)) {
+        return false;
+    }
+
+    return true;
+}
+
+/* The boot code checksums of a zeroed IPL3, like the one of the dummy files */
+uint64_t get_zeroed_bootcode_checksum(Ipl3Checksum_CICKind kind) {
+    switch (kind) {
+        case Ipl3Checksum_CICKind_CIC_6101:
+        case Ipl3Checksum_CICKind_CIC_6102_7101:
+        case Ipl3Checksum_CICKind_CIC_7102:
+            return 0x2982AD8C201D;
+        case Ipl3Checksum_CICKind_CIC_X103:
+            return 0xB30F78D0CAD9;
+        case Ipl3Checksum_CICKind_CIC_X105:
+            return 0x31085BBE1381;
+        case Ipl3Checksum_CICKind_CIC_X106:
+            return 0xBB2AF8DB9C3A;
+        case Ipl3Checksum_CICKind_CIC_5101:
+            return 0x3B492E705C47;
+    }
+    return 0;
+}
+
+void print_usage(int argc, char *argv[]) {
+    (void)argc;
+
+    fprintf(stderr, "Usage: %s bin_file cic_kind\n", argv[0]);
+    fprintf(stderr, "\n");
+    fprintf(stderr, "This programs calculates and validates the boot code checksum of the IPL3 of a big endian binary file with a given cic kind\n");
+}
+
+int main(int argc, char *argv[]) {
+    int ret = 0;
+
+    if (argc < 3) {
+        print_usage(argc, argv);
+        return -1;
+    }
+
+    fprintf(stderr, "Running ipl3checksum version %s\n", ipl3checksum_version_str);
+
+    const char *bin_path = argv[1];
+    const char *cic_kind_name = argv[2];
+
+    size_t bin_size = 0;
+    uint8_t *bin = read_binary_file(bin_path, &bin_size);
+    assert(bin_size > 0x1000);
+    assert(bin != NULL);
+
+    Ipl3Checksum_CICKind kind;
+    if (!get_cic_kind(&kind, cic_kind_name)) {
+        ret++;
+        goto cleanup;
+    }
+
+    const uint8_t *ipl3 = &bin[0x40];
+
+    {
+        uint64_t expected_checksum = get_zeroed_bootcode_checksum(kind);
+        uint64_t checksum;
+        Ipl3Checksum_Error err = ipl3checksum_calculate_bootcode_checksum(&checksum, 0xFC0, ipl3, kind);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to calculate the boot code checksum: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        fprintf(stderr, "Boot code checksum: %012" PRIX64 "\n", checksum);
+
+        if (checksum != expected_checksum) {
+            fprintf(stderr, "Boot code checksum doesn't match, expected %012" PRIX64 "\n", expected_checksum);
+            ret++;
+        }
+    }
+
+    {
+        // The dummy files don't contain a real IPL3, so it is never valid
+        bool valid = true;
+        Ipl3Checksum_Error err = ipl3checksum_validate_bootcode(&valid, 0xFC0, ipl3, kind);
+
+        if (kind == Ipl3Checksum_CICKind_CIC_5101) {
+            if (err.tag != Ipl3Checksum_Error_UnknownBootcodeChecksum) {
+                fprintf(stderr, "Unexpected result trying to validate the boot code: %s\n", get_ipl3checksum_error_str(err));
+                ret++;
+            }
+        } else if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to validate the boot code: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if (valid) {
+            fprintf(stderr, "A zeroed IPL3 was reported as valid\n");
+            ret++;
+        }
+    }
+
+    {
+        uint64_t checksum;
+        Ipl3Checksum_Error err = ipl3checksum_calculate_bootcode_checksum(&checksum, 0x1000, ipl3, kind);
+
+        if (err.tag != Ipl3Checksum_Error_BufferSizeIsWrong) {
+            fprintf(stderr, "Unexpected result trying to calculate the boot code checksum of a wrong size: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+
+        err = ipl3checksum_calculate_bootcode_checksum(NULL, 0xFC0, ipl3, kind);
+        if (err.tag != Ipl3Checksum_Error_NullPointer) {
+            fprintf(stderr, "Unexpected result passing a null pointer: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+    }
+
+cleanup:
+    free(bin);
+
+    return ret;
+}
@@ -5,6 +5,7 @@
 
 #include <assert.h>
 #include <stdio.h>
+#include <string.h>
 
 #include "utils.h"
 
@@ -62,6 +63,73 @@ int main(int argc, char *argv[]) {
 
     fprintf(stderr, "Expected checksum: %08X %08X\n", expected_checksum0, expected_checksum1);
 
+    {
+        Ipl3Checksum_Checksum header_checksum;
+        Ipl3Checksum_Error err = ipl3checksum_checksum_from_header(&header_checksum, bin_size, bin);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to read the header checksum: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        if ((header_checksum.word0 != expected_checksum0) || (header_checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Header checksum doesn't match: %08X %08X\n", header_checksum.word0, header_checksum.word1);
+            ret++;
+            goto cleanup;
+        }
+
+        char *checksum_str = NULL;
+        err = ipl3checksum_checksum_to_string(header_checksum, &checksum_str);
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to format the header checksum: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+
+        Ipl3Checksum_Checksum parsed_checksum;
+        err = ipl3checksum_checksum_from_string(&parsed_checksum, checksum_str);
+        ipl3checksum_free_string(checksum_str);
+        if ((err.tag != Ipl3Checksum_Error_Okay) || (parsed_checksum.word0 != header_checksum.word0) || (parsed_checksum.word1 != header_checksum.word1)) {
+            fprintf(stderr, "Checksum string round trip failed\n");
+            ret++;
+            goto cleanup;
+        }
+    }
+
+    {
+        Ipl3Checksum_RomHeader header;
+        Ipl3Checksum_Error err = ipl3checksum_rom_header_from_bytes(&header, bin_size, bin);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to parse the header: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        if ((header.boot_address != read_be_word(bin, 0x08)) || (header.checksum.word0 != expected_checksum0) || (header.checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Parsed header doesn't match\n");
+            ret++;
+            goto cleanup;
+        }
+
+        char *header_str = NULL;
+        err = ipl3checksum_rom_header_to_string(header, &header_str);
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to format the header: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        fprintf(stderr, "%s\n", header_str);
+        ipl3checksum_free_string(header_str);
+
+        uint8_t header_bytes[0x40] = { 0 };
+        err = ipl3checksum_rom_header_write_to_rom(header, sizeof(header_bytes), header_bytes);
+        if ((err.tag != Ipl3Checksum_Error_Okay) || (memcmp(header_bytes, bin, sizeof(header_bytes)) != 0)) {
+            fprintf(stderr, "Header round trip failed\n");
+            ret++;
+            goto cleanup;
+        }
+    }
+
     {
         uint32_t checksum0;
         uint32_t checksum1;
@@ -82,6 +150,113 @@ int main(int argc, char *argv[]) {
         }
     }
 
+    {
+        uint32_t header_entrypoint = read_be_word(bin, 0x08);
+        Ipl3Checksum_CicParams params = ipl3checksum_cickind_get_params(kind, header_entrypoint);
+        Ipl3Checksum_Checksum checksum;
+
+        Ipl3Checksum_Error err = ipl3checksum_calculate_checksum_with_params(&checksum, bin_size, bin, params);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to compute the checksum with params: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if ((checksum.word0 != expected_checksum0) || (checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Checksum computed with params doesn't match: %08X %08X\n", checksum.word0, checksum.word1);
+            ret++;
+        }
+    }
+
+    {
+        // The PI configuration word is not part of the checked bytes, so it can be set to allow detecting the byte order
+        bin[0] = 0x80;
+        bin[1] = 0x37;
+        bin[2] = 0x12;
+        bin[3] = 0x40;
+        ipl3checksum_normalize_byte_order(bin_size, bin, Ipl3Checksum_RomByteOrder_LittleEndian);
+
+        Ipl3Checksum_Checksum checksum;
+        Ipl3Checksum_RomByteOrder order;
+        Ipl3Checksum_Error err = ipl3checksum_calculate_checksum_any_order(&checksum, &order, bin_size, bin, kind);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to compute the checksum of a little endian ROM: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if ((order != Ipl3Checksum_RomByteOrder_LittleEndian) || (checksum.word0 != expected_checksum0) || (checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Checksum of a little endian ROM doesn't match: %08X %08X\n", checksum.word0, checksum.word1);
+            ret++;
+        }
+
+        ipl3checksum_normalize_byte_order(bin_size, bin, Ipl3Checksum_RomByteOrder_LittleEndian);
+    }
+
+    {
+        Ipl3Checksum_Checksum checksum;
+        Ipl3Checksum_Error err = ipl3checksum_calculate_checksum_with_policy(&checksum, bin_size, bin, kind, Ipl3Checksum_ShortRomPolicy_OpenBus);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to compute the checksum with a short ROM policy: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if ((checksum.word0 != expected_checksum0) || (checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Checksum computed with a short ROM policy doesn't match: %08X %08X\n", checksum.word0, checksum.word1);
+            ret++;
+        }
+    }
+
+    {
+        size_t padded_size = 0;
+        size_t trimmed_size = 0;
+        Ipl3Checksum_Error err = ipl3checksum_padded_rom_size(&padded_size, bin_size, bin, kind, 0, Ipl3Checksum_PadRounding_PowerOfTwo);
+
+        if (err.tag == Ipl3Checksum_Error_Okay) {
+            err = ipl3checksum_trimmed_rom_size(&trimmed_size, bin_size, bin, kind);
+        }
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to compute the padded and trimmed sizes: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if ((padded_size < bin_size) || (trimmed_size > bin_size)) {
+            fprintf(stderr, "Wrong padded or trimmed size: 0x%zX 0x%zX\n", padded_size, trimmed_size);
+            ret++;
+        }
+        fprintf(stderr, "Padded size: 0x%zX. Trimmed size: 0x%zX\n", padded_size, trimmed_size);
+    }
+
+    {
+        Ipl3Checksum_Checksum checksum;
+        Ipl3Checksum_Error err = ipl3checksum_calculate_checksum_from_path(&checksum, bin_path, kind);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to compute the checksum from the file path: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if ((checksum.word0 != expected_checksum0) || (checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "Checksum computed from the file path doesn't match: %08X %08X\n", checksum.word0, checksum.word1);
+            ret++;
+        }
+    }
+
+    {
+        Ipl3Checksum_Checksum target = { expected_checksum0, expected_checksum1 };
+        size_t written_start = 0;
+        size_t written_end = 0;
+
+        Ipl3Checksum_Error err = ipl3checksum_forge_checksum(&written_start, &written_end, bin_size, bin, kind, target, 0x80000, 0x400);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to forge the checksum: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        fprintf(stderr, "Forged bytes: 0x%zX..0x%zX\n", written_start, written_end);
+
+        uint32_t checksum0;
+        uint32_t checksum1;
+        err = ipl3checksum_calculate_checksum(&checksum0, &checksum1, bin_size, bin, kind);
+        if ((err.tag != Ipl3Checksum_Error_Okay) || (checksum0 != expected_checksum0) || (checksum1 != expected_checksum1)) {
+            fprintf(stderr, "Forged checksum doesn't match\n");
+            ret++;
+        }
+    }
+
 cleanup:
     free(bin);
 
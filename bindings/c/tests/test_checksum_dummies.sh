@@ -11,5 +11,8 @@ for i in tests/dummytests/*/*; do
     echo "CIC kind" $CIC_KIND
 
     ./bindings/c/tests/test_checksum.elf $i $CIC_KIND
+    ./bindings/c/tests/test_fix_checksum.elf $i $CIC_KIND
+    ./bindings/c/tests/test_bootcode.elf $i $CIC_KIND
+    ./bindings/c/tests/test_boot_state.elf $i $CIC_KIND
     echo
 done
@@ -0,0 +1,82 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#include "ipl3checksum.h"
+
+#include <stdio.h>
+#include <string.h>
+
+#include "utils.h"
+
+static const uint8_t challenges[][30] = {
+    {
+        0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0x0,
+        0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0xD,
+    },
+    {
+        0x3, 0xA, 0x1, 0x8, 0xF, 0x6, 0xD, 0x4, 0xB, 0x2, 0x9, 0x0, 0x7, 0xE, 0x5, 0xC, 0x3,
+        0xA, 0x1, 0x8, 0xF, 0x6, 0xD, 0x4, 0xB, 0x2, 0x9, 0x0, 0x7, 0xE,
+    },
+    {
+        0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0,
+        0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0,
+    },
+};
+
+static const uint8_t expected_responses[][30] = {
+    {
+        0xB, 0x4, 0x8, 0xB, 0x9, 0x8, 0xA, 0xB, 0x7, 0xE, 0x8, 0x3, 0x3, 0x8, 0x2, 0x5, 0x5,
+        0xA, 0x2, 0x9, 0xD, 0xC, 0x4, 0x1, 0xF, 0x6, 0x0, 0xB, 0x1, 0x8,
+    },
+    {
+        0xA, 0xA, 0xD, 0xB, 0xA, 0x6, 0xF, 0xD, 0xA, 0x2, 0x7, 0x1, 0xA, 0xE, 0xF, 0x5, 0x4,
+        0x0, 0x9, 0x1, 0xC, 0x4, 0xF, 0xD, 0xA, 0x2, 0x7, 0x1, 0xA, 0xE,
+    },
+    // Only the lower nibble is used
+    {
+        0xB, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9,
+        0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF, 0x9, 0xF,
+    },
+};
+
+int main(void) {
+    int ret = 0;
+
+    fprintf(stderr, "Running ipl3checksum version %s\n", ipl3checksum_version_str);
+
+    for (size_t i = 0; i < sizeof(challenges) / sizeof(challenges[0]); i++) {
+        uint8_t response[30];
+        Ipl3Checksum_Error err = ipl3checksum_cic6105_respond(&response, &challenges[i]);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to respond challenge %zu: %s\n", i, get_ipl3checksum_error_str(err));
+            ret++;
+            continue;
+        }
+
+        if (memcmp(response, expected_responses[i], sizeof(response)) != 0) {
+            fprintf(stderr, "Response to challenge %zu doesn't match:", i);
+            for (size_t j = 0; j < sizeof(response); j++) {
+                fprintf(stderr, " %X", response[j]);
+            }
+            fprintf(stderr, "\n");
+            ret++;
+        }
+    }
+
+    {
+        uint8_t response[30];
+        Ipl3Checksum_Error err = ipl3checksum_cic6105_respond(&response, NULL);
+
+        if (err.tag != Ipl3Checksum_Error_NullPointer) {
+            fprintf(stderr, "Unexpected result passing a null pointer: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+    }
+
+    if (ret == 0) {
+        fprintf(stderr, "Every response matches\n");
+    }
+
+    return ret;
+}
@@ -0,0 +1,134 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#include "ipl3checksum.h"
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "utils.h"
+
+bool get_cic_kind(Ipl3Checksum_CICKind *dst_kind, const char *cic_kind_name) {
+    fprintf(stderr, "Passed CIC kind: '%s'\n", cic_kind_name);
+
+    Ipl3Checksum_Error err = ipl3checksum_cickind_from_name(dst_kind, cic_kind_name);
+
+    if (err.tag != Ipl3Checksum_Error_Okay) {
+        fprintf(stderr, "Passed CIC kind was not valid: %s\n", get_ipl3checksum_error_str(err));
+        return false;
+    }
+
+    if (!eprint_cickind_name(*dst_kind)) {
+        return false;
+    }
+
+    return true;
+}
+
+void print_usage(int argc, char *argv[]) {
+    (void)argc;
+
+    fprintf(stderr, "Usage: %s bin_file cic_kind\n", argv[0]);
+    fprintf(stderr, "\n");
+    fprintf(stderr, "This programs breaks the checksum on the header of a big endian binary file and fixes it back with a given cic kind\n");
+}
+
+int main(int argc, char *argv[]) {
+    int ret = 0;
+
+    if (argc < 3) {
+        print_usage(argc, argv);
+        return -1;
+    }
+
+    fprintf(stderr, "Running ipl3checksum version %s\n", ipl3checksum_version_str);
+
+    const char *bin_path = argv[1];
+    const char *cic_kind_name = argv[2];
+
+    size_t bin_size = 0;
+    uint8_t *bin = read_binary_file(bin_path, &bin_size);
+    assert(bin_size > 0);
+    assert(bin != NULL);
+
+    Ipl3Checksum_CICKind kind;
+    if (!get_cic_kind(&kind, cic_kind_name)) {
+        ret++;
+        goto cleanup;
+    }
+
+    uint32_t expected_checksum0 = read_be_word(bin, 0x10);
+    uint32_t expected_checksum1 = read_be_word(bin, 0x14);
+
+    fprintf(stderr, "Expected checksum: %08X %08X\n", expected_checksum0, expected_checksum1);
+
+    {
+        // Break the checksum on the header
+        bin[0x10] ^= 0xFF;
+        bin[0x17] ^= 0xFF;
+        uint32_t broken_checksum0 = read_be_word(bin, 0x10);
+        uint32_t broken_checksum1 = read_be_word(bin, 0x14);
+
+        Ipl3Checksum_Checksum old_checksum;
+        Ipl3Checksum_Checksum new_checksum;
+        Ipl3Checksum_Error err = ipl3checksum_fix_checksum(&old_checksum, &new_checksum, bin_size, bin, kind);
+
+        if (err.tag != Ipl3Checksum_Error_Okay) {
+            fprintf(stderr, "Error trying to fix the checksum: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+            goto cleanup;
+        }
+        fprintf(stderr, "Old checksum: %08X %08X\n", old_checksum.word0, old_checksum.word1);
+        fprintf(stderr, "New checksum: %08X %08X\n", new_checksum.word0, new_checksum.word1);
+
+        if ((old_checksum.word0 != broken_checksum0) || (old_checksum.word1 != broken_checksum1)) {
+            fprintf(stderr, "Old checksum doesn't match the broken one\n");
+            ret++;
+        }
+        if ((new_checksum.word0 != expected_checksum0) || (new_checksum.word1 != expected_checksum1)) {
+            fprintf(stderr, "New checksum doesn't match\n");
+            ret++;
+        }
+        if ((read_be_word(bin, 0x10) != expected_checksum0) || (read_be_word(bin, 0x14) != expected_checksum1)) {
+            fprintf(stderr, "The header was not fixed\n");
+            ret++;
+        }
+    }
+
+    {
+        // The dummy files don't contain a real IPL3, so the CIC kind can't be autodetected and the ROM is left untouched
+        uint8_t header_copy[0x40];
+        memcpy(header_copy, bin, sizeof(header_copy));
+        bin[0x10] ^= 0xFF;
+
+        Ipl3Checksum_Checksum old_checksum;
+        Ipl3Checksum_Checksum new_checksum;
+        Ipl3Checksum_Error err = ipl3checksum_fix_checksum_autodetect(&old_checksum, &new_checksum, bin_size, bin);
+
+        bin[0x10] ^= 0xFF;
+        if (err.tag != Ipl3Checksum_Error_UnableToDetectCIC) {
+            fprintf(stderr, "Unexpected result trying to fix the checksum with autodetection: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        } else if (memcmp(header_copy, bin, sizeof(header_copy)) != 0) {
+            fprintf(stderr, "The header was modified on a failed fix\n");
+            ret++;
+        }
+    }
+
+    {
+        Ipl3Checksum_Checksum old_checksum;
+        Ipl3Checksum_Checksum new_checksum;
+        Ipl3Checksum_Error err = ipl3checksum_fix_checksum(&old_checksum, &new_checksum, 0x1000, bin, kind);
+
+        if (err.tag != Ipl3Checksum_Error_BufferNotBigEnough) {
+            fprintf(stderr, "Unexpected result trying to fix the checksum of a short ROM: %s\n", get_ipl3checksum_error_str(err));
+            ret++;
+        }
+    }
+
+cleanup:
+    free(bin);
+
+    return ret;
+}
@@ -53,6 +53,15 @@ const char *const ipl3checksum_error_str[] = {
     [Ipl3Checksum_Error_BufferNotBigEnough] = "BufferNotBigEnough",
     [Ipl3Checksum_Error_BufferSizeIsWrong] = "BufferSizeIsWrong",
     [Ipl3Checksum_Error_UnableToDetectCIC] = "UnableToDetectCIC",
+    [Ipl3Checksum_Error_InvalidChecksumString] = "InvalidChecksumString",
+    [Ipl3Checksum_Error_InvalidForgeRegion] = "InvalidForgeRegion",
+    [Ipl3Checksum_Error_ForgeRegionTooSmall] = "ForgeRegionTooSmall",
+    [Ipl3Checksum_Error_UnableToForgeChecksum] = "UnableToForgeChecksum",
+    [Ipl3Checksum_Error_UnknownByteOrder] = "UnknownByteOrder",
+    [Ipl3Checksum_Error_UnknownBootcodeChecksum] = "UnknownBootcodeChecksum",
+    [Ipl3Checksum_Error_InvalidBootcodeSearch] = "InvalidBootcodeSearch",
+    [Ipl3Checksum_Error_UnknownCicRam] = "UnknownCicRam",
+    [Ipl3Checksum_Error_IoError] = "IoError",
     [Ipl3Checksum_Error_StringConversion] = "StringConversion",
 };
 
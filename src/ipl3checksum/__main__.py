@@ -19,6 +19,7 @@ def ipl3checksumMain():
 
     ipl3checksum.frontends.check.addSubparser(subparsers)
     ipl3checksum.frontends.detect_cic.addSubparser(subparsers)
+    ipl3checksum.frontends.search_params.addSubparser(subparsers)
     ipl3checksum.frontends.sum.addSubparser(subparsers)
 
     args = parser.parse_args()
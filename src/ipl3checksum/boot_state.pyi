@@ -0,0 +1,122 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .cickinds import CICKind
+
+class TvType():
+    """The video standard of the console, as passed to the game on `osTvType`."""
+
+    Pal: TvType
+    Ntsc: TvType
+    Mpal: TvType
+
+class MemoryWrite():
+    """A word written by the IPL3 outside of the copied game code."""
+
+    @property
+    def address(self) -> int:
+        """Virtual address of the word."""
+    @property
+    def value(self) -> int:
+        """The written word."""
+
+class BootState():
+    """The state the IPL3 leaves the console in when jumping to the entrypoint of the game.
+
+    Meant for emulators which skip running the PIF ROM and the IPL3 (HLE boot). The IPL3 passes some values to the
+    game on the `s3` to `s7` registers, which libultra stores on `osRomType`, `osTvType`, `osResetType`, `osCicId`
+    and `osVersion` respectively.
+    """
+
+    @property
+    def kind(self) -> CICKind:
+        """The CIC kind of the ROM."""
+    @property
+    def rom_type(self) -> int:
+        """Kind of media the game booted from, stored on `s3`. 0 for cartridges."""
+    @property
+    def tv_type(self) -> TvType:
+        """The video standard of the console, stored on `s4`."""
+    @property
+    def reset_type(self) -> int:
+        """Kind of reset, stored on `s5`. 0 for a cold boot."""
+    @property
+    def seed(self) -> int:
+        """Seed of the CIC, stored on `s6`."""
+    @property
+    def version(self) -> int:
+        """Stored on `s7`. 6 on PAL and MPAL consoles and 0 on NTSC ones, the values mupen64plus's HLE boot uses
+        (`r4300_reset_soft`)."""
+    @property
+    def entrypoint(self) -> int:
+        """Address the game code is copied to, and where the IPL3 jumps to."""
+    @property
+    def rom_copy_offset(self) -> int:
+        """Offset of the ROM where the copied game code starts."""
+    @property
+    def rom_copy_size(self) -> int:
+        """Amount of bytes copied from the ROM to `entrypoint`."""
+    @property
+    def table_copy_address(self) -> int:
+        """Address the 6105 IPL3 copies a table from its own ROM range to, 0xA0000004. 0 for every other kind."""
+    @property
+    def table_copy_offset(self) -> int:
+        """Offset of the ROM where the table copied to `table_copy_address` starts."""
+    @property
+    def table_copy_size(self) -> int:
+        """Amount of bytes copied from the ROM to `table_copy_address`. 0 for every kind but the 6105."""
+    @property
+    def rdram_size(self) -> int:
+        """Size in bytes of the RDRAM, which the IPL3 stores on `osMemSize`. 0x400000, the RDRAM of a console without
+        the Expansion Pak."""
+
+    @staticmethod
+    def forKind(kind: CICKind, headerEntrypoint: int, tvType: TvType) -> BootState:
+        """The boot state for a cold boot of a cartridge with the given CIC kind.
+
+        Args:
+            kind (CICKind): The CIC kind of the ROM.
+            headerEntrypoint (int): The entrypoint specified on the ROM header.
+            tvType (TvType): The video standard of the emulated console.
+
+        The console is assumed to not have the Expansion Pak, see `rdram_size`.
+        """
+
+    @staticmethod
+    def forRom(romBytes: bytes, tvType: TvType) -> BootState|None:
+        """The boot state for a cold boot of a cartridge. The CIC kind is detected from the IPL3 of the ROM.
+
+        Args:
+            romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x1000 bytes.
+            tvType (TvType): The video standard of the emulated console.
+
+        Returns:
+            BootState|None: The boot state of the ROM, or `None` if the ROM is too small or its CIC kind could not be
+            detected.
+        """
+
+    def getRegisters(self) -> list[tuple[int, int]]:
+        """The values the IPL3 leaves on the `s3` to `s7` registers, paired with the register number."""
+
+    def romCopy(self, romBytes: bytes) -> bytes:
+        """The part of the ROM which is copied to `entrypoint`.
+
+        The returned bytes are shorter than `rom_copy_size` if the ROM is smaller than the copied range.
+        """
+
+    def tableCopy(self, romBytes: bytes) -> bytes:
+        """The part of the ROM which is copied to `table_copy_address`. Empty for every kind but the 6105.
+
+        The returned bytes are shorter than `table_copy_size` if the ROM is smaller than the copied range.
+        """
+
+    def memoryWrites(self) -> list[MemoryWrite]:
+        """Words written by the IPL3 besides the copied ROM ranges, which have to be applied after the copies.
+
+        Every IPL3 stores `rdram_size` on `osMemSize`, at 0x80000318, or at 0x800003F0 for the 6105. The 6105 IPL3
+        also leaves a small routine on SP IMEM, starting at 0xA4001004.
+        """
@@ -0,0 +1,33 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .cickinds import CICKind
+
+def calculateBootcodeChecksum(ipl3Bytes: bytes, kind: CICKind) -> int|None:
+    """Calculates the boot code checksum the PIF's IPL2 computes over an IPL3 before running it.
+
+    The IPL2 compares this 48-bit value against the one sent by the CIC, and only boots the console if they match.
+
+    Args:
+        ipl3Bytes (bytes): The IPL3 binary in big endian format, located at offset 0x40 of a ROM. It must be exactly 0xFC0 bytes long, like the input of `detectCICRaw`.
+        kind (CICKind): The CIC kind, which provides the seed.
+
+    Returns:
+        int|None: The 48-bit checksum, or `None` if `ipl3Bytes` doesn't have the expected size.
+    """
+
+
+def validateBootcode(ipl3Bytes: bytes, kind: CICKind) -> bool|None:
+    """Checks if an IPL3 would be accepted by the IPL2 when paired with the given CIC.
+
+    Args:
+        ipl3Bytes (bytes): The IPL3 binary in big endian format. It must be exactly 0xFC0 bytes long.
+        kind (CICKind): The CIC kind to check against.
+
+    Returns:
+        bool|None: Whether the boot code checksum of the IPL3 matches the one of the CIC, or `None` if `ipl3Bytes` doesn't have the expected size or the boot code checksum of the CIC kind is not known.
+    """
@@ -0,0 +1,36 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+class RomByteOrder():
+    """The byte order of a N64 ROM dump."""
+
+    BigEndian: RomByteOrder
+    """The native byte order of the N64. Usually `.z64` files."""
+    ByteSwapped: RomByteOrder
+    """Every pair of bytes is swapped. Usually `.v64` files."""
+    LittleEndian: RomByteOrder
+    """Every word is stored in little endian. Usually `.n64` files."""
+
+def detectByteOrder(romBytes: bytes) -> RomByteOrder|None:
+    """Detects the byte order of a N64 ROM by looking at the PI configuration word at the start of its header.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in any byte order. It must have a minimum size of 0x4 bytes.
+
+    Returns:
+        RomByteOrder|None: The detected byte order, or `None` if was not able to detect it.
+    """
+
+def normalizeByteOrder(romBytes: bytearray, order: RomByteOrder) -> None:
+    """Converts the bytes of a N64 ROM from the given byte order to big endian, in place.
+
+    The conversion is its own inverse, so calling this function on a big endian ROM converts it back to `order`.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM to convert.
+        order (RomByteOrder): The current byte order of `romBytes`.
+    """
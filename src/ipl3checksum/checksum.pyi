@@ -6,8 +6,12 @@
 from __future__ import annotations
 
 from .cickinds import CICKind
+from .checksum_value import Checksum
+from .params import CicParams
+from .byte_order import RomByteOrder
+from .short_rom import ShortRomPolicy
 
-def calculateChecksum(romBytes: bytes, kind: CICKind) -> tuple[int, int]|None:
+def calculateChecksum(romBytes: bytes, kind: CICKind) -> Checksum|None:
     """Calculates the checksum required by an official CIC of a N64 ROM.
 
     Args:
@@ -15,12 +19,11 @@ def calculateChecksum(romBytes: bytes, kind: CICKind) -> tuple[int, int]|None:
         kind (CICKind): The CIC kind variation used to calculate the checksum.
 
     Returns:
-        tuple[int, int]|None: If no error happens then the calculated checksum is returned, stored as a tuple
-        containing two 32-bits words. Otherwise, `None` is returned. Possible errors:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
         - `romBytes` not being big enough
     """
 
-def calculateChecksumAutodetect(romBytes: bytes) -> tuple[int, int]|None:
+def calculateChecksumAutodetect(romBytes: bytes) -> Checksum|None:
     """Calculates the checksum required by an official CIC of a N64 ROM.
 
     This function will try to autodetect the CIC kind automatically. If it fails to detect it then it will return `None`.
@@ -29,8 +32,93 @@ def calculateChecksumAutodetect(romBytes: bytes) -> tuple[int, int]|None:
         romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
 
     Returns:
-        tuple[int, int]|None: If no error happens then the calculated checksum is returned, stored as a tuple
-        containing two 32-bits words. Otherwise, `None` is returned. Possible errors:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+        - Not able to detect the CIC kind
+    """
+
+def calculateChecksumWithParams(romBytes: bytes, params: CicParams) -> Checksum|None:
+    """Calculates the checksum of a N64 ROM using custom parameters instead of the ones of an official CIC.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of `0x1000 + params.bytes_to_check` bytes.
+        params (CicParams): The parameters used to calculate the checksum.
+
+    Returns:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+    """
+
+def calculateChecksumWithPolicy(romBytes: bytes, kind: CICKind, policy: ShortRomPolicy) -> Checksum|None:
+    """Calculates the checksum required by an official CIC of a N64 ROM which may be smaller than the range checked by the CIC.
+
+    The bytes past the end of the ROM are filled as `policy` says, including the header and the IPL3 if the ROM is smaller than them.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+        policy (ShortRomPolicy): How to fill the bytes past the end of the ROM.
+
+    Returns:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough when using `ShortRomPolicy.Error`
+    """
+
+def calculateChecksumAnyOrder(romBytes: bytes, kind: CICKind) -> tuple[Checksum, RomByteOrder]|None:
+    """Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+
+    The byte order is detected from the ROM header.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum size of 0x101000 bytes.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+
+    Returns:
+        tuple[Checksum, RomByteOrder]|None: If no error happens then the calculated checksum and the byte order of the ROM are returned. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+    """
+
+def calculateChecksumAutodetectAnyOrder(romBytes: bytes) -> tuple[Checksum, RomByteOrder]|None:
+    """Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+
+    This function will try to autodetect both the byte order and the CIC kind automatically.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum size of 0x101000 bytes.
+
+    Returns:
+        tuple[Checksum, RomByteOrder]|None: If no error happens then the calculated checksum and the byte order of the ROM are returned. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+    """
+
+def fixChecksum(romBytes: bytearray, kind: CICKind) -> tuple[Checksum, Checksum]|None:
+    """Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+            The calculated checksum is written to its header.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+
+    Returns:
+        tuple[Checksum, Checksum]|None: If no error happens then a tuple containing the checksum that
+        was in the header before updating it and the newly calculated checksum is returned, in that order.
+        Otherwise, `None` is returned and `romBytes` is left untouched. Possible errors:
+        - `romBytes` not being big enough
+    """
+
+def fixChecksumAutodetect(romBytes: bytearray) -> tuple[Checksum, Checksum]|None:
+    """Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+
+    This function will try to autodetect the CIC kind automatically. If it fails to detect it then it will return `None`.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+            The calculated checksum is written to its header.
+
+    Returns:
+        tuple[Checksum, Checksum]|None: If no error happens then a tuple containing the checksum that
+        was in the header before updating it and the newly calculated checksum is returned, in that order.
+        Otherwise, `None` is returned and `romBytes` is left untouched. Possible errors:
         - `romBytes` not being big enough
         - Not able to detect the CIC kind
     """
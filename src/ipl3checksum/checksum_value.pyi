@@ -0,0 +1,70 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from typing import Iterator
+
+class Checksum():
+    """
+    The checksum of a N64 ROM, made of two 32-bits words.
+
+    It is stored on the ROM header at offset 0x10 in big endian.
+
+    It can be indexed, unpacked and compared as if it were a `tuple[int, int]`.
+    `str()` formats it in the usual `XXXXXXXX XXXXXXXX` form.
+    """
+
+    def __init__(self, word0: int, word1: int) -> None: ...
+
+    @property
+    def word0(self) -> int: ...
+    @property
+    def word1(self) -> int: ...
+
+    @staticmethod
+    def fromHeader(romBytes: bytes) -> Checksum|None:
+        """
+        Reads the checksum stored on the header of a N64 ROM.
+
+        Returns `None` if `romBytes` is smaller than 0x18 bytes.
+        """
+
+    @staticmethod
+    def fromString(s: str) -> Checksum|None:
+        """
+        Parses either two whitespace separated hexadecimal words (`XXXXXXXX XXXXXXXX`) or a single 64-bits
+        hexadecimal value (`XXXXXXXXXXXXXXXX`). A `0x` prefix is allowed on each value.
+
+        Returns `None` if the string can't be parsed.
+        """
+
+    @staticmethod
+    def fromU64(value: int) -> Checksum:
+        """
+        The first word is taken from the upper 32 bits.
+        """
+
+    def toU64(self) -> int: ...
+
+    @staticmethod
+    def fromBeBytes(bytes: bytes) -> Checksum: ...
+
+    def toBeBytes(self) -> bytes:
+        """
+        The checksum as it is stored on the ROM header.
+        """
+
+    def matchesHeader(self, romBytes: bytes) -> bool:
+        """
+        Checks if this checksum is the one stored on the header of a N64 ROM.
+        """
+
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> int: ...
+    def __iter__(self) -> Iterator[int]: ...
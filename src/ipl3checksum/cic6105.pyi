@@ -0,0 +1,16 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+def cic6105Respond(challenge: bytes) -> bytes|None:
+    """Calculates the response of the CIC 6105 to a challenge.
+
+    Args:
+        challenge (bytes): The 30 nibbles of the challenge, one per byte. Only the lower 4 bits of each byte are used.
+
+    Returns:
+        bytes|None: The 30 nibbles of the response, one per byte, or `None` if `challenge` isn't exactly 30 bytes long.
+    """
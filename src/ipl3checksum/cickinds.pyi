@@ -5,6 +5,9 @@
 
 from __future__ import annotations
 
+from .params import CicParams, FinalizationVariant
+from .checksum_value import Checksum
+
 class CICKind():
     CIC_6101: CICKind
     CIC_6102_7101: CICKind
@@ -29,6 +32,33 @@ class CICKind():
         Magic value hardcoded inside the IPL3 itself
         """
 
+    def getBootcodeChecksum(self) -> int|None:
+        """
+        The 48-bit boot code checksum stored on the CIC, which the PIF ROM compares against the one it calculates over
+        the IPL3. See `calculateBootcodeChecksum`.
+
+        `None` if the value isn't known.
+        """
+
+    def getFinalizationVariant(self) -> FinalizationVariant:
+        """
+        How the IPL3 combines its registers into the final checksum.
+        """
+
+    def getParams(self, header_entrypoint: int) -> CicParams:
+        """
+        Every parameter used by the IPL3 of this kind to calculate the checksum.
+
+        The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+        """
+
+    def getMinRomSize(self, header_entrypoint: int) -> int:
+        """
+        The minimum size a ROM needs for this kind to checksum it, which is the header, the IPL3 and the checked range.
+
+        The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+        """
+
     def getEntrypoint(self, header_entrypoint: int) -> int:
         """
         Calculates the actual entrypoint address based on the entrypoint specified on the header.
@@ -61,13 +91,12 @@ class CICKind():
     def fromValue(value: int) -> CICKind|None:
         ...
 
-    def calculateChecksum(self, romBytes: bytes) -> tuple[int, int]:
+    def calculateChecksum(self, romBytes: bytes) -> Checksum:
         """Calculates the checksum required by an official CIC of a N64 ROM.
 
         Args:
             romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
 
         Returns:
-            tuple[int, int]: If no error happens then the calculated checksum is returned, stored as a tuple
-            containing two 32-bits words. If an errors occurs an exception will be raised (see ipl3checksum.exceptions).
+            Checksum: If no error happens then the calculated checksum is returned. If an errors occurs an exception will be raised (see ipl3checksum.exceptions).
         """
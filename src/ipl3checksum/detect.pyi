@@ -6,6 +6,7 @@
 from __future__ import annotations
 
 from .cickinds import CICKind
+from .byte_order import RomByteOrder
 
 def detectCICRaw(rawBytes: bytes) -> CICKind|None:
     """Tries to detect an IPL3 binary.
@@ -31,3 +32,16 @@ def detectCIC(romBytes: bytes) -> CICKind|None:
     Returns:
         CICKind|None: The detected CIC kind, or `None` if was not able to detect the CIC kind.
     """
+
+
+def detectCICAnyOrder(romBytes: bytes) -> tuple[CICKind, RomByteOrder]|None:
+    """Tries to detect an IPL3 in a ROM of any byte order.
+
+    The byte order is detected from the ROM header.
+
+    Args:
+        romBytes (bytes): ROM binary in big endian, byteswapped or little endian format.
+
+    Returns:
+        tuple[CICKind, RomByteOrder]|None: The detected CIC kind and the byte order of the ROM, or `None` if was not able to detect either of them.
+    """
@@ -53,3 +53,46 @@ class UnableToDetectCIC(Ipl3ChecksumError):
     """
     Unable to detect CIC variant
     """
+
+class InvalidChecksumString(Ipl3ChecksumError):
+    """
+    The string could not be parsed as a checksum.
+    """
+
+class InvalidForgeRegion(Ipl3ChecksumError):
+    """
+    The region passed to `forgeChecksum` is not aligned to a word or is not
+    inside the range checked by the CIC.
+    """
+
+class ForgeRegionTooSmall(Ipl3ChecksumError):
+    """
+    The region passed to `forgeChecksum` is not big enough.
+
+    The error runtime string specifies the expected size.
+    """
+
+class UnableToForgeChecksum(Ipl3ChecksumError):
+    """
+    No words that produce the target checksum could be found.
+    """
+
+class UnknownByteOrder(Ipl3ChecksumError):
+    """
+    The byte order of the ROM could not be detected from its header.
+    """
+
+class UnknownBootcodeChecksum(Ipl3ChecksumError):
+    """
+    The boot code checksum of the given CIC kind is not known, so the IPL3 can't be validated against it.
+    """
+
+class InvalidBootcodeSearch(Ipl3ChecksumError):
+    """
+    The offsets or the range of candidates passed to a boot code collision search are not valid.
+    """
+
+class UnknownCicRam(Ipl3ChecksumError):
+    """
+    The initial RAM of the CIC for the given region is not known.
+    """
@@ -0,0 +1,34 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .checksum_value import Checksum
+from .cickinds import CICKind
+
+def forgeChecksum(romBytes: bytearray, kind: CICKind, target: Checksum, regionOffset: int, regionSize: int) -> tuple[int, int]|None:
+    """Overwrites words of a N64 ROM so its calculated checksum becomes `target` for the given CIC kind.
+
+    Only words inside the `regionSize` bytes starting at `regionOffset` are modified, which must be inside the
+    range checked by the CIC kind. The ROM header is not modified.
+
+    Not every target can be reached, since some bits of the result are tied to the data outside the region. Targets
+    which break those bits are rejected before searching, and only the first 64 positions of the region are tried, so
+    a failed search takes a few seconds at most.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+        target (Checksum): The checksum the ROM should produce.
+        regionOffset (int): Offset of the writable region in the ROM. It must be a multiple of 4.
+        regionSize (int): Size in bytes of the writable region. It must be at least 0x40 bytes.
+
+    Returns:
+        tuple[int, int]|None: If no error happens then the start and end offsets of the overwritten bytes are returned.
+        Otherwise, `None` is returned and `romBytes` is left untouched. Possible errors:
+        - `romBytes` not being big enough
+
+        An invalid region or not finding a solution raises an exception instead.
+    """
@@ -8,4 +8,5 @@ from __future__ import annotations
 
 from . import check as check
 from . import detect_cic as detect_cic
+from . import search_params as search_params
 from . import sum as sum
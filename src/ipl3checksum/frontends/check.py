@@ -7,37 +7,41 @@ from __future__ import annotations
 
 import argparse
 from pathlib import Path
-import struct
 
 import ipl3checksum
 
 def doCheck(romBytes: bytes, kindName: str | None) -> int:
     if kindName is None:
         # Detect kind if none was specified by the user
-        kind = ipl3checksum.detectCIC(romBytes)
-        if kind is None:
-            print(f"Unable to detect CIC kind")
-            return 1
-        print(f"Detected kind is '{kind.name}'")
+        report = ipl3checksum.verifyRom(romBytes)
     else:
         kind = ipl3checksum.CICKind.fromName(kindName)
         if kind is None:
             print(f"Invalid choice for cic kind. Valid choices: {ipl3checksum.CICKind.validNames()}")
             return 1
+        report = ipl3checksum.verifyRomWithKind(romBytes, kind)
 
-    ogChk0, ogChk1 = struct.unpack_from(f">II", romBytes, 0x10)
-    print(f"Checksum in ROM:     {ogChk0:08X} {ogChk1:08X}")
-
-    checksum = ipl3checksum.calculateChecksum(romBytes, kind)
-    if checksum is None:
+    if report is None:
         print(f"Unable to calculate checksum")
         return 1
 
-    chk0, chk1 = checksum
-    print(f"Calculated checksum: {chk0:08X} {chk1:08X}")
+    if report.kind is None or report.calculated_checksum is None:
+        print(f"Unable to detect CIC kind")
+        print(f"Checksum in ROM:     {report.header_checksum}")
+        return 1
+
+    if report.detection_method == ipl3checksum.DetectionMethod.Ipl3Hash:
+        print(f"Detected kind is '{report.kind.name}'")
+    elif report.detection_method == ipl3checksum.DetectionMethod.HeaderChecksum:
+        print(f"Unknown IPL3, kind '{report.kind.name}' matches the checksum in the header")
+
+    print(f"Checksum in ROM:     {report.header_checksum}")
+    print(f"Calculated checksum: {report.calculated_checksum}")
 
-    if chk0 != ogChk0 or chk1 != ogChk1:
+    if not report.matches:
         print(f"Checksum doesn't match")
+        if report.alternative_kind is not None:
+            print(f"The checksum in the header matches kind '{report.alternative_kind.name}' instead")
         return 1
 
     print("Checksum matches")
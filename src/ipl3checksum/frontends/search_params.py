@@ -0,0 +1,44 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import argparse
+from pathlib import Path
+
+import ipl3checksum
+
+def doSearchParams(romsBytes: list[bytes], bytesToCheck: int) -> int:
+    candidates = ipl3checksum.searchCicParameters(romsBytes, bytesToCheck)
+    if candidates is None:
+        print(f"Unable to search the parameters, a ROM is too small")
+        return 1
+
+    if len(candidates) == 0:
+        print(f"No combination of parameters matches the checksum of every ROM")
+        return 1
+
+    for candidate in candidates:
+        print(f"seed: 0x{candidate.seed:02X}, magic: 0x{candidate.magic:08X}, finalization: {candidate.finalization}, uses X105 table: {candidate.uses_x105_table}")
+
+    return 0
+
+
+def processArguments(args: argparse.Namespace):
+    romPaths: list[Path] = args.rom_paths
+    bytesToCheck: int = args.bytes_to_check
+
+    romsBytes = [romPath.read_bytes() for romPath in romPaths]
+
+    exit(doSearchParams(romsBytes, bytesToCheck))
+
+def addSubparser(subparser: argparse._SubParsersAction[argparse.ArgumentParser]):
+    parser = subparser.add_parser("search_params", help="Brute forces the seed, magic and finalization of the CIC used by big endian ROMs with a known good checksum")
+
+    parser.add_argument("rom_paths", help="Paths to big endian ROM files which use the same CIC", type=Path, nargs="+")
+
+    parser.add_argument("-b", "--bytes-to-check", help="How many bytes after the IPL3 are checked by the CIC. Defaults to 0x100000", dest="bytes_to_check", type=lambda x: int(x, 0), default=0x100000)
+
+    parser.set_defaults(func=processArguments)
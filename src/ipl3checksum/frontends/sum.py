@@ -7,7 +7,6 @@ from __future__ import annotations
 
 import argparse
 from pathlib import Path
-import struct
 
 import ipl3checksum
 
@@ -30,15 +29,14 @@ def doSum(romBytes: bytes, kindName: str | None, update: bool, outputPath: Path)
         print(f"Unable to calculate checksum")
         return 1
 
-    chk0, chk1 = checksum
-    print(f"Calculated checksum: {chk0:08X} {chk1:08X}")
+    print(f"Calculated checksum: {checksum}")
 
     if update:
         print(f"Writing updated ROM to '{outputPath}'")
         outputPath.parent.mkdir(parents=True, exist_ok=True)
         with outputPath.open("wb") as f:
             f.write(romBytes[:0x10])
-            f.write(struct.pack(f">II", chk0, chk1))
+            f.write(checksum.toBeBytes())
             f.write(romBytes[0x18:])
 
     return 0
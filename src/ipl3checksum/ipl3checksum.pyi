@@ -7,10 +7,59 @@ from __future__ import annotations
 
 from .cickinds import CICKind as CICKind
 
+from .checksum_value import Checksum as Checksum
+
+from .rom_header import RomHeader as RomHeader
+
+from .byte_order import RomByteOrder as RomByteOrder
+from .byte_order import detectByteOrder as detectByteOrder
+from .byte_order import normalizeByteOrder as normalizeByteOrder
+
+from .short_rom import ShortRomPolicy as ShortRomPolicy
+
+from .padding import PadRounding as PadRounding
+from .padding import ResizeReport as ResizeReport
+from .padding import paddedRomSize as paddedRomSize
+from .padding import trimmedRomSize as trimmedRomSize
+from .padding import padRom as padRom
+from .padding import trimRom as trimRom
+
+from .params import FinalizationVariant as FinalizationVariant
+from .params import CicParams as CicParams
+
 from .checksum import calculateChecksum as calculateChecksum
 from .checksum import calculateChecksumAutodetect as calculateChecksumAutodetect
+from .checksum import calculateChecksumWithParams as calculateChecksumWithParams
+from .checksum import calculateChecksumWithPolicy as calculateChecksumWithPolicy
+from .checksum import calculateChecksumAnyOrder as calculateChecksumAnyOrder
+from .checksum import calculateChecksumAutodetectAnyOrder as calculateChecksumAutodetectAnyOrder
+from .checksum import fixChecksum as fixChecksum
+from .checksum import fixChecksumAutodetect as fixChecksumAutodetect
+
+from .reader import calculateChecksumFromPath as calculateChecksumFromPath
+from .reader import calculateChecksumAutodetectFromPath as calculateChecksumAutodetectFromPath
+
+from .forge import forgeChecksum as forgeChecksum
+
+from .seed_search import CicParameterCandidate as CicParameterCandidate
+from .seed_search import searchCicParameters as searchCicParameters
+
+from .verify import DetectionMethod as DetectionMethod
+from .verify import VerificationReport as VerificationReport
+from .verify import verifyRom as verifyRom
+from .verify import verifyRomWithKind as verifyRomWithKind
 
 from .detect import detectCIC as detectCIC
 from .detect import detectCICRaw as detectCICRaw
+from .detect import detectCICAnyOrder as detectCICAnyOrder
+
+from .bootcode import calculateBootcodeChecksum as calculateBootcodeChecksum
+from .bootcode import validateBootcode as validateBootcode
+
+from .cic6105 import cic6105Respond as cic6105Respond
+
+from .boot_state import TvType as TvType
+from .boot_state import MemoryWrite as MemoryWrite
+from .boot_state import BootState as BootState
 
 from .exceptions import exceptions as exceptions
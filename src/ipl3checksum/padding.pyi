@@ -0,0 +1,85 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .cickinds import CICKind
+
+class PadRounding():
+    """How the size of a padded ROM is rounded up."""
+
+    Exact: PadRounding
+    """No rounding."""
+    PowerOfTwo: PadRounding
+    """Round up to the next power of two, like the size of a real cartridge."""
+    MultipleOfMiB: PadRounding
+    """Round up to the next multiple of 1 MiB."""
+
+class ResizeReport():
+    """The result of padding or trimming a ROM."""
+
+    old_len: int
+    """The size of the ROM before resizing it."""
+    new_len: int
+    """The size of the ROM after resizing it."""
+    checksum_changed: bool
+    """Whether bytes inside the range checked by the CIC were added, so the checksum on the header has to be fixed again."""
+
+def paddedRomSize(romBytes: bytes, kind: CICKind, targetSize: int = 0, rounding: PadRounding = PadRounding.Exact) -> int:
+    """Calculates the size `padRom` would pad the ROM to.
+
+    The result is never smaller than the ROM itself nor than the minimum size needed by the CIC kind, see `CICKind.getMinRomSize`.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format.
+        kind (CICKind): The CIC kind variation the ROM is meant for.
+        targetSize (int): The desired size. Pass 0 to only pad to the minimum size needed by the CIC kind.
+        rounding (PadRounding): How the size is rounded up after applying the minimums.
+
+    Returns:
+        int: The padded size.
+    """
+
+def trimmedRomSize(romBytes: bytes, kind: CICKind) -> int:
+    """Calculates the size `trimRom` would trim the ROM to.
+
+    The trailing run of 0x00 or 0xFF bytes is removed, keeping the size a multiple of 4 and never trimming inside the range checked by the CIC kind, so the checksum is not affected.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format.
+        kind (CICKind): The CIC kind variation the ROM is meant for.
+
+    Returns:
+        int: The trimmed size.
+    """
+
+def padRom(romBytes: bytearray, kind: CICKind, targetSize: int = 0, rounding: PadRounding = PadRounding.Exact, fill: int = 0) -> ResizeReport:
+    """Pads a N64 ROM up to the given size in place, appending `fill` bytes.
+
+    The ROM is always padded to at least the minimum size needed by the CIC kind, see `paddedRomSize`.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM in big endian format.
+        kind (CICKind): The CIC kind variation the ROM is meant for.
+        targetSize (int): The desired size. Pass 0 to only pad to the minimum size needed by the CIC kind.
+        rounding (PadRounding): How the size is rounded up after applying the minimums.
+        fill (int): The byte used for padding. Usually 0x00 or 0xFF.
+
+    Returns:
+        ResizeReport: A report of the resize. If its `checksum_changed` is set then the checksum has to be fixed again, for example with `fixChecksum`.
+    """
+
+def trimRom(romBytes: bytearray, kind: CICKind) -> ResizeReport:
+    """Removes the trailing padding of a N64 ROM in place.
+
+    The bytes checked by the CIC kind are never removed, see `trimmedRomSize`.
+
+    Args:
+        romBytes (bytearray): The bytes of the N64 ROM in big endian format.
+        kind (CICKind): The CIC kind variation the ROM is meant for.
+
+    Returns:
+        ResizeReport: A report of the resize. Its `checksum_changed` is never set.
+    """
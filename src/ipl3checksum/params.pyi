@@ -0,0 +1,46 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .cickinds import CICKind
+
+class FinalizationVariant():
+    """How the registers are combined into the checksum once every word has been processed."""
+
+    Xor: FinalizationVariant
+    """`(a3 ^ t2) ^ t3` and `(s0 ^ a2) ^ t4`. Used by most CIC kinds."""
+    Add: FinalizationVariant
+    """`(a3 ^ t2) + t3` and `(s0 ^ a2) + t4`. Used by CIC_X103 and CIC_5101."""
+    Mul: FinalizationVariant
+    """`(a3 * t2) + t3` and `(s0 * a2) + t4`. Used by CIC_X106."""
+
+class CicParams():
+    """Everything that changes between the checksum algorithms of the different IPL3s.
+
+    Every `CICKind` has its own parameters (see `CICKind.getParams`), but custom ones can be used to checksum ROMs
+    meant for non retail IPL3s with `calculateChecksumWithParams`.
+    """
+
+    seed: int
+    """Seed value set by the PIF ROM before the CPU (and the IPL3) is executed."""
+    magic: int
+    """Magic value hardcoded inside the IPL3."""
+    bytes_to_check: int
+    """How many bytes after the IPL3 are checked. It should be a multiple of 4."""
+    finalization: FinalizationVariant
+    """How the registers are combined into the final checksum."""
+    uses_x105_table: bool
+    """Whether each word is mixed with a word of the table at offset 0x750 of the IPL3, like CIC_X105 does."""
+
+    def __init__(self, seed: int, magic: int, bytes_to_check: int, finalization: FinalizationVariant, uses_x105_table: bool) -> None: ...
+
+    @staticmethod
+    def fromKind(kind: CICKind, header_entrypoint: int) -> CicParams:
+        """
+        Every parameter used by the IPL3 of the given kind to calculate the checksum.
+
+        The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+        """
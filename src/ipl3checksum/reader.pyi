@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import os
+
+from .cickinds import CICKind
+from .checksum_value import Checksum
+
+def calculateChecksumFromPath(path: str|os.PathLike[str], kind: CICKind) -> Checksum|None:
+    """Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+
+    Only the header, the IPL3 and the range checked by the CIC kind are read from the file.
+
+    Args:
+        path (str|os.PathLike[str]): Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+
+    Returns:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
+        - The file not being big enough
+
+    Raises:
+        OSError: The file does not exist or it failed to be read.
+    """
+
+def calculateChecksumAutodetectFromPath(path: str|os.PathLike[str]) -> Checksum|None:
+    """Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+
+    This function will try to autodetect the CIC kind automatically. If it fails to detect it then it will return `None`.
+
+    Args:
+        path (str|os.PathLike[str]): Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+
+    Returns:
+        Checksum|None: If no error happens then the calculated checksum is returned. Otherwise, `None` is returned. Possible errors:
+        - The file not being big enough
+        - Not able to detect the CIC kind
+
+    Raises:
+        OSError: The file does not exist or it failed to be read.
+    """
@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .checksum_value import Checksum
+
+class RomHeader():
+    """The header of a N64 ROM, stored on its first 0x40 bytes.
+
+    `str()` returns a multiline summary of its fields.
+    """
+
+    pi_bsd_domain1_config: int
+    """Configuration of the PI bus for the cartridge domain, its most significant byte is always 0x80."""
+    clock_rate: int
+    """Value used to compute the CPU clock rate, ignored on retail consoles."""
+    boot_address: int
+    """Address where the IPL3 copies the code of the ROM to, and jumps to once it is done. Also known as entrypoint."""
+    libultra_release: int
+    """The libultra release the ROM was built with. The last byte is the revision letter."""
+    checksum: Checksum
+    """The checksum checked by the CIC, also known as CRC1 and CRC2."""
+    image_name: bytes
+    """Name of the game, in ASCII or Shift-JIS, padded with spaces or zeroes. Must be 20 bytes long."""
+    category_code: int
+    """The kind of media the game is distributed on, as an ASCII character. For example `N` for cartridges."""
+    game_id: bytes
+    """Two ASCII characters that identify the game."""
+    destination_code: int
+    """The region the game was released on, as an ASCII character. For example `E` for North America."""
+    rom_version: int
+    """Revision of the ROM, starting at 0."""
+
+    @staticmethod
+    def fromBytes(romBytes: bytes) -> RomHeader|None:
+        """Parses the header of a N64 ROM.
+
+        Args:
+            romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+
+        Returns:
+            RomHeader|None: The parsed header, or `None` if `romBytes` is not big enough.
+        """
+
+    def toBytes(self) -> bytes:
+        """The header as it is stored on the ROM."""
+
+    def writeToRom(self, romBytes: bytearray) -> bool:
+        """Writes this header to a N64 ROM, replacing its first 0x40 bytes.
+
+        Keep in mind the checksum is not recalculated, use `fixChecksum` afterwards if the boot address changed.
+
+        Args:
+            romBytes (bytearray): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+
+        Returns:
+            bool: `False` if `romBytes` is not big enough, in which case it is left untouched.
+        """
+
+    def decodeImageName(self) -> str:
+        """Decodes the image name, without its trailing padding.
+
+        Besides ASCII, only the Shift-JIS characters used in practice by image names are supported: half-width
+        katakana, full-width alphanumerics, hiragana, katakana and a few punctuation marks. Any other character is
+        decoded as U+FFFD.
+        """
+
+    def gameCode(self) -> str:
+        """The category code, game id and destination code, in that order. Usually known as the game code."""
+
+    def categoryName(self) -> str|None:
+        """Describes the category code, if it is a known one."""
+
+    def destinationName(self) -> str|None:
+        """Describes the destination code, if it is a known one."""
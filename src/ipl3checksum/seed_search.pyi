@@ -0,0 +1,42 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .params import FinalizationVariant
+
+class CicParameterCandidate():
+    """A combination of CIC parameters which produces the header checksum of every searched ROM."""
+
+    @property
+    def seed(self) -> int:
+        """Seed value set by the PIF ROM."""
+    @property
+    def magic(self) -> int:
+        """Magic value hardcoded inside the IPL3."""
+    @property
+    def finalization(self) -> FinalizationVariant:
+        """How the registers are combined into the final checksum."""
+    @property
+    def uses_x105_table(self) -> bool:
+        """Whether the IPL3 mixes the CIC_X105 table into the checksum."""
+
+def searchCicParameters(romsBytes: list[bytes], bytesToCheck: int = 0x100000) -> list[CicParameterCandidate]|None:
+    """Searches every seed and known magic value, with every finalization variant and with and without the CIC_X105
+    table, for the combinations that produce the header checksum of all the given ROMs.
+
+    Useful to find or confirm the parameters of a CIC whose seed is not known. The search runs on as many threads as
+    the system provides.
+
+    Args:
+        romsBytes (list[bytes]): The bytes of N64 ROMs in big endian format, all of them using the same CIC and with a
+            correct checksum on their header. More samples narrow down the results.
+        bytesToCheck (int): How many bytes after the IPL3 are checked by the CIC. It should be a multiple of 4.
+
+    Returns:
+        list[CicParameterCandidate]|None: If no error happens then every matching combination is returned, sorted by
+        seed. Otherwise, `None` is returned. Possible errors:
+        - A ROM not being big enough
+    """
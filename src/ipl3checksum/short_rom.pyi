@@ -0,0 +1,24 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+class ShortRomPolicy():
+    """What to do when a ROM is smaller than the range checked by the CIC.
+
+    The IPL3 doesn't know the size of the cartridge, so on real hardware it checksums whatever the bus returns past the end of the ROM.
+    """
+
+    Error: ShortRomPolicy
+    """Fail like `calculateChecksum` does."""
+    ZeroFill: ShortRomPolicy
+    """Every byte past the end of the ROM reads as 0x00."""
+    FfFill: ShortRomPolicy
+    """Every byte past the end of the ROM reads as 0xFF."""
+    OpenBus: ShortRomPolicy
+    """Emulates the open bus behaviour of the N64 PI, where reading an unmapped cartridge address returns the low 16 bits of said address. Each halfword past the end of the ROM reads as the low halfword of its own offset."""
+
+    def fillByte(self, offset: int) -> int|None:
+        """The byte read at `offset` when said offset is past the end of the ROM, or `None` for `ShortRomPolicy.Error`."""
@@ -0,0 +1,74 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2024 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from .checksum_value import Checksum
+from .cickinds import CICKind
+
+class DetectionMethod():
+    """How the CIC kind of a verified ROM was determined."""
+
+    Ipl3Hash: DetectionMethod
+    """The IPL3 of the ROM matched the hash of a known IPL3."""
+    HeaderChecksum: DetectionMethod
+    """The IPL3 is unknown, but the checksum calculated for this kind matched the one in the header."""
+    Specified: DetectionMethod
+    """The CIC kind was specified by the caller."""
+
+class VerificationReport():
+    """The result of verifying the checksum of a N64 ROM."""
+
+    @property
+    def header_checksum(self) -> Checksum:
+        """The checksum stored on the ROM header."""
+    @property
+    def kind(self) -> CICKind|None:
+        """The CIC kind used to verify the ROM, if one could be determined."""
+    @property
+    def detection_method(self) -> DetectionMethod|None:
+        """How `kind` was determined. `None` if `kind` is `None`."""
+    @property
+    def calculated_checksum(self) -> Checksum|None:
+        """The checksum calculated for `kind`. `None` if `kind` is `None`."""
+    @property
+    def matches(self) -> bool:
+        """Whether `calculated_checksum` matches `header_checksum`."""
+    @property
+    def alternative_kind(self) -> CICKind|None:
+        """
+        On a mismatch, another CIC kind that produces the checksum stored on the header, if any.
+
+        If this is set then the ROM data is most likely fine, but its IPL3 does not correspond to the CIC kind
+        that was expected.
+        """
+
+def verifyRom(romBytes: bytes) -> VerificationReport|None:
+    """Verifies the checksum stored on the header of a N64 ROM.
+
+    The CIC kind is detected from the IPL3 of the ROM. If the IPL3 is unknown then the first CIC kind whose
+    calculated checksum matches the header is used instead.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+
+    Returns:
+        VerificationReport|None: If no error happens then a report describing the outcome is returned. A checksum
+        mismatch is not considered an error. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+    """
+
+def verifyRomWithKind(romBytes: bytes, kind: CICKind) -> VerificationReport|None:
+    """Verifies the checksum stored on the header of a N64 ROM, using the given CIC kind instead of detecting it.
+
+    Args:
+        romBytes (bytes): The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+        kind (CICKind): The CIC kind variation used to calculate the checksum.
+
+    Returns:
+        VerificationReport|None: If no error happens then a report describing the outcome is returned. A checksum
+        mismatch is not considered an error. Otherwise, `None` is returned. Possible errors:
+        - `romBytes` not being big enough
+    """
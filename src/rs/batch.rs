@@ -0,0 +1,813 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use core::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::{verify, CICKind, Checksum, Ipl3ChecksumError, RomByteOrder};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The outcome of checking a single file of a batch.
+pub enum BatchStatus {
+    /// The checksum in the header matches the calculated one.
+    Ok,
+    /// The checksum in the header does not match the one calculated for the detected CIC kind.
+    Mismatch,
+    /// Neither the IPL3 nor the checksum in the header match any known CIC kind.
+    UnknownCic,
+    /// The file does not look like a N64 ROM in any byte order.
+    NotARom,
+    /// The file could not be read or checksummed. See `BatchEntry::error`.
+    Error,
+}
+
+impl BatchStatus {
+    /// Every status, in the order used by `BatchSummary`.
+    pub const ALL: [BatchStatus; 5] = [
+        Self::Ok,
+        Self::Mismatch,
+        Self::UnknownCic,
+        Self::NotARom,
+        Self::Error,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Ok => "Ok",
+            Self::Mismatch => "Mismatch",
+            Self::UnknownCic => "UnknownCic",
+            Self::NotARom => "NotARom",
+            Self::Error => "Error",
+        }
+    }
+
+    /// Whether this status should be reported as a failure. Files which are not ROMs are just skipped.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Mismatch | Self::UnknownCic | Self::Error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Why a file of a batch could not be checked.
+pub enum BatchError {
+    /// Reading the file failed.
+    Io(#[cfg_attr(feature = "serde", serde(with = "serde_io_error_kind"))] io::ErrorKind),
+    /// The file was read, but the checksum could not be calculated.
+    Checksum(Ipl3ChecksumError),
+}
+
+impl BatchError {
+    /// The name of the error variant, used to group failures on reports.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "IoError",
+            Self::Checksum(e) => match e {
+                #[cfg(feature = "c_bindings")]
+                Ipl3ChecksumError::Okay => "Okay",
+                #[cfg(feature = "c_bindings")]
+                Ipl3ChecksumError::NullPointer => "NullPointer",
+                #[cfg(feature = "c_bindings")]
+                Ipl3ChecksumError::StringConversion => "StringConversion",
+                Ipl3ChecksumError::UnalignedRead { .. } => "UnalignedRead",
+                Ipl3ChecksumError::ByteConversion { .. } => "ByteConversion",
+                Ipl3ChecksumError::OutOfBounds { .. } => "OutOfBounds",
+                Ipl3ChecksumError::BufferNotBigEnough { .. } => "BufferNotBigEnough",
+                Ipl3ChecksumError::BufferSizeIsWrong { .. } => "BufferSizeIsWrong",
+                Ipl3ChecksumError::UnableToDetectCIC => "UnableToDetectCIC",
+                Ipl3ChecksumError::InvalidChecksumString => "InvalidChecksumString",
+                Ipl3ChecksumError::InvalidForgeRegion { .. } => "InvalidForgeRegion",
+                Ipl3ChecksumError::ForgeRegionTooSmall { .. } => "ForgeRegionTooSmall",
+                Ipl3ChecksumError::UnableToForgeChecksum => "UnableToForgeChecksum",
+                Ipl3ChecksumError::UnknownByteOrder => "UnknownByteOrder",
+                Ipl3ChecksumError::UnknownBootcodeChecksum => "UnknownBootcodeChecksum",
+                Ipl3ChecksumError::InvalidBootcodeSearch => "InvalidBootcodeSearch",
+                Ipl3ChecksumError::UnknownCicRam => "UnknownCicRam",
+                #[cfg(feature = "c_bindings")]
+                Ipl3ChecksumError::IoError => "IoError",
+            },
+        }
+    }
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(kind) => write!(f, "{}", io::Error::from(*kind)),
+            Self::Checksum(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// `io::ErrorKind` is stored as the name of its variant. Unknown names are read back as `io::ErrorKind::Other`.
+#[cfg(feature = "serde")]
+mod serde_io_error_kind {
+    use std::io::ErrorKind;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const KINDS: [ErrorKind; 17] = [
+        ErrorKind::NotFound,
+        ErrorKind::PermissionDenied,
+        ErrorKind::ConnectionRefused,
+        ErrorKind::ConnectionReset,
+        ErrorKind::ConnectionAborted,
+        ErrorKind::NotConnected,
+        ErrorKind::AddrInUse,
+        ErrorKind::AddrNotAvailable,
+        ErrorKind::BrokenPipe,
+        ErrorKind::AlreadyExists,
+        ErrorKind::WouldBlock,
+        ErrorKind::InvalidInput,
+        ErrorKind::InvalidData,
+        ErrorKind::TimedOut,
+        ErrorKind::WriteZero,
+        ErrorKind::Interrupted,
+        ErrorKind::UnexpectedEof,
+    ];
+
+    pub(super) fn serialize<S: Serializer>(
+        kind: &ErrorKind,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:?}", kind))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ErrorKind, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        Ok(KINDS
+            .into_iter()
+            .find(|kind| format!("{:?}", kind) == name)
+            .unwrap_or(ErrorKind::Other))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The result of checking a single file of a batch.
+pub struct BatchEntry {
+    /// Path to the checked file, or to the directory which could not be read.
+    pub path: PathBuf,
+    /// Size of the file in bytes. Zero if it could not be read.
+    pub size: usize,
+    /// The byte order of the ROM, if it looks like one.
+    pub byte_order: Option<RomByteOrder>,
+    /// The CIC kind used to verify the ROM, if one could be determined.
+    pub kind: Option<CICKind>,
+    /// The checksum stored on the ROM header.
+    pub header_checksum: Option<Checksum>,
+    /// The checksum calculated for `kind`.
+    pub calculated_checksum: Option<Checksum>,
+    /// The outcome of the check.
+    pub status: BatchStatus,
+    /// Why the file could not be checked. Only set if `status` is `BatchStatus::Error` or `BatchStatus::NotARom`.
+    pub error: Option<BatchError>,
+}
+
+impl BatchEntry {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            size: 0,
+            byte_order: None,
+            kind: None,
+            header_checksum: None,
+            calculated_checksum: None,
+            status: BatchStatus::Error,
+            error: None,
+        }
+    }
+
+    fn check(path: PathBuf) -> Self {
+        let mut entry = Self::new(path);
+
+        let mut rom_bytes = match fs::read(&entry.path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                entry.error = Some(BatchError::Io(e.kind()));
+                return entry;
+            }
+        };
+        entry.size = rom_bytes.len();
+
+        let order = match RomByteOrder::detect(&rom_bytes) {
+            Ok(order) => order,
+            Err(e) => {
+                entry.status = BatchStatus::NotARom;
+                entry.error = Some(BatchError::Checksum(e));
+                return entry;
+            }
+        };
+        entry.byte_order = Some(order);
+        crate::normalize_byte_order(&mut rom_bytes, order);
+
+        let report = match verify::verify_rom(&rom_bytes) {
+            Ok(report) => report,
+            Err(e) => {
+                entry.error = Some(BatchError::Checksum(e));
+                return entry;
+            }
+        };
+
+        entry.kind = report.kind;
+        entry.header_checksum = Some(report.header_checksum);
+        entry.calculated_checksum = report.calculated_checksum;
+        entry.status = if report.matches {
+            BatchStatus::Ok
+        } else if report.kind.is_none() {
+            BatchStatus::UnknownCic
+        } else {
+            BatchStatus::Mismatch
+        };
+
+        entry
+    }
+
+    fn write_json_line<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        fn optional<T: fmt::Display>(value: Option<T>) -> String {
+            match value {
+                Some(value) => json_string(&value.to_string()),
+                None => "null".to_string(),
+            }
+        }
+
+        writeln!(
+            writer,
+            "{{\"path\":{},\"size\":{},\"byte_order\":{},\"kind\":{},\"header_checksum\":{},\"calculated_checksum\":{},\"status\":{},\"error\":{}}}",
+            json_string(&self.path.display().to_string()),
+            self.size,
+            optional(self.byte_order.map(byte_order_name)),
+            optional(self.kind.map(|kind| kind.get_name())),
+            optional(self.header_checksum),
+            optional(self.calculated_checksum),
+            json_string(self.status.get_name()),
+            optional(self.error.as_ref().map(|e| e.variant_name())),
+        )
+    }
+
+    fn write_csv_record<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        fn optional<T: fmt::Display>(value: Option<T>) -> String {
+            value.map(|value| value.to_string()).unwrap_or_default()
+        }
+
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{}",
+            csv_field(&self.path.display().to_string()),
+            self.size,
+            optional(self.byte_order.map(byte_order_name)),
+            optional(self.kind.map(|kind| kind.get_name())),
+            optional(self.header_checksum),
+            optional(self.calculated_checksum),
+            self.status.get_name(),
+            optional(self.error.as_ref().map(|e| e.variant_name())),
+        )
+    }
+}
+
+fn byte_order_name(order: RomByteOrder) -> &'static str {
+    match order {
+        RomByteOrder::BigEndian => "BigEndian",
+        RomByteOrder::ByteSwapped => "ByteSwapped",
+        RomByteOrder::LittleEndian => "LittleEndian",
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The format used by `write_batch_report`.
+pub enum ReportFormat {
+    /// One JSON object per line, per file.
+    JsonLines,
+    /// Comma separated values, with a header row.
+    Csv,
+}
+
+/// Writes a machine readable report of the checked files, one record per entry.
+///
+/// Missing values are written as `null` in JSON Lines and as empty fields in CSV. Checksums are written in the
+/// `XXXXXXXX XXXXXXXX` form, while byte orders, CIC kinds, statuses and errors are written as the name of their
+/// variant.
+pub fn write_batch_report<W: Write>(
+    mut writer: W,
+    entries: &[BatchEntry],
+    format: ReportFormat,
+) -> io::Result<()> {
+    if format == ReportFormat::Csv {
+        writeln!(
+            writer,
+            "path,size,byte_order,kind,header_checksum,calculated_checksum,status,error"
+        )?;
+    }
+
+    for entry in entries {
+        match format {
+            ReportFormat::JsonLines => entry.write_json_line(&mut writer)?,
+            ReportFormat::Csv => entry.write_csv_record(&mut writer)?,
+        }
+    }
+
+    writer.flush()
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+// The error names are borrowed from `BatchError::variant_name`, so this can't be deserialized
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Counts of the outcomes of a batch.
+///
+/// Only the non zero counts are kept. Statuses and kinds are listed in declaration order, and errors in the order
+/// they were first found.
+pub struct BatchSummary {
+    /// How many files were checked.
+    pub total: usize,
+    /// How many files ended with each status.
+    pub statuses: Vec<(BatchStatus, usize)>,
+    /// How many ROMs were verified with each CIC kind.
+    pub kinds: Vec<(CICKind, usize)>,
+    /// How many files failed with each error variant, by `BatchError::variant_name`.
+    pub errors: Vec<(&'static str, usize)>,
+}
+
+impl BatchSummary {
+    pub fn new(entries: &[BatchEntry]) -> Self {
+        let statuses = BatchStatus::ALL
+            .into_iter()
+            .map(|status| {
+                (
+                    status,
+                    entries.iter().filter(|e| e.status == status).count(),
+                )
+            })
+            .filter(|(_, count)| *count != 0)
+            .collect();
+
+        let kinds = CICKind::iter()
+            .map(|kind| {
+                (
+                    kind,
+                    entries.iter().filter(|e| e.kind == Some(kind)).count(),
+                )
+            })
+            .filter(|(_, count)| *count != 0)
+            .collect();
+
+        let mut errors: Vec<(&'static str, usize)> = Vec::new();
+        for entry in entries {
+            // Not being a ROM is not a failure
+            if entry.status == BatchStatus::NotARom {
+                continue;
+            }
+            if let Some(error) = &entry.error {
+                let name = error.variant_name();
+                match errors.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, count)) => *count += 1,
+                    None => errors.push((name, 1)),
+                }
+            }
+        }
+
+        Self {
+            total: entries.len(),
+            statuses,
+            kinds,
+            errors,
+        }
+    }
+
+    /// How many files ended with the given status.
+    pub fn count(&self, status: BatchStatus) -> usize {
+        self.statuses
+            .iter()
+            .find(|(s, _)| *s == status)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// How many files failed, as defined by `BatchStatus::is_failure`.
+    pub fn failures(&self) -> usize {
+        self.statuses
+            .iter()
+            .filter(|(status, _)| status.is_failure())
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Total files: {}", self.total)?;
+
+        writeln!(f, "By status:")?;
+        for (status, count) in &self.statuses {
+            writeln!(f, "    {:<24} {}", status.get_name(), count)?;
+        }
+
+        if !self.kinds.is_empty() {
+            writeln!(f, "By CIC kind:")?;
+            for (kind, count) in &self.kinds {
+                writeln!(f, "    {:<24} {}", kind.get_name(), count)?;
+            }
+        }
+
+        if !self.errors.is_empty() {
+            writeln!(f, "By error:")?;
+            for (name, count) in &self.errors {
+                writeln!(f, "    {:<24} {}", name, count)?;
+            }
+        }
+
+        write!(f, "Total failures: {}", self.failures())
+    }
+}
+
+fn read_sorted_dir(path: &Path) -> io::Result<Vec<(PathBuf, fs::FileType)>> {
+    let mut children = fs::read_dir(path)?
+        .map(|entry| entry.and_then(|e| Ok((e.path(), e.file_type()?))))
+        .collect::<io::Result<Vec<_>>>()?;
+    children.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(children)
+}
+
+fn collect_paths_into(
+    children: Vec<(PathBuf, fs::FileType)>,
+    paths: &mut Vec<PathBuf>,
+    unreadable: &mut Vec<(PathBuf, io::ErrorKind)>,
+) {
+    for (child, file_type) in children {
+        let hidden = child
+            .file_name()
+            .map_or(false, |name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            match read_sorted_dir(&child) {
+                Ok(grandchildren) => collect_paths_into(grandchildren, paths, unreadable),
+                Err(e) => unreadable.push((child, e.kind())),
+            }
+        } else if file_type.is_symlink() && child.is_dir() {
+            // Symlinked directories are not followed, so a link to a parent can't make the walk loop forever
+            continue;
+        } else {
+            paths.push(child);
+        }
+    }
+}
+
+/// Lists the files inside `path`, and the subdirectories which could not be read with their error.
+fn walk_rom_paths(
+    path: &Path,
+    paths: &mut Vec<PathBuf>,
+    unreadable: &mut Vec<(PathBuf, io::ErrorKind)>,
+) -> io::Result<()> {
+    if !path.is_dir() {
+        // Make sure the file exists
+        fs::metadata(path)?;
+        paths.push(path.to_path_buf());
+    } else {
+        collect_paths_into(read_sorted_dir(path)?, paths, unreadable);
+    }
+
+    Ok(())
+}
+
+/// Lists every file inside `path` and its subdirectories, sorted by path. Hidden files and directories (the ones
+/// starting with a dot) are skipped.
+///
+/// Symlinks to files are listed like any other file, but symlinks to directories are not followed. Subdirectories
+/// which can't be read are skipped, `verify_directory` reports them instead.
+///
+/// If `path` is a file then it is the only one returned.
+pub fn collect_rom_paths<P: AsRef<Path>>(path: P) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    walk_rom_paths(path.as_ref(), &mut paths, &mut Vec::new())?;
+    Ok(paths)
+}
+
+/// Checks the checksum of every given file. The files are processed in parallel, on as many threads as the system
+/// provides.
+///
+/// ROMs of any byte order are accepted. The CIC kind is detected as in `verify_rom`, so ROMs with an unknown IPL3
+/// are checked against every CIC kind.
+///
+/// ## Arguments
+///
+/// * `paths` - The files to check.
+///
+/// ## Return
+///
+/// * A `BatchEntry` for each path, in the same order. Failing to check a file is reported on its entry instead of
+///   stopping the batch.
+pub fn verify_paths(paths: &[PathBuf]) -> Vec<BatchEntry> {
+    let thread_count = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(paths.len());
+
+    let paths = Arc::new(paths.to_vec());
+    let next_path = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..thread_count)
+        .map(|_| {
+            let paths = Arc::clone(&paths);
+            let next_path = Arc::clone(&next_path);
+
+            thread::spawn(move || {
+                let mut checked = Vec::new();
+
+                loop {
+                    let index = next_path.fetch_add(1, Ordering::Relaxed);
+                    let path = match paths.get(index) {
+                        Some(path) => path,
+                        None => break,
+                    };
+
+                    checked.push((index, BatchEntry::check(path.clone())));
+                }
+
+                checked
+            })
+        })
+        .collect();
+
+    let mut entries = Vec::with_capacity(paths.len());
+    for handle in handles {
+        match handle.join() {
+            Ok(checked) => entries.extend(checked),
+            Err(e) => std::panic::resume_unwind(e),
+        }
+    }
+    entries.sort_unstable_by_key(|(index, _)| *index);
+
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Checks the checksum of every file inside `path` and its subdirectories, in parallel.
+///
+/// The files are listed with `collect_rom_paths` and checked with `verify_paths`.
+///
+/// ## Arguments
+///
+/// * `path` - The directory to walk. It may also be a single file.
+///
+/// ## Return
+///
+/// * A `BatchEntry` for each file, sorted by path. Subdirectories which can't be read get an entry with a
+///   `BatchError::Io` error instead of stopping the batch. Only failing to list `path` itself is an error.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use ipl3checksum;
+/// let entries = ipl3checksum::verify_directory("roms").unwrap();
+/// let summary = ipl3checksum::BatchSummary::new(&entries);
+///
+/// ipl3checksum::write_batch_report(std::io::stdout(), &entries, ipl3checksum::ReportFormat::Csv).unwrap();
+/// eprintln!("{}", summary);
+/// ```
+pub fn verify_directory<P: AsRef<Path>>(path: P) -> io::Result<Vec<BatchEntry>> {
+    let mut paths = Vec::new();
+    let mut unreadable = Vec::new();
+    walk_rom_paths(path.as_ref(), &mut paths, &mut unreadable)?;
+
+    let mut entries = verify_paths(&paths);
+    entries.extend(unreadable.into_iter().map(|(dir, kind)| {
+        let mut entry = BatchEntry::new(dir);
+        entry.error = Some(BatchError::Io(kind));
+        entry
+    }));
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchError, BatchStatus, BatchSummary, ReportFormat};
+    use crate::{Ipl3ChecksumError, RomByteOrder};
+    use std::fs;
+
+    #[test]
+    fn test_batch_dummy_files() {
+        let dir = std::env::temp_dir().join(format!("ipl3checksum_batch_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+
+        let orders = [
+            RomByteOrder::BigEndian,
+            RomByteOrder::ByteSwapped,
+            RomByteOrder::LittleEndian,
+        ];
+        let mut rom_count = 0;
+
+        crate::tests::for_each_dummy_rom(|_, _, mut bin_bytes| {
+            // The dummy files don't have a PI configuration word
+            bin_bytes[..4].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+            crate::normalize_byte_order(&mut bin_bytes, orders[rom_count % orders.len()]);
+
+            fs::write(
+                dir.join("nested").join(format!("{}.z64", rom_count)),
+                &bin_bytes,
+            )?;
+            rom_count += 1;
+            Ok::<(), std::io::Error>(())
+        })
+        .unwrap();
+
+        fs::write(dir.join("not_a_rom.txt"), b"hello").unwrap();
+        fs::write(
+            dir.join("too_small.z64"),
+            [0x80, 0x37, 0x12, 0x40, 0, 0, 0, 0],
+        )
+        .unwrap();
+        fs::write(dir.join(".hidden.z64"), [0x80, 0x37, 0x12, 0x40]).unwrap();
+
+        let entries = super::verify_directory(&dir).unwrap();
+        assert_eq!(entries.len(), rom_count + 2);
+
+        // Sorted by path, so the nested ones come first
+        for (i, entry) in entries[..rom_count].iter().enumerate() {
+            println!("{:?}", entry);
+
+            assert_eq!(entry.byte_order, Some(orders[i % orders.len()]));
+            assert_eq!(entry.status, BatchStatus::Ok);
+            assert_eq!(entry.header_checksum, entry.calculated_checksum);
+            // Some kinds share their parameters, so the detected kind may not be the one of the folder
+            assert!(entry.kind.is_some());
+            assert_eq!(entry.error, None);
+        }
+
+        let not_a_rom = &entries[rom_count];
+        assert_eq!(not_a_rom.status, BatchStatus::NotARom);
+        assert_eq!(not_a_rom.size, 5);
+        assert_eq!(
+            not_a_rom.error,
+            Some(BatchError::Checksum(Ipl3ChecksumError::UnknownByteOrder))
+        );
+
+        let too_small = &entries[rom_count + 1];
+        assert_eq!(too_small.status, BatchStatus::Error);
+        assert_eq!(too_small.byte_order, Some(RomByteOrder::BigEndian));
+        assert_eq!(
+            too_small.error.as_ref().map(|e| e.variant_name()),
+            Some("BufferNotBigEnough")
+        );
+
+        let summary = BatchSummary::new(&entries);
+        println!("{}", summary);
+        assert_eq!(summary.total, entries.len());
+        assert_eq!(summary.count(BatchStatus::Ok), rom_count);
+        assert_eq!(summary.count(BatchStatus::NotARom), 1);
+        assert_eq!(summary.failures(), 1);
+        assert_eq!(summary.errors, [("BufferNotBigEnough", 1)]);
+
+        let mut jsonl = Vec::new();
+        super::write_batch_report(&mut jsonl, &entries, ReportFormat::JsonLines).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        assert_eq!(jsonl.lines().count(), entries.len());
+        assert!(jsonl
+            .lines()
+            .last()
+            .unwrap()
+            .ends_with("\"kind\":null,\"header_checksum\":null,\"calculated_checksum\":null,\"status\":\"Error\",\"error\":\"BufferNotBigEnough\"}"));
+
+        let mut csv = Vec::new();
+        super::write_batch_report(&mut csv, &entries, ReportFormat::Csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), entries.len() + 1);
+        assert!(csv.lines().nth(1).unwrap().contains(",BigEndian,"));
+
+        assert_eq!(super::json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(super::csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+
+        assert!(super::verify_directory(dir.join("does_not_exist")).is_err());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&entries).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Vec<super::BatchEntry>>(&json).unwrap(),
+                entries
+            );
+            assert!(serde_json::to_string(&summary)
+                .unwrap()
+                .contains("\"errors\":[[\"BufferNotBigEnough\",1]]"));
+
+            let error = BatchError::Io(std::io::ErrorKind::NotFound);
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(json, "{\"Io\":\"NotFound\"}");
+            assert_eq!(serde_json::from_str::<BatchError>(&json).unwrap(), error);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_batch_symlinks_and_unreadable_dirs() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir =
+            std::env::temp_dir().join(format!("ipl3checksum_batch_links_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("roms")).unwrap();
+        fs::create_dir_all(dir.join("unreadable")).unwrap();
+
+        let mut bin_bytes = fs::read("tests/dummytests/CIC_6102_7101/dummy.bin").unwrap();
+        bin_bytes[..4].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+        fs::write(dir.join("roms").join("a.z64"), &bin_bytes).unwrap();
+
+        // A link back to the root would make the walk loop forever if it was followed
+        symlink(&dir, dir.join("roms").join("loop")).unwrap();
+        symlink(
+            dir.join("roms").join("a.z64"),
+            dir.join("roms").join("b.z64"),
+        )
+        .unwrap();
+        symlink(
+            dir.join("missing.z64"),
+            dir.join("roms").join("dangling.z64"),
+        )
+        .unwrap();
+
+        fs::set_permissions(dir.join("unreadable"), fs::Permissions::from_mode(0o000)).unwrap();
+        // Permissions are not enforced for root
+        let can_read_unreadable = fs::read_dir(dir.join("unreadable")).is_ok();
+
+        assert_eq!(
+            super::collect_rom_paths(&dir).unwrap(),
+            [
+                dir.join("roms").join("a.z64"),
+                dir.join("roms").join("b.z64"),
+                dir.join("roms").join("dangling.z64"),
+            ]
+        );
+
+        let entries = super::verify_directory(&dir).unwrap();
+        for entry in &entries {
+            println!("{:?}", entry);
+        }
+
+        assert_eq!(entries[0].status, BatchStatus::Ok);
+        assert_eq!(entries[1].status, BatchStatus::Ok);
+        assert_eq!(entries[2].path, dir.join("roms").join("dangling.z64"));
+        assert_eq!(entries[2].status, BatchStatus::Error);
+        assert_eq!(
+            entries[2].error,
+            Some(BatchError::Io(std::io::ErrorKind::NotFound))
+        );
+
+        if can_read_unreadable {
+            assert_eq!(entries.len(), 3);
+        } else {
+            assert_eq!(entries.len(), 4);
+            assert_eq!(entries[3].path, dir.join("unreadable"));
+            assert_eq!(entries[3].status, BatchStatus::Error);
+            assert_eq!(
+                entries[3].error,
+                Some(BatchError::Io(std::io::ErrorKind::PermissionDenied))
+            );
+        }
+
+        fs::set_permissions(dir.join("unreadable"), fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
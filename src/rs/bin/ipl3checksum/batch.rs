@@ -0,0 +1,91 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+
+use clap::builder::PossibleValuesParser;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use ipl3checksum::{BatchEntry, BatchSummary, ReportFormat};
+
+fn write_report(entries: &[BatchEntry], format: ReportFormat, output: Option<&PathBuf>) -> bool {
+    let result = match output {
+        None => ipl3checksum::write_batch_report(io::stdout(), entries, format),
+        Some(output) => {
+            if let Some(parent) = output.parent() {
+                if let Err(e) = fs::create_dir_all(parent) {
+                    eprintln!("Unable to create '{}': {}", parent.display(), e);
+                    return false;
+                }
+            }
+            File::create(output).and_then(|file| {
+                ipl3checksum::write_batch_report(BufWriter::new(file), entries, format)
+            })
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Unable to write the report: {}", e);
+        return false;
+    }
+    true
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let path = matches
+        .get_one::<PathBuf>("path")
+        .expect("path is required");
+    let format = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("csv") => ReportFormat::Csv,
+        _ => ReportFormat::JsonLines,
+    };
+
+    let entries = match ipl3checksum::verify_directory(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Unable to read '{}': {}", path.display(), e);
+            return 1;
+        }
+    };
+
+    if !write_report(&entries, format, matches.get_one::<PathBuf>("output")) {
+        return 1;
+    }
+
+    // The report may be going to stdout, so keep the summary out of it
+    let summary = BatchSummary::new(&entries);
+    eprintln!("{}", summary);
+
+    if summary.failures() != 0 {
+        1
+    } else {
+        0
+    }
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("batch")
+        .about("Checks every ROM inside a directory and its subdirectories in parallel, writing a machine readable report")
+        .arg(
+            Arg::new("path")
+                .help("Directory to check recursively. A single ROM file is accepted too")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("format")
+                .help("Format of the report")
+                .short('f')
+                .long("format")
+                .value_parser(PossibleValuesParser::new(["jsonl", "csv"]))
+                .default_value("jsonl"),
+        )
+        .arg(
+            Arg::new("output")
+                .help("Path to write the report to, instead of stdout")
+                .short('o')
+                .long("output")
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
@@ -0,0 +1,76 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use clap::{ArgMatches, Command};
+use ipl3checksum::{CICKind, DetectionMethod};
+
+use crate::utils;
+
+fn do_check(rom_bytes: &[u8], kind: Option<CICKind>) -> i32 {
+    let report = match kind {
+        // Detect kind if none was specified by the user
+        None => ipl3checksum::verify_rom(rom_bytes),
+        Some(kind) => ipl3checksum::verify_rom_with_kind(rom_bytes, kind),
+    };
+
+    let report = match report {
+        Ok(report) => report,
+        Err(_) => {
+            println!("Unable to calculate checksum");
+            return 1;
+        }
+    };
+
+    let (kind, calculated_checksum) = match (report.kind, report.calculated_checksum) {
+        (Some(kind), Some(calculated_checksum)) => (kind, calculated_checksum),
+        _ => {
+            println!("Unable to detect CIC kind");
+            println!("Checksum in ROM:     {}", report.header_checksum);
+            return 1;
+        }
+    };
+
+    match report.detection_method {
+        Some(DetectionMethod::Ipl3Hash) => println!("Detected kind is '{}'", kind.get_name()),
+        Some(DetectionMethod::HeaderChecksum) => println!(
+            "Unknown IPL3, kind '{}' matches the checksum in the header",
+            kind.get_name()
+        ),
+        _ => {}
+    }
+
+    println!("Checksum in ROM:     {}", report.header_checksum);
+    println!("Calculated checksum: {}", calculated_checksum);
+
+    if !report.matches {
+        println!("Checksum doesn't match");
+        if let Some(alternative_kind) = report.alternative_kind {
+            println!(
+                "The checksum in the header matches kind '{}' instead",
+                alternative_kind.get_name()
+            );
+        }
+        return 1;
+    }
+
+    println!("Checksum matches");
+    0
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let rom_bytes = match utils::read_rom(utils::rom_path(matches)) {
+        Some(bytes) => bytes,
+        None => return 1,
+    };
+
+    do_check(&rom_bytes, utils::kind(matches))
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("check")
+        .about("Checks if the checksum in the header matches the calculated checksum")
+        .arg(utils::rom_path_arg())
+        .arg(utils::kind_arg(
+            "Use this variant to calculate the checksum instead of automatically detecting which kind the ROM uses",
+        ))
+}
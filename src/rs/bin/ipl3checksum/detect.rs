@@ -0,0 +1,35 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use clap::{ArgMatches, Command};
+
+use crate::utils;
+
+fn do_detect(rom_bytes: &[u8]) -> i32 {
+    match utils::detect_kind(rom_bytes) {
+        Some(kind) => {
+            println!("Detected kind is '{}'", kind.get_name());
+            0
+        }
+        None => {
+            println!("Unable to detect CIC kind");
+            1
+        }
+    }
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let rom_bytes = match utils::read_rom(utils::rom_path(matches)) {
+        Some(bytes) => bytes,
+        None => return 1,
+    };
+
+    do_detect(&rom_bytes)
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("detect")
+        .about("Detects the CIC variant of a given rom")
+        .alias("detect_cic")
+        .arg(utils::rom_path_arg())
+}
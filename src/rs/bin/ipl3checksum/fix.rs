@@ -0,0 +1,59 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs;
+use std::path::Path;
+
+use clap::{ArgMatches, Command};
+
+use crate::utils;
+
+fn do_fix(rom_bytes: &mut [u8], matches: &ArgMatches, rom_path: &Path) -> i32 {
+    let kind = match utils::kind_or_detect(matches, rom_bytes) {
+        Some(kind) => kind,
+        None => return 1,
+    };
+
+    let (old_checksum, new_checksum) = match ipl3checksum::fix_checksum(rom_bytes, kind) {
+        Ok(checksums) => checksums,
+        Err(_) => {
+            println!("Unable to calculate checksum");
+            return 1;
+        }
+    };
+
+    println!("Checksum in ROM:     {}", old_checksum);
+    println!("Calculated checksum: {}", new_checksum);
+
+    if old_checksum == new_checksum {
+        println!("Checksum already matches");
+        return 0;
+    }
+
+    println!("Writing updated ROM to '{}'", rom_path.display());
+    if let Err(e) = fs::write(rom_path, rom_bytes) {
+        println!("Unable to write '{}': {}", rom_path.display(), e);
+        return 1;
+    }
+
+    0
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let rom_path = utils::rom_path(matches);
+    let mut rom_bytes = match utils::read_rom(rom_path) {
+        Some(bytes) => bytes,
+        None => return 1,
+    };
+
+    do_fix(&mut rom_bytes, matches, rom_path)
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("fix")
+        .about("Calculates the ipl3 checksum of a big endian ROM and writes it to the ROM header in place")
+        .arg(utils::rom_path_arg())
+        .arg(utils::kind_arg(
+            "Use this variant to calculate the checksum instead of automatically detecting which kind the ROM uses",
+        ))
+}
@@ -0,0 +1,38 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use clap::{ArgMatches, Command};
+use ipl3checksum::RomInfo;
+
+use crate::utils;
+
+fn do_info(rom_bytes: &[u8]) -> i32 {
+    match RomInfo::from_rom(rom_bytes) {
+        Ok(info) => {
+            println!("{}", info);
+            0
+        }
+        Err(e) => {
+            println!("Unable to read the ROM information: {}", e);
+            1
+        }
+    }
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let rom_bytes = match utils::read_rom(utils::rom_path(matches)) {
+        Some(bytes) => bytes,
+        None => return 1,
+    };
+
+    do_info(&rom_bytes)
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("info")
+        .about("Prints everything known about a ROM: its header, byte order, CIC kind, entrypoint and checksums")
+        .arg(
+            utils::rom_path_arg()
+                .help("Path to a ROM file, in big endian, byteswapped or little endian format"),
+        )
+}
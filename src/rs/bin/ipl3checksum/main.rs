@@ -0,0 +1,38 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+mod batch;
+mod check;
+mod detect;
+mod fix;
+mod info;
+mod sum;
+mod utils;
+
+use clap::Command;
+
+fn main() {
+    let matches = Command::new("ipl3checksum")
+        .about("Interface to call any of the ipl3checksum's CLI utilities")
+        .version(ipl3checksum::version::VERSION_STR)
+        .subcommand_required(true)
+        .subcommand(batch::subcommand())
+        .subcommand(check::subcommand())
+        .subcommand(detect::subcommand())
+        .subcommand(fix::subcommand())
+        .subcommand(info::subcommand())
+        .subcommand(sum::subcommand())
+        .get_matches();
+
+    let exit_code = match matches.subcommand() {
+        Some(("batch", sub_matches)) => batch::process_arguments(sub_matches),
+        Some(("check", sub_matches)) => check::process_arguments(sub_matches),
+        Some(("detect", sub_matches)) => detect::process_arguments(sub_matches),
+        Some(("fix", sub_matches)) => fix::process_arguments(sub_matches),
+        Some(("info", sub_matches)) => info::process_arguments(sub_matches),
+        Some(("sum", sub_matches)) => sum::process_arguments(sub_matches),
+        _ => unreachable!("clap requires a subcommand"),
+    };
+
+    std::process::exit(exit_code);
+}
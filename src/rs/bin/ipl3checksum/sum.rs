@@ -0,0 +1,92 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+
+use crate::utils;
+
+fn do_sum(rom_bytes: &mut [u8], matches: &ArgMatches, output_path: Option<&Path>) -> i32 {
+    let kind = match utils::kind_or_detect(matches, rom_bytes) {
+        Some(kind) => kind,
+        None => return 1,
+    };
+
+    let checksum = match ipl3checksum::calculate_checksum(rom_bytes, kind) {
+        Ok(checksum) => checksum,
+        Err(_) => {
+            println!("Unable to calculate checksum");
+            return 1;
+        }
+    };
+
+    println!("Calculated checksum: {}", checksum);
+
+    if let Some(output_path) = output_path {
+        println!("Writing updated ROM to '{}'", output_path.display());
+
+        // The checksum was calculated, so the header is big enough
+        checksum
+            .write_to_header(rom_bytes)
+            .expect("the header was already read");
+
+        if let Some(parent) = output_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                println!("Unable to create '{}': {}", parent.display(), e);
+                return 1;
+            }
+        }
+        if let Err(e) = fs::write(output_path, rom_bytes) {
+            println!("Unable to write '{}': {}", output_path.display(), e);
+            return 1;
+        }
+    }
+
+    0
+}
+
+pub(crate) fn process_arguments(matches: &ArgMatches) -> i32 {
+    let rom_path = utils::rom_path(matches);
+    let mut rom_bytes = match utils::read_rom(rom_path) {
+        Some(bytes) => bytes,
+        None => return 1,
+    };
+
+    let output_path = if matches.get_flag("update") {
+        Some(
+            matches
+                .get_one::<PathBuf>("output")
+                .unwrap_or(rom_path)
+                .as_path(),
+        )
+    } else {
+        None
+    };
+
+    do_sum(&mut rom_bytes, matches, output_path)
+}
+
+pub(crate) fn subcommand() -> Command {
+    Command::new("sum")
+        .about("Calculates the ipl3 checksum of a big endian ROM by detecting the CIC it uses and optionally update it.")
+        .arg(utils::rom_path_arg())
+        .arg(utils::kind_arg(
+            "Use this variant to calculate the checksum instead of automatically detecting which kind the ROM uses",
+        ))
+        .arg(
+            Arg::new("update")
+                .help("Updates the ROM with the calculated checksum. This option modifies the input rom unless `--output` is used")
+                .short('u')
+                .long("update")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output")
+                .help("Path to write the updated ROM. This option is ignored if `--update` is not used")
+                .short('o')
+                .long("output")
+                .value_parser(value_parser!(PathBuf)),
+        )
+}
@@ -0,0 +1,77 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::builder::PossibleValuesParser;
+use clap::{value_parser, Arg, ArgMatches};
+use ipl3checksum::CICKind;
+
+pub(crate) fn rom_path_arg() -> Arg {
+    Arg::new("rom_path")
+        .help("Path to a big endian ROM file")
+        .required(true)
+        .value_parser(value_parser!(PathBuf))
+}
+
+pub(crate) fn kind_arg(help: &'static str) -> Arg {
+    Arg::new("kind")
+        .help(help)
+        .short('k')
+        .short_alias('c')
+        .long("kind")
+        .alias("cic")
+        .value_name("KIND")
+        .value_parser(PossibleValuesParser::new(CICKind::valid_names()))
+}
+
+pub(crate) fn rom_path(matches: &ArgMatches) -> &PathBuf {
+    matches
+        .get_one::<PathBuf>("rom_path")
+        .expect("rom_path is required")
+}
+
+/// The kind passed by the user, if any. The name was already validated by clap.
+pub(crate) fn kind(matches: &ArgMatches) -> Option<CICKind> {
+    matches
+        .get_one::<String>("kind")
+        .map(|name| name.parse().expect("validated by clap"))
+}
+
+pub(crate) fn read_rom(path: &Path) -> Option<Vec<u8>> {
+    match fs::read(path) {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            println!("Unable to read '{}': {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Like `ipl3checksum::detect_cic`, but also failing for ROMs too small to contain an IPL3.
+pub(crate) fn detect_kind(rom_bytes: &[u8]) -> Option<CICKind> {
+    if rom_bytes.len() < 0x1000 {
+        return None;
+    }
+
+    ipl3checksum::detect_cic(rom_bytes).ok()
+}
+
+/// Detects the kind if none was specified by the user, reporting it.
+pub(crate) fn kind_or_detect(matches: &ArgMatches, rom_bytes: &[u8]) -> Option<CICKind> {
+    if let Some(kind) = kind(matches) {
+        return Some(kind);
+    }
+
+    match detect_kind(rom_bytes) {
+        Some(kind) => {
+            println!("Detected kind is '{}'", kind.get_name());
+            Some(kind)
+        }
+        None => {
+            println!("Unable to detect CIC kind");
+            None
+        }
+    }
+}
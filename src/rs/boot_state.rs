@@ -0,0 +1,508 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::checksum::HEADER_IPL3_SIZE;
+use crate::{detect, utils, CICKind, Ipl3ChecksumError};
+
+/// Offset of the ROM where the IPL3 starts copying the game code from.
+pub const BOOT_ROM_COPY_OFFSET: u32 = 0x1000;
+
+/// Amount of bytes the IPL3 copies from the ROM to the entrypoint.
+pub const BOOT_ROM_COPY_SIZE: u32 = 0x100000;
+
+/// Offset of the ROM, inside the IPL3, of the table the 6105 IPL3 copies to `BOOT_X105_TABLE_ADDRESS`.
+pub const BOOT_X105_TABLE_OFFSET: u32 = 0x554;
+
+/// Amount of bytes of the table copied by the 6105 IPL3.
+pub const BOOT_X105_TABLE_SIZE: u32 = 0x330;
+
+/// Address the 6105 IPL3 copies its table to.
+pub const BOOT_X105_TABLE_ADDRESS: u32 = 0xA0000004;
+
+/// Size of the RDRAM of a console without the Expansion Pak.
+pub const BOOT_RDRAM_SIZE: u32 = 0x400000;
+
+/// Maximum amount of words returned by `BootState::memory_writes`.
+pub const BOOT_MAX_MEMORY_WRITES: usize = 1 + X105_IMEM_WRITES.len();
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/boot_state.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The video standard of the console, as passed to the game on `osTvType`.
+pub enum TvType {
+    Pal,
+    Ntsc,
+    Mpal,
+}
+
+impl TvType {
+    /// The value stored on the `s4` register.
+    pub fn value(&self) -> u32 {
+        match self {
+            Self::Pal => 0,
+            Self::Ntsc => 1,
+            Self::Mpal => 2,
+        }
+    }
+}
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/boot_state.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum", get_all))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A word written by the IPL3 outside of the copied game code.
+pub struct MemoryWrite {
+    /// Virtual address of the word.
+    pub address: u32,
+    /// The written word.
+    pub value: u32,
+}
+
+/// The routine the 6105 IPL3 leaves on SP IMEM, which polls the PIF RAM. These are the same words mupen64plus
+/// writes on its HLE boot.
+const X105_IMEM_WRITES: [MemoryWrite; 8] = [
+    MemoryWrite {
+        address: 0xA4001004,
+        value: 0x3C0DBFC0,
+    },
+    MemoryWrite {
+        address: 0xA4001008,
+        value: 0x8DA807FC,
+    },
+    MemoryWrite {
+        address: 0xA400100C,
+        value: 0x25AD07C0,
+    },
+    MemoryWrite {
+        address: 0xA4001010,
+        value: 0x31080080,
+    },
+    MemoryWrite {
+        address: 0xA4001014,
+        value: 0x5500FFFC,
+    },
+    MemoryWrite {
+        address: 0xA4001018,
+        value: 0x3C0DBFC0,
+    },
+    MemoryWrite {
+        address: 0xA400101C,
+        value: 0x8DA80024,
+    },
+    MemoryWrite {
+        address: 0xA4001020,
+        value: 0x3C0BB000,
+    },
+];
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/boot_state.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum", get_all))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The state the IPL3 leaves the console in when jumping to the entrypoint of the game.
+///
+/// Meant for emulators which skip running the PIF ROM and the IPL3 (HLE boot). The IPL3 passes some values to the
+/// game on the `s3` to `s7` registers, which libultra stores on `osRomType`, `osTvType`, `osResetType`, `osCicId`
+/// and `osVersion` respectively.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let state = ipl3checksum::BootState::for_kind(
+///     ipl3checksum::CICKind::CIC_X106,
+///     0x80200400,
+///     ipl3checksum::TvType::Ntsc,
+/// );
+/// assert_eq!(state.entrypoint, 0x80000400);
+/// assert_eq!(state.registers()[3], (22, 0x85));
+/// ```
+pub struct BootState {
+    /// The CIC kind of the ROM.
+    pub kind: CICKind,
+    /// Kind of media the game booted from, stored on `s3`. 0 for cartridges.
+    pub rom_type: u32,
+    /// The video standard of the console, stored on `s4`.
+    pub tv_type: TvType,
+    /// Kind of reset, stored on `s5`. 0 for a cold boot.
+    pub reset_type: u32,
+    /// Seed of the CIC, stored on `s6`.
+    pub seed: u32,
+    /// Stored on `s7`. 6 on PAL and MPAL consoles and 0 on NTSC ones, the values mupen64plus's HLE boot uses
+    /// (`r4300_reset_soft`).
+    pub version: u32,
+    /// Address the game code is copied to, and where the IPL3 jumps to.
+    pub entrypoint: u32,
+    /// Offset of the ROM where the copied game code starts.
+    pub rom_copy_offset: u32,
+    /// Amount of bytes copied from the ROM to `entrypoint`.
+    pub rom_copy_size: u32,
+    /// Address the 6105 IPL3 copies a table from its own ROM range to, `BOOT_X105_TABLE_ADDRESS`. 0 for every
+    /// other kind.
+    pub table_copy_address: u32,
+    /// Offset of the ROM where the table copied to `table_copy_address` starts.
+    pub table_copy_offset: u32,
+    /// Amount of bytes copied from the ROM to `table_copy_address`. 0 for every kind but the 6105.
+    pub table_copy_size: u32,
+    /// Size in bytes of the RDRAM, which the IPL3 stores on `osMemSize`. `BOOT_RDRAM_SIZE` by default, set it to
+    /// 0x800000 to emulate a console with the Expansion Pak.
+    pub rdram_size: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// The words returned by `BootState::memory_writes`.
+pub struct MemoryWrites {
+    writes: [MemoryWrite; BOOT_MAX_MEMORY_WRITES],
+    len: usize,
+}
+
+impl MemoryWrites {
+    /// The writes, in the order they have to be applied.
+    pub fn as_slice(&self) -> &[MemoryWrite] {
+        &self.writes[..self.len]
+    }
+}
+
+impl BootState {
+    /// The boot state for a cold boot of a cartridge with the given CIC kind.
+    ///
+    /// ## Arguments
+    ///
+    /// * `kind` - The CIC kind of the ROM.
+    /// * `header_entrypoint` - The entrypoint specified on the ROM header.
+    /// * `tv_type` - The video standard of the emulated console.
+    ///
+    /// The console is assumed to not have the Expansion Pak, see `rdram_size`.
+    pub fn for_kind(kind: CICKind, header_entrypoint: u32, tv_type: TvType) -> Self {
+        let (table_copy_address, table_copy_offset, table_copy_size) = match kind {
+            CICKind::CIC_X105 => (
+                BOOT_X105_TABLE_ADDRESS,
+                BOOT_X105_TABLE_OFFSET,
+                BOOT_X105_TABLE_SIZE,
+            ),
+            _ => (0, 0, 0),
+        };
+
+        Self {
+            kind,
+            rom_type: 0,
+            tv_type,
+            reset_type: 0,
+            seed: kind.get_seed(),
+            version: match tv_type {
+                TvType::Pal | TvType::Mpal => 6,
+                TvType::Ntsc => 0,
+            },
+            entrypoint: kind.get_entrypoint(header_entrypoint),
+            rom_copy_offset: BOOT_ROM_COPY_OFFSET,
+            rom_copy_size: BOOT_ROM_COPY_SIZE,
+            table_copy_address,
+            table_copy_offset,
+            table_copy_size,
+            rdram_size: BOOT_RDRAM_SIZE,
+        }
+    }
+
+    /// The boot state for a cold boot of a cartridge. The CIC kind is detected from the IPL3 of the ROM.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x1000 bytes.
+    /// * `tv_type` - The video standard of the emulated console.
+    ///
+    /// ## Return
+    ///
+    /// * The boot state of the ROM. Possible errors:
+    ///     - `rom_bytes` not being big enough
+    ///     - Not able to detect the CIC kind
+    pub fn for_rom(rom_bytes: &[u8], tv_type: TvType) -> Result<Self, Ipl3ChecksumError> {
+        if rom_bytes.len() < HEADER_IPL3_SIZE {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: HEADER_IPL3_SIZE,
+            });
+        }
+
+        let kind = detect::detect_cic(rom_bytes)?;
+        let header_entrypoint = utils::read_u32(rom_bytes, 0x08)?;
+
+        Ok(Self::for_kind(kind, header_entrypoint, tv_type))
+    }
+
+    /// The values the IPL3 leaves on the `s3` to `s7` registers, paired with the register number.
+    pub fn registers(&self) -> [(usize, u32); 5] {
+        [
+            (19, self.rom_type),
+            (20, self.tv_type.value()),
+            (21, self.reset_type),
+            (22, self.seed),
+            (23, self.version),
+        ]
+    }
+
+    /// The part of the ROM which is copied to `entrypoint`.
+    ///
+    /// The returned slice is shorter than `rom_copy_size` if the ROM is smaller than the copied range.
+    pub fn rom_copy<'a>(&self, rom_bytes: &'a [u8]) -> &'a [u8] {
+        let start = (self.rom_copy_offset as usize).min(rom_bytes.len());
+        let end = (start + self.rom_copy_size as usize).min(rom_bytes.len());
+
+        &rom_bytes[start..end]
+    }
+
+    /// The part of the ROM which is copied to `table_copy_address`. Empty for every kind but the 6105.
+    ///
+    /// The returned slice is shorter than `table_copy_size` if the ROM is smaller than the copied range.
+    pub fn table_copy<'a>(&self, rom_bytes: &'a [u8]) -> &'a [u8] {
+        let start = (self.table_copy_offset as usize).min(rom_bytes.len());
+        let end = (start + self.table_copy_size as usize).min(rom_bytes.len());
+
+        &rom_bytes[start..end]
+    }
+
+    /// Words written by the IPL3 besides the copied ROM ranges, which have to be applied after the copies.
+    ///
+    /// Every IPL3 stores `rdram_size` on `osMemSize`, at 0x80000318, or at 0x800003F0 for the 6105. The 6105 IPL3
+    /// also leaves a small routine on SP IMEM, starting at 0xA4001004.
+    pub fn memory_writes(&self) -> MemoryWrites {
+        let mut writes = MemoryWrites {
+            writes: [MemoryWrite {
+                address: 0,
+                value: 0,
+            }; BOOT_MAX_MEMORY_WRITES],
+            len: 1,
+        };
+
+        writes.writes[0] = MemoryWrite {
+            address: match self.kind {
+                CICKind::CIC_X105 => 0x800003F0,
+                _ => 0x80000318,
+            },
+            value: self.rdram_size,
+        };
+        if self.kind == CICKind::CIC_X105 {
+            writes.writes[1..].copy_from_slice(&X105_IMEM_WRITES);
+            writes.len += X105_IMEM_WRITES.len();
+        }
+
+        writes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BootState, MemoryWrite, TvType};
+    use crate::{CICKind, Ipl3ChecksumError};
+
+    #[test]
+    fn test_boot_state() {
+        for kind in CICKind::iter() {
+            let state = BootState::for_kind(kind, 0x80200400, TvType::Ntsc);
+
+            assert_eq!(state.seed, kind.get_seed());
+            assert_eq!(state.entrypoint, kind.get_entrypoint(0x80200400));
+            assert_eq!(
+                state.registers(),
+                [(19, 0), (20, 1), (21, 0), (22, kind.get_seed()), (23, 0)]
+            );
+
+            if kind == CICKind::CIC_X105 {
+                assert_eq!(
+                    (
+                        state.table_copy_address,
+                        state.table_copy_offset,
+                        state.table_copy_size
+                    ),
+                    (0xA0000004, 0x554, 0x330)
+                );
+            } else {
+                assert_eq!(state.table_copy_size, 0);
+                assert!(state.table_copy(&[0; 0x1000]).is_empty());
+            }
+
+            let writes = state.memory_writes();
+            let mem_size_address = if kind == CICKind::CIC_X105 {
+                assert_eq!(writes.as_slice().len(), 9);
+                assert_eq!(writes.as_slice()[1].address, 0xA4001004);
+                assert_eq!(writes.as_slice()[8].address, 0xA4001020);
+                0x800003F0
+            } else {
+                assert_eq!(writes.as_slice().len(), 1);
+                0x80000318
+            };
+            assert_eq!(
+                writes.as_slice()[0],
+                MemoryWrite {
+                    address: mem_size_address,
+                    value: 0x400000
+                }
+            );
+
+            // With the Expansion Pak
+            let state = BootState {
+                rdram_size: 0x800000,
+                ..state
+            };
+            assert_eq!(state.memory_writes().as_slice()[0].value, 0x800000);
+        }
+
+        let state = BootState::for_kind(CICKind::CIC_7102, 0x80000400, TvType::Pal);
+        assert_eq!(state.entrypoint, 0x80000480);
+        assert_eq!(state.registers()[1], (20, 0));
+        assert_eq!(state.registers()[4], (23, 6));
+        assert_eq!(
+            BootState::for_kind(CICKind::CIC_7102, 0x80000400, TvType::Mpal).registers()[4],
+            (23, 6)
+        );
+
+        let mut bytes = vec![0; 0x101000];
+        bytes[0x1000] = 0x12;
+        bytes[0x100FFF] = 0x34;
+        let copy = state.rom_copy(&bytes);
+        assert_eq!(copy.len(), 0x100000);
+        assert_eq!((copy[0], copy[0xFFFFF]), (0x12, 0x34));
+        assert_eq!(state.rom_copy(&bytes[..0x2000]).len(), 0x1000);
+        assert!(state.rom_copy(&bytes[..0x800]).is_empty());
+
+        // The 6105 table is inside the IPL3
+        let x105 = BootState::for_kind(CICKind::CIC_X105, 0x80000400, TvType::Ntsc);
+        bytes[0x554] = 0x56;
+        bytes[0x883] = 0x78;
+        let table = x105.table_copy(&bytes);
+        assert_eq!(table.len(), 0x330);
+        assert_eq!((table[0], table[0x32F]), (0x56, 0x78));
+        assert_eq!(x105.table_copy(&bytes[..0x600]).len(), 0xAC);
+        bytes[0x554] = 0;
+        bytes[0x883] = 0;
+
+        // There's no ipl3 binary on an array of zeroes
+        assert_eq!(
+            BootState::for_rom(&bytes, TvType::Ntsc),
+            Err(Ipl3ChecksumError::UnableToDetectCIC)
+        );
+        assert!(matches!(
+            BootState::for_rom(&bytes[..0x800], TvType::Ntsc),
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+#[allow(unknown_lints, non_local_definitions)]
+mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::PyBytes;
+    use std::borrow::Cow;
+
+    use crate::{CICKind, Ipl3ChecksumError};
+
+    #[pymethods]
+    impl super::BootState {
+        #[staticmethod]
+        pub fn forKind(kind: CICKind, header_entrypoint: u32, tv_type: super::TvType) -> Self {
+            Self::for_kind(kind, header_entrypoint, tv_type)
+        }
+
+        #[staticmethod]
+        pub fn forRom(
+            rom_bytes: Cow<[u8]>,
+            tv_type: super::TvType,
+        ) -> Result<Option<Self>, Ipl3ChecksumError> {
+            match Self::for_rom(&rom_bytes, tv_type) {
+                Ok(state) => Ok(Some(state)),
+                Err(e) => match e {
+                    Ipl3ChecksumError::BufferNotBigEnough { .. } => Ok(None),
+                    Ipl3ChecksumError::UnableToDetectCIC => Ok(None),
+                    _ => Err(e),
+                },
+            }
+        }
+
+        pub fn getRegisters(&self) -> Vec<(usize, u32)> {
+            self.registers().to_vec()
+        }
+
+        pub fn romCopy<'py>(&self, py: Python<'py>, rom_bytes: Cow<[u8]>) -> &'py PyBytes {
+            PyBytes::new(py, self.rom_copy(&rom_bytes))
+        }
+
+        pub fn tableCopy<'py>(&self, py: Python<'py>, rom_bytes: Cow<[u8]>) -> &'py PyBytes {
+            PyBytes::new(py, self.table_copy(&rom_bytes))
+        }
+
+        pub fn memoryWrites(&self) -> Vec<super::MemoryWrite> {
+            self.memory_writes().as_slice().to_vec()
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    use crate::{utils, BootState, CICKind, Ipl3ChecksumError, MemoryWrite, TvType};
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_boot_state_for_kind(
+        kind: CICKind,
+        header_entrypoint: u32,
+        tv_type: TvType,
+    ) -> BootState {
+        BootState::for_kind(kind, header_entrypoint, tv_type)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_boot_state_for_rom(
+        dst_state: *mut BootState,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        tv_type: TvType,
+    ) -> Ipl3ChecksumError {
+        if dst_state.is_null() || rom_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(
+            rom_bytes_len.min(super::HEADER_IPL3_SIZE),
+            rom_bytes,
+        ) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let state = match BootState::for_rom(&bytes, tv_type) {
+            Err(e) => return e,
+            Ok(s) => s,
+        };
+
+        unsafe { *dst_state = state };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_boot_state_memory_writes(
+        state: BootState,
+        dst_writes: *mut [MemoryWrite; super::BOOT_MAX_MEMORY_WRITES],
+        dst_writes_len: *mut usize,
+    ) -> Ipl3ChecksumError {
+        if dst_writes.is_null() || dst_writes_len.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let writes = state.memory_writes();
+        let writes = writes.as_slice();
+
+        let dst_writes = unsafe { &mut *dst_writes };
+        dst_writes[..writes.len()].copy_from_slice(writes);
+        unsafe { *dst_writes_len = writes.len() };
+
+        Ipl3ChecksumError::Okay
+    }
+}
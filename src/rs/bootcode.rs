@@ -0,0 +1,566 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use crate::{utils, CICKind, Ipl3ChecksumError};
+
+/// Size of the IPL3 hashed by the IPL2, the same bytes `detect_cic_raw` expects.
+pub const BOOTCODE_SIZE: usize = 0xFC0;
+
+/// Amount of words of the IPL3 hashed by the IPL2.
+pub(crate) const BOOTCODE_WORDS: usize = BOOTCODE_SIZE / 4;
+
+/// Only the lower 48 bits of the boot code checksum are sent by the CIC.
+pub const BOOTCODE_CHECKSUM_MASK: u64 = 0xFFFF_FFFF_FFFF;
+
+const BOOTCODE_MAGIC: u32 = 0x6C078965;
+
+/// The multiplication based mixing function used everywhere by the IPL2.
+///
+/// The difference between the high and low words of the product, or `a` if both are the same.
+fn mix(a: u32, b: u32, index: u32) -> u32 {
+    let b = if b == 0 { index } else { b };
+
+    let product = a as u64 * b as u64;
+    let diff = ((product >> 32) as u32).wrapping_sub(product as u32);
+
+    if diff == 0 {
+        a
+    } else {
+        diff
+    }
+}
+
+/// The 16 registers the IPL2 accumulates while hashing the IPL3.
+///
+/// The IPL2 shifts with the `sllv`/`srlv` instructions, which only use the lower 5 bits of the amount, so each
+/// shift pair of the original code is a rotation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BootcodeState {
+    buf: [u32; 16],
+}
+
+impl BootcodeState {
+    pub(crate) fn new(seed: u32, first_word: u32) -> Self {
+        let init = BOOTCODE_MAGIC.wrapping_mul(seed & 0xFF).wrapping_add(1) ^ first_word;
+
+        Self { buf: [init; 16] }
+    }
+
+    /// Runs the `i`th iteration of the IPL2 loop, counting from 1.
+    ///
+    /// Each iteration reads the word `i - 1` along with the one before and after it, so a word only affects the
+    /// iterations around its own.
+    #[inline]
+    pub(crate) fn update(&mut self, words: &[u32], i: usize) {
+        let buf = &mut self.buf;
+
+        let prev = words[i.saturating_sub(2)];
+        let data = words[i - 1];
+        let i = i as u32;
+
+        buf[0] = buf[0].wrapping_add(mix(0x3EFu32.wrapping_sub(i), data, i));
+        buf[1] = mix(buf[1], data, i);
+        buf[2] ^= data;
+        buf[3] = buf[3].wrapping_add(mix(data.wrapping_add(5), BOOTCODE_MAGIC, i));
+
+        if prev < data {
+            buf[9] = mix(buf[9], data, i);
+        } else {
+            buf[9] = buf[9].wrapping_add(data);
+        }
+
+        buf[4] = buf[4].wrapping_add(data.rotate_right(prev & 0x1F));
+        buf[7] = mix(buf[7], data.rotate_left(prev & 0x1F), i);
+
+        if data < buf[6] {
+            buf[6] = data.wrapping_add(i) ^ buf[3].wrapping_add(buf[6]);
+        } else {
+            buf[6] ^= buf[4].wrapping_add(data);
+        }
+
+        buf[5] = buf[5].wrapping_add(data.rotate_left(prev >> 27));
+        buf[8] = mix(buf[8], data.rotate_right(prev >> 27), i);
+
+        let next = match words.get(i as usize) {
+            Some(next) => *next,
+            None => return,
+        };
+
+        buf[15] = mix(
+            mix(buf[15], data.rotate_left(prev >> 27), i),
+            next.rotate_left(data >> 27),
+            i,
+        );
+        buf[14] = mix(
+            mix(buf[14], data.rotate_right(prev & 0x1F), i),
+            next.rotate_right(data & 0x1F),
+            i,
+        );
+        buf[13] = buf[13]
+            .wrapping_add(data.rotate_right(data & 0x1F))
+            .wrapping_add(next.rotate_right(next & 0x1F));
+        buf[10] = mix(buf[10].wrapping_add(data), next, i);
+        buf[11] = mix(buf[11] ^ data, next, i);
+        buf[12] = buf[12].wrapping_add(buf[8] ^ data);
+    }
+
+    pub(crate) fn finalize(&self) -> u64 {
+        let mut sum = [self.buf[0]; 4];
+        for (i, &data) in self.buf.iter().enumerate() {
+            let i = i as u32;
+
+            sum[0] = sum[0].wrapping_add(data.rotate_right(data & 0x1F));
+
+            if data < sum[0] {
+                sum[1] = sum[1].wrapping_add(data);
+            } else {
+                sum[1] = mix(sum[1], data, i);
+            }
+
+            if (data & 0x02) >> 1 == data & 0x01 {
+                sum[2] = sum[2].wrapping_add(data);
+            } else {
+                sum[2] = mix(sum[2], data, i);
+            }
+
+            if data & 0x01 == 0x01 {
+                sum[3] ^= data;
+            } else {
+                sum[3] = mix(sum[3], data, i);
+            }
+        }
+
+        let high = mix(sum[0], sum[1], 16);
+        let low = sum[3] ^ sum[2];
+
+        (((high as u64) << 32) | low as u64) & BOOTCODE_CHECKSUM_MASK
+    }
+}
+
+/// Calculates the boot code checksum of the given IPL3 words.
+pub(crate) fn bootcode_checksum_words(words: &[u32], seed: u32) -> u64 {
+    debug_assert_eq!(words.len(), BOOTCODE_WORDS);
+
+    let mut state = BootcodeState::new(seed, words[0]);
+    for i in 1..=BOOTCODE_WORDS {
+        state.update(words, i);
+    }
+
+    state.finalize()
+}
+
+pub(crate) fn read_bootcode_words(
+    ipl3_bytes: &[u8],
+) -> Result<[u32; BOOTCODE_WORDS], Ipl3ChecksumError> {
+    if ipl3_bytes.len() != BOOTCODE_SIZE {
+        return Err(Ipl3ChecksumError::BufferSizeIsWrong {
+            buffer_len: ipl3_bytes.len(),
+            expected_len: BOOTCODE_SIZE,
+        });
+    }
+
+    let mut words = [0; BOOTCODE_WORDS];
+    for (word, chunk) in words.iter_mut().zip(ipl3_bytes.chunks_exact(4)) {
+        *word = utils::u32_from_be_chunk(chunk);
+    }
+
+    Ok(words)
+}
+
+/// Calculates the boot code checksum the PIF's IPL2 computes over an IPL3 before running it.
+///
+/// The IPL2 compares this 48-bit value against the one sent by the CIC, and only boots the console if they match.
+///
+/// ## Arguments
+///
+/// * `ipl3_bytes` - The IPL3 binary in big endian format, located at offset 0x40 of a ROM. It must be exactly 0xFC0
+///   bytes long, like the input of `detect_cic_raw`.
+/// * `kind` - The CIC kind, which provides the seed.
+///
+/// ## Return
+///
+/// * The 48-bit checksum, or an error if `ipl3_bytes` doesn't have the expected size.
+pub fn calculate_bootcode_checksum(
+    ipl3_bytes: &[u8],
+    kind: CICKind,
+) -> Result<u64, Ipl3ChecksumError> {
+    calculate_bootcode_checksum_with_seed(ipl3_bytes, kind.get_seed())
+}
+
+/// Like `calculate_bootcode_checksum`, but using an arbitrary seed instead of the one of a CIC kind.
+///
+/// ## Arguments
+///
+/// * `ipl3_bytes` - The IPL3 binary in big endian format. It must be exactly 0xFC0 bytes long.
+/// * `seed` - The seed sent by the CIC. Only its lower 8 bits are used.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let ipl3 = vec![0; 0xFC0];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let checksum = ipl3checksum::calculate_bootcode_checksum_with_seed(&ipl3, kind.get_seed()).unwrap();
+/// assert_eq!(checksum, ipl3checksum::calculate_bootcode_checksum(&ipl3, kind).unwrap());
+/// assert!(checksum <= ipl3checksum::BOOTCODE_CHECKSUM_MASK);
+/// ```
+pub fn calculate_bootcode_checksum_with_seed(
+    ipl3_bytes: &[u8],
+    seed: u32,
+) -> Result<u64, Ipl3ChecksumError> {
+    let words = read_bootcode_words(ipl3_bytes)?;
+
+    Ok(bootcode_checksum_words(&words, seed))
+}
+
+/// Checks if an IPL3 would be accepted by the IPL2 when paired with the given CIC.
+///
+/// ## Arguments
+///
+/// * `ipl3_bytes` - The IPL3 binary in big endian format. It must be exactly 0xFC0 bytes long.
+/// * `kind` - The CIC kind to check against.
+///
+/// ## Return
+///
+/// * Whether the boot code checksum of the IPL3 matches the one of the CIC. Possible errors:
+///     - `ipl3_bytes` not having the expected size
+///     - The boot code checksum of the CIC kind not being known, see `CICKind::get_bootcode_checksum`
+pub fn validate_bootcode(ipl3_bytes: &[u8], kind: CICKind) -> Result<bool, Ipl3ChecksumError> {
+    let expected = kind
+        .get_bootcode_checksum()
+        .ok_or(Ipl3ChecksumError::UnknownBootcodeChecksum)?;
+
+    Ok(calculate_bootcode_checksum(ipl3_bytes, kind)? == expected)
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use std::borrow::Cow;
+
+    /**
+     * We use a `Cow` instead of a plain &[u8] the latter only allows Python's
+     * `bytes` objects, while Cow allows for both `bytes` and `bytearray`.
+     * This is important because an argument typed as `bytes` allows to pass a
+     * `bytearray` object too.
+     */
+
+    #[pyfunction]
+    pub(crate) fn calculateBootcodeChecksum(
+        ipl3_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+    ) -> Result<Option<u64>, super::Ipl3ChecksumError> {
+        match super::calculate_bootcode_checksum(&ipl3_bytes, kind) {
+            Ok(checksum) => Ok(Some(checksum)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferSizeIsWrong {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn validateBootcode(
+        ipl3_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+    ) -> Result<Option<bool>, super::Ipl3ChecksumError> {
+        match super::validate_bootcode(&ipl3_bytes, kind) {
+            Ok(valid) => Ok(Some(valid)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferSizeIsWrong {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                super::Ipl3ChecksumError::UnknownBootcodeChecksum => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    use crate::{utils, CICKind, Ipl3ChecksumError};
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_bootcode_checksum(
+        dst_checksum: *mut u64,
+        ipl3_bytes_len: usize,
+        ipl3_bytes: *const u8,
+        kind: CICKind,
+    ) -> Ipl3ChecksumError {
+        if dst_checksum.is_null() || ipl3_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(ipl3_bytes_len, ipl3_bytes) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let checksum = match super::calculate_bootcode_checksum(&bytes, kind) {
+            Err(e) => return e,
+            Ok(c) => c,
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_validate_bootcode(
+        dst_valid: *mut bool,
+        ipl3_bytes_len: usize,
+        ipl3_bytes: *const u8,
+        kind: CICKind,
+    ) -> Ipl3ChecksumError {
+        if dst_valid.is_null() || ipl3_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(ipl3_bytes_len, ipl3_bytes) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let valid = match super::validate_bootcode(&bytes, kind) {
+            Err(e) => return e,
+            Ok(v) => v,
+        };
+
+        unsafe { *dst_valid = valid };
+
+        Ipl3ChecksumError::Okay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cickinds::CICKind, Ipl3ChecksumError};
+
+    /// A direct translation of the IPL2 loop, with the shift pairs and the word reads of the original code, to check
+    /// `BootcodeState` against.
+    fn reference_checksum(ipl3_bytes: &[u8], seed: u32) -> u64 {
+        fn mix(a: u32, b: u32, index: u32) -> u32 {
+            let b = if b == 0 { index } else { b };
+            let product = a as u64 * b as u64;
+            let hi = (product >> 32) as u32;
+            let lo = product as u32;
+            if hi.wrapping_sub(lo) == 0 {
+                a
+            } else {
+                hi.wrapping_sub(lo)
+            }
+        }
+        // `sllv` and `srlv` only use the lower 5 bits of the amount
+        fn sllv(value: u32, amount: u32) -> u32 {
+            value << (amount & 0x1F)
+        }
+        fn srlv(value: u32, amount: u32) -> u32 {
+            value >> (amount & 0x1F)
+        }
+
+        let word = |i: u32| {
+            let i = i as usize * 4;
+            u32::from_be_bytes([
+                ipl3_bytes[i],
+                ipl3_bytes[i + 1],
+                ipl3_bytes[i + 2],
+                ipl3_bytes[i + 3],
+            ])
+        };
+
+        let init = 0x6C078965u32.wrapping_mul(seed & 0xFF).wrapping_add(1) ^ word(0);
+        let mut buf = [init; 16];
+        let mut prev = word(0);
+
+        for i in 1..=0x3F0u32 {
+            let data = word(i - 1);
+
+            buf[0] = buf[0].wrapping_add(mix(0x3EFu32.wrapping_sub(i), data, i));
+            buf[1] = mix(buf[1], data, i);
+            buf[2] ^= data;
+            buf[3] = buf[3].wrapping_add(mix(data.wrapping_add(5), 0x6C078965, i));
+            if prev < data {
+                buf[9] = mix(buf[9], data, i);
+            } else {
+                buf[9] = buf[9].wrapping_add(data);
+            }
+            buf[4] =
+                buf[4].wrapping_add(sllv(data, 0x20 - (prev & 0x1F)) | srlv(data, prev & 0x1F));
+            buf[7] = mix(
+                buf[7],
+                srlv(data, 0x20 - (prev & 0x1F)) | sllv(data, prev & 0x1F),
+                i,
+            );
+            if data < buf[6] {
+                buf[6] = data.wrapping_add(i) ^ buf[3].wrapping_add(buf[6]);
+            } else {
+                buf[6] ^= buf[4].wrapping_add(data);
+            }
+            buf[5] = buf[5].wrapping_add(srlv(data, 0x20 - (prev >> 27)) | sllv(data, prev >> 27));
+            buf[8] = mix(
+                buf[8],
+                sllv(data, 0x20 - (prev >> 27)) | srlv(data, prev >> 27),
+                i,
+            );
+
+            if i == 0x3F0 {
+                break;
+            }
+
+            let next = word(i);
+            buf[15] = mix(
+                mix(
+                    buf[15],
+                    srlv(data, 0x20 - (prev >> 27)) | sllv(data, prev >> 27),
+                    i,
+                ),
+                srlv(next, 0x20 - (data >> 27)) | sllv(next, data >> 27),
+                i,
+            );
+            buf[14] = mix(
+                mix(
+                    buf[14],
+                    sllv(data, 0x20 - (prev & 0x1F)) | srlv(data, prev & 0x1F),
+                    i,
+                ),
+                sllv(next, 0x20 - (data & 0x1F)) | srlv(next, data & 0x1F),
+                i,
+            );
+            buf[13] = buf[13]
+                .wrapping_add(sllv(data, 0x20 - (data & 0x1F)) | srlv(data, data & 0x1F))
+                .wrapping_add(sllv(next, 0x20 - (next & 0x1F)) | srlv(next, next & 0x1F));
+            buf[10] = mix(buf[10].wrapping_add(data), next, i);
+            buf[11] = mix(buf[11] ^ data, next, i);
+            buf[12] = buf[12].wrapping_add(buf[8] ^ data);
+
+            prev = data;
+        }
+
+        let mut sum = [buf[0]; 4];
+        for (i, &data) in buf.iter().enumerate() {
+            let i = i as u32;
+
+            sum[0] =
+                sum[0].wrapping_add(sllv(data, 0x20 - (data & 0x1F)) | srlv(data, data & 0x1F));
+            if data < sum[0] {
+                sum[1] = sum[1].wrapping_add(data);
+            } else {
+                sum[1] = mix(sum[1], data, i);
+            }
+            if (data & 0x02) >> 1 == data & 0x01 {
+                sum[2] = sum[2].wrapping_add(data);
+            } else {
+                sum[2] = mix(sum[2], data, i);
+            }
+            if data & 0x01 == 0x01 {
+                sum[3] ^= data;
+            } else {
+                sum[3] = mix(sum[3], data, i);
+            }
+        }
+
+        let high = mix(sum[0], sum[1], 16) as u64;
+        let low = (sum[3] ^ sum[2]) as u64;
+
+        ((high << 32) | low) & 0xFFFF_FFFF_FFFF
+    }
+
+    #[test]
+    fn test_bootcode_known_answers() -> Result<(), Ipl3ChecksumError> {
+        // The IPL3 of the dummy files is zeroed, so none of them would boot on a real console
+        let zeroes = [0; 0xFC0];
+        for (kind, expected) in [
+            (CICKind::CIC_6101, 0x2982AD8C201D),
+            (CICKind::CIC_6102_7101, 0x2982AD8C201D),
+            (CICKind::CIC_7102, 0x2982AD8C201D),
+            (CICKind::CIC_X103, 0xB30F78D0CAD9),
+            (CICKind::CIC_X105, 0x31085BBE1381),
+            (CICKind::CIC_X106, 0xBB2AF8DB9C3A),
+            (CICKind::CIC_5101, 0x3B492E705C47),
+        ] {
+            let checksum = super::calculate_bootcode_checksum(&zeroes, kind)?;
+            println!("{}: 0x{:012X}", kind, checksum);
+
+            assert_eq!(checksum, expected);
+            assert_eq!(reference_checksum(&zeroes, kind.get_seed()), expected);
+
+            match kind.get_bootcode_checksum() {
+                Some(_) => assert_eq!(super::validate_bootcode(&zeroes, kind), Ok(false)),
+                None => assert_eq!(
+                    super::validate_bootcode(&zeroes, kind),
+                    Err(Ipl3ChecksumError::UnknownBootcodeChecksum)
+                ),
+            }
+        }
+
+        // The values sent by the retail CICs
+        assert_eq!(
+            CICKind::CIC_6102_7101.get_bootcode_checksum(),
+            Some(0xA536C0F1D859)
+        );
+        assert_eq!(
+            CICKind::CIC_X106.get_bootcode_checksum(),
+            Some(0x2BBAD4E6EB74)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bootcode_checksum() -> Result<(), Ipl3ChecksumError> {
+        let zeroes = [0; 0xFC0];
+        let mut ipl3 = [0; 0xFC0];
+        for (i, byte) in ipl3.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(0x1D) ^ 0x5A;
+        }
+
+        for kind in CICKind::iter() {
+            let checksum = super::calculate_bootcode_checksum(&ipl3, kind)?;
+            println!("{}: {:012X}", kind, checksum);
+
+            assert!(checksum <= super::BOOTCODE_CHECKSUM_MASK);
+            assert_eq!(
+                super::calculate_bootcode_checksum_with_seed(&ipl3, kind.get_seed() | 0x100)?,
+                checksum
+            );
+            assert_ne!(super::calculate_bootcode_checksum(&zeroes, kind)?, checksum);
+
+            assert_eq!(checksum, reference_checksum(&ipl3, kind.get_seed()));
+        }
+
+        // Every word affects the checksum
+        let kind = CICKind::CIC_6102_7101;
+        let checksum = super::calculate_bootcode_checksum(&ipl3, kind)?;
+        for offset in (0..0xFC0).step_by(0x1F8) {
+            let mut modified = ipl3;
+            modified[offset] ^= 0x01;
+            assert_ne!(
+                super::calculate_bootcode_checksum(&modified, kind)?,
+                checksum
+            );
+        }
+
+        // The seeds differ, so the checksums must too
+        assert_ne!(
+            super::calculate_bootcode_checksum(&ipl3, CICKind::CIC_6102_7101)?,
+            super::calculate_bootcode_checksum(&ipl3, CICKind::CIC_X105)?
+        );
+
+        assert_eq!(
+            super::calculate_bootcode_checksum(&ipl3[..0xFBC], kind),
+            Err(Ipl3ChecksumError::BufferSizeIsWrong {
+                buffer_len: 0xFBC,
+                expected_len: 0xFC0
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,570 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+//! Boot code collision search.
+//!
+//! Custom IPL3s need to produce the same boot code checksum as a retail one to be accepted by the IPL2, which is
+//! done by brute forcing a few words of the IPL3 that aren't used by its code.
+//!
+//! Every candidate of the search is a number, which is split into the words written at the given offsets, the last
+//! offset taking the lowest 32 bits. Each word only affects the iterations of the IPL2 around its own, so the state
+//! before the first offset is computed once and shared by every candidate. Placing the offsets near the end of the
+//! IPL3 makes the search a lot faster.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bootcode::{self, BootcodeState, BOOTCODE_SIZE, BOOTCODE_WORDS};
+use crate::{utils, CICKind, Ipl3ChecksumError};
+
+/// Maximum amount of offsets a search can vary, so every candidate fits in a `u64`.
+pub const BOOTCODE_SEARCH_MAX_OFFSETS: usize = 2;
+
+/// How often the progress callback of the search is called.
+pub const BOOTCODE_SEARCH_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Amount of candidates taken by a worker thread at once. Cancellation is checked between chunks.
+const CHUNK_SIZE: u64 = 1 << 12;
+
+/// How often the calling thread checks if the search was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The range of candidates of a boot code collision search, and how far into it the search got.
+///
+/// Updated by `search_bootcode_collision` when it returns, so it can be stored and passed again to resume the
+/// search.
+pub struct BootcodeSearchState {
+    /// First candidate of the range.
+    pub first_candidate: u64,
+    /// Last candidate of the range, inclusive.
+    pub last_candidate: u64,
+    /// Every candidate before this one has already been checked.
+    pub next_candidate: u64,
+    /// Whether every candidate of the range has been checked.
+    pub finished: bool,
+}
+
+impl BootcodeSearchState {
+    /// A search over every possible value of the words at `offset_count` offsets.
+    ///
+    /// `offset_count` should be between 1 and `BOOTCODE_SEARCH_MAX_OFFSETS`.
+    pub fn new(offset_count: usize) -> Self {
+        let last_candidate = match offset_count {
+            0 => 0,
+            1 => u32::MAX as u64,
+            _ => u64::MAX,
+        };
+
+        Self::with_range(0, last_candidate)
+    }
+
+    /// A search over the candidates between `first_candidate` and `last_candidate`, both inclusive.
+    ///
+    /// Useful to split a search between several machines.
+    pub fn with_range(first_candidate: u64, last_candidate: u64) -> Self {
+        Self {
+            first_candidate,
+            last_candidate,
+            next_candidate: first_candidate,
+            finished: first_candidate > last_candidate,
+        }
+    }
+
+    /// Amount of candidates already checked.
+    pub fn searched(&self) -> u64 {
+        if self.finished {
+            self.last_candidate
+                .wrapping_sub(self.first_candidate)
+                .wrapping_add(1)
+        } else {
+            self.next_candidate - self.first_candidate
+        }
+    }
+
+    /// Fraction of the range already checked, between 0 and 1.
+    pub fn progress(&self) -> f64 {
+        if self.finished {
+            return 1.0;
+        }
+
+        let total = (self.last_candidate - self.first_candidate) as f64 + 1.0;
+        self.searched() as f64 / total
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How a boot code collision search ended.
+pub enum BootcodeSearchOutcome {
+    /// A collision was found and its words were written to the IPL3.
+    Found {
+        /// The candidate which produced the collision.
+        candidate: u64,
+    },
+    /// The search was cancelled before checking every candidate.
+    Cancelled,
+    /// Every candidate was checked without finding a collision.
+    Exhausted,
+}
+
+/// The words written at the offsets for a candidate.
+fn candidate_words(candidate: u64, offset_count: usize) -> [u32; BOOTCODE_SEARCH_MAX_OFFSETS] {
+    match offset_count {
+        1 => [candidate as u32, 0],
+        _ => [(candidate >> 32) as u32, candidate as u32],
+    }
+}
+
+/// Everything the worker threads share.
+struct Search {
+    words: [u32; BOOTCODE_WORDS],
+    /// Word indices of the offsets.
+    indices: Vec<usize>,
+    seed: u32,
+    target: u64,
+    /// State of the IPL2 before the first iteration affected by the offsets, and the number of that iteration.
+    prefix: Option<(BootcodeState, usize)>,
+    last_candidate: u64,
+}
+
+impl Search {
+    fn check(&self, words: &mut [u32; BOOTCODE_WORDS], candidate: u64) -> bool {
+        let values = candidate_words(candidate, self.indices.len());
+        for (index, value) in self.indices.iter().zip(values) {
+            words[*index] = value;
+        }
+
+        let (mut state, first_iteration) = match self.prefix {
+            Some(prefix) => prefix,
+            None => (BootcodeState::new(self.seed, words[0]), 1),
+        };
+        for i in first_iteration..=BOOTCODE_WORDS {
+            state.update(words, i);
+        }
+
+        state.finalize() == self.target
+    }
+
+    /// Checks the candidates between `start` and `end`, both inclusive, returning the first collision.
+    fn check_chunk(&self, words: &mut [u32; BOOTCODE_WORDS], start: u64, end: u64) -> Option<u64> {
+        (start..=end).find(|candidate| self.check(words, *candidate))
+    }
+}
+
+/// The chunks checked so far, to know up to which candidate the search can be resumed.
+struct Completed {
+    next_candidate: u64,
+    finished: bool,
+    /// Chunks finished out of order, as start and inclusive end.
+    pending: BTreeMap<u64, u64>,
+}
+
+impl Completed {
+    fn complete(&mut self, start: u64, end: u64, last_candidate: u64) {
+        self.pending.insert(start, end);
+
+        while let Some(end) = self.pending.remove(&self.next_candidate) {
+            if end == last_candidate {
+                self.finished = true;
+                break;
+            }
+            self.next_candidate = end + 1;
+        }
+    }
+}
+
+/// Searches for values of the words at `offsets` which make the IPL3 produce the boot code checksum of the given CIC
+/// kind, writing them to the IPL3 if found.
+///
+/// The search runs on as many threads as the system provides, starting from `state.next_candidate` and returning on
+/// the first collision found, once every candidate was checked, or once `cancel` is set. The rest of the IPL3 is left
+/// untouched. `state` is updated so it can be passed again to resume the search, after the found collision if there
+/// was one.
+///
+/// A full search over two words can't realistically be completed, the checksum has 48 bits so about 2^48 candidates
+/// need to be checked on average.
+///
+/// ## Arguments
+///
+/// * `ipl3_bytes` - The IPL3 binary in big endian format. It must be exactly 0xFC0 bytes long.
+/// * `kind` - The CIC kind whose boot code checksum is searched for.
+/// * `offsets` - Offsets of the words allowed to vary, relative to the start of the IPL3. Up to
+///   `BOOTCODE_SEARCH_MAX_OFFSETS` offsets can be given, and they must be aligned to a word.
+/// * `state` - The range of candidates to check and how far into it the search got.
+/// * `cancel` - Checked periodically by the search, which stops once it is set.
+/// * `progress` - Called every `BOOTCODE_SEARCH_PROGRESS_INTERVAL` from the calling thread with the current state.
+///
+/// ## Return
+///
+/// * How the search ended. Possible errors:
+///     - `ipl3_bytes` not having the expected size
+///     - The boot code checksum of the CIC kind not being known
+///     - Invalid `offsets`, or a range of candidates which doesn't fit in them
+pub fn search_bootcode_collision(
+    ipl3_bytes: &mut [u8],
+    kind: CICKind,
+    offsets: &[usize],
+    state: &mut BootcodeSearchState,
+    cancel: &AtomicBool,
+    progress: impl FnMut(&BootcodeSearchState),
+) -> Result<BootcodeSearchOutcome, Ipl3ChecksumError> {
+    let target = kind
+        .get_bootcode_checksum()
+        .ok_or(Ipl3ChecksumError::UnknownBootcodeChecksum)?;
+
+    search_bootcode_collision_with_target(
+        ipl3_bytes,
+        kind.get_seed(),
+        target,
+        offsets,
+        state,
+        cancel,
+        progress,
+    )
+}
+
+/// Like `search_bootcode_collision`, but searching for an arbitrary seed and boot code checksum instead of the ones
+/// of a CIC kind.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// use std::sync::atomic::AtomicBool;
+///
+/// let mut ipl3 = vec![0; 0xFC0];
+/// ipl3[0xFBC..].copy_from_slice(&[0x00, 0x00, 0x12, 0x34]);
+/// let seed = ipl3checksum::CICKind::CIC_6102_7101.get_seed();
+/// let target = ipl3checksum::calculate_bootcode_checksum_with_seed(&ipl3, seed).unwrap();
+/// ipl3[0xFBC..].copy_from_slice(&[0; 4]);
+///
+/// let mut state = ipl3checksum::BootcodeSearchState::with_range(0x1000, 0x1FFF);
+/// let outcome = ipl3checksum::search_bootcode_collision_with_target(
+///     &mut ipl3, seed, target, &[0xFBC], &mut state, &AtomicBool::new(false), |_| {},
+/// ).unwrap();
+/// assert_eq!(outcome, ipl3checksum::BootcodeSearchOutcome::Found { candidate: 0x1234 });
+/// assert_eq!(ipl3[0xFBC..], [0x00, 0x00, 0x12, 0x34]);
+/// ```
+pub fn search_bootcode_collision_with_target(
+    ipl3_bytes: &mut [u8],
+    seed: u32,
+    target: u64,
+    offsets: &[usize],
+    state: &mut BootcodeSearchState,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(&BootcodeSearchState),
+) -> Result<BootcodeSearchOutcome, Ipl3ChecksumError> {
+    let words = bootcode::read_bootcode_words(ipl3_bytes)?;
+
+    if offsets.is_empty() || offsets.len() > BOOTCODE_SEARCH_MAX_OFFSETS {
+        return Err(Ipl3ChecksumError::InvalidBootcodeSearch);
+    }
+    for (i, offset) in offsets.iter().enumerate() {
+        if offset % 4 != 0 || *offset >= BOOTCODE_SIZE || offsets[..i].contains(offset) {
+            return Err(Ipl3ChecksumError::InvalidBootcodeSearch);
+        }
+    }
+    if state.first_candidate > state.next_candidate
+        || state.last_candidate > BootcodeSearchState::new(offsets.len()).last_candidate
+    {
+        return Err(Ipl3ChecksumError::InvalidBootcodeSearch);
+    }
+
+    if state.finished || state.next_candidate > state.last_candidate {
+        state.finished = true;
+        return Ok(BootcodeSearchOutcome::Exhausted);
+    }
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(BootcodeSearchOutcome::Cancelled);
+    }
+
+    let indices: Vec<usize> = offsets.iter().map(|offset| offset / 4).collect();
+
+    // The first word affects the initial state, so nothing can be reused if it varies
+    let first_index = *indices.iter().min().unwrap();
+    let prefix = if first_index == 0 {
+        None
+    } else {
+        let mut prefix_state = BootcodeState::new(seed, words[0]);
+        for i in 1..first_index {
+            prefix_state.update(&words, i);
+        }
+        Some((prefix_state, first_index))
+    };
+
+    let search = Arc::new(Search {
+        words,
+        indices,
+        seed,
+        target,
+        prefix,
+        last_candidate: state.last_candidate,
+    });
+    let completed = Arc::new(Mutex::new(Completed {
+        next_candidate: state.next_candidate,
+        finished: false,
+        pending: BTreeMap::new(),
+    }));
+    let found = Arc::new(Mutex::new(None));
+    let stop = Arc::new(AtomicBool::new(false));
+    let next_chunk = Arc::new(AtomicU64::new(0));
+    let base = state.next_candidate;
+
+    let chunk_count = (state.last_candidate - base) / CHUNK_SIZE + 1;
+    let thread_count = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(chunk_count.try_into().unwrap_or(usize::MAX));
+
+    let (sender, receiver) = mpsc::channel();
+
+    let handles: Vec<_> = (0..thread_count)
+        .map(|_| {
+            let search = Arc::clone(&search);
+            let completed = Arc::clone(&completed);
+            let found = Arc::clone(&found);
+            let stop = Arc::clone(&stop);
+            let next_chunk = Arc::clone(&next_chunk);
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                let mut words = search.words;
+
+                while !stop.load(Ordering::Relaxed) {
+                    let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    if chunk >= chunk_count {
+                        break;
+                    }
+
+                    let start = base + chunk * CHUNK_SIZE;
+                    let end = start
+                        .saturating_add(CHUNK_SIZE - 1)
+                        .min(search.last_candidate);
+
+                    match search.check_chunk(&mut words, start, end) {
+                        Some(candidate) => {
+                            let mut found = found.lock().unwrap();
+                            if found.map_or(true, |found| candidate < found) {
+                                *found = Some(candidate);
+                            }
+                            stop.store(true, Ordering::Relaxed);
+                        }
+                        None => {
+                            completed
+                                .lock()
+                                .unwrap()
+                                .complete(start, end, search.last_candidate);
+                        }
+                    }
+                }
+
+                // The receiver only goes away once every thread is joined
+                sender.send(()).ok();
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let snapshot = |completed: &Completed| BootcodeSearchState {
+        next_candidate: completed.next_candidate,
+        finished: completed.finished,
+        ..*state
+    };
+
+    let mut running = thread_count;
+    let mut last_progress = Instant::now();
+    while running > 0 {
+        match receiver.recv_timeout(CANCEL_POLL_INTERVAL) {
+            Ok(()) => running -= 1,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if cancel.load(Ordering::Relaxed) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+                if last_progress.elapsed() >= BOOTCODE_SEARCH_PROGRESS_INTERVAL {
+                    progress(&snapshot(&completed.lock().unwrap()));
+                    last_progress = Instant::now();
+                }
+            }
+            // A thread panicked, which is reported when joining it
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    for handle in handles {
+        if let Err(e) = handle.join() {
+            std::panic::resume_unwind(e);
+        }
+    }
+
+    let found = *found.lock().unwrap();
+    let completed = completed.lock().unwrap();
+
+    let outcome = match found {
+        Some(candidate) => {
+            // Every chunk before the found one was checked, so it is the first collision of the range
+            let values = candidate_words(candidate, search.indices.len());
+            for (offset, value) in offsets.iter().zip(values) {
+                utils::write_u32(ipl3_bytes, *offset, value)?;
+            }
+
+            if candidate == state.last_candidate {
+                state.finished = true;
+            } else {
+                state.next_candidate = candidate + 1;
+            }
+            BootcodeSearchOutcome::Found { candidate }
+        }
+        None => {
+            *state = snapshot(&completed);
+            if state.finished {
+                BootcodeSearchOutcome::Exhausted
+            } else {
+                BootcodeSearchOutcome::Cancelled
+            }
+        }
+    };
+    progress(state);
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BootcodeSearchOutcome, BootcodeSearchState};
+    use crate::{CICKind, Ipl3ChecksumError};
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn test_bootcode_search() -> Result<(), Ipl3ChecksumError> {
+        let seed = CICKind::CIC_6102_7101.get_seed();
+        let not_cancelled = AtomicBool::new(false);
+
+        let mut ipl3 = [0; 0xFC0];
+        for (i, byte) in ipl3.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(0x1D) ^ 0x5A;
+        }
+
+        // Two words near the end, like custom IPL3s do
+        let offsets = [0xF80, 0xFB8];
+        ipl3[0xF80..0xF84].copy_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        ipl3[0xFB8..0xFBC].copy_from_slice(&[0x00, 0x00, 0x80, 0x00]);
+        let expected = ipl3;
+        let target = crate::calculate_bootcode_checksum_with_seed(&expected, seed)?;
+        let candidate = 0x1_0000_8000;
+
+        ipl3[0xF80..0xF84].copy_from_slice(&[0xFF; 4]);
+        ipl3[0xFB8..0xFBC].copy_from_slice(&[0xFF; 4]);
+
+        let mut state = BootcodeSearchState::with_range(candidate - 0xC000, candidate + 0x100);
+        let mut progress_calls = 0;
+        let outcome = super::search_bootcode_collision_with_target(
+            &mut ipl3,
+            seed,
+            target,
+            &offsets,
+            &mut state,
+            &not_cancelled,
+            |_| progress_calls += 1,
+        )?;
+        assert_eq!(outcome, BootcodeSearchOutcome::Found { candidate });
+        assert_eq!(ipl3, expected);
+        assert_eq!(state.next_candidate, candidate + 1);
+        assert!(!state.finished);
+        assert!(progress_calls >= 1);
+
+        // Resuming goes through the rest of the range
+        let outcome = super::search_bootcode_collision_with_target(
+            &mut ipl3,
+            seed,
+            target,
+            &offsets,
+            &mut state,
+            &not_cancelled,
+            |_| {},
+        )?;
+        assert_eq!(outcome, BootcodeSearchOutcome::Exhausted);
+        assert!(state.finished);
+        assert_eq!(state.searched(), 0xC101);
+        assert_eq!(state.progress(), 1.0);
+        assert_eq!(ipl3, expected);
+
+        // Cancelling keeps the state
+        let mut state = BootcodeSearchState::with_range(0, candidate);
+        let outcome = super::search_bootcode_collision_with_target(
+            &mut ipl3,
+            seed,
+            target,
+            &offsets,
+            &mut state,
+            &AtomicBool::new(true),
+            |_| {},
+        )?;
+        assert_eq!(outcome, BootcodeSearchOutcome::Cancelled);
+        assert!(state.next_candidate < candidate);
+        assert!(!state.finished);
+
+        // The first word changes the initial state, so nothing is reused when it varies
+        let mut state = BootcodeSearchState::with_range(0x100, 0x2FF);
+        let first_word = u32::from_be_bytes([ipl3[0], ipl3[1], ipl3[2], ipl3[3]]);
+        ipl3[..4].copy_from_slice(&0x200u32.to_be_bytes());
+        let target = crate::calculate_bootcode_checksum_with_seed(&ipl3, seed)?;
+        ipl3[..4].copy_from_slice(&first_word.to_be_bytes());
+        let outcome = super::search_bootcode_collision_with_target(
+            &mut ipl3,
+            seed,
+            target,
+            &[0],
+            &mut state,
+            &not_cancelled,
+            |_| {},
+        )?;
+        assert_eq!(outcome, BootcodeSearchOutcome::Found { candidate: 0x200 });
+        assert_eq!(ipl3[..4], 0x200u32.to_be_bytes());
+
+        // Invalid searches
+        for offsets in [&[][..], &[0xF82], &[0xFC0], &[0xF80, 0xF80], &[0, 4, 8]] {
+            assert_eq!(
+                super::search_bootcode_collision_with_target(
+                    &mut ipl3,
+                    seed,
+                    target,
+                    offsets,
+                    &mut BootcodeSearchState::with_range(0, 0),
+                    &not_cancelled,
+                    |_| {},
+                ),
+                Err(Ipl3ChecksumError::InvalidBootcodeSearch)
+            );
+        }
+        assert_eq!(
+            super::search_bootcode_collision_with_target(
+                &mut ipl3,
+                seed,
+                target,
+                &[0xF80],
+                &mut BootcodeSearchState::new(2),
+                &not_cancelled,
+                |_| {},
+            ),
+            Err(Ipl3ChecksumError::InvalidBootcodeSearch)
+        );
+        assert_eq!(
+            super::search_bootcode_collision(
+                &mut ipl3,
+                CICKind::CIC_5101,
+                &[0xF80],
+                &mut BootcodeSearchState::new(1),
+                &not_cancelled,
+                |_| {},
+            ),
+            Err(Ipl3ChecksumError::UnknownBootcodeChecksum)
+        );
+
+        Ok(())
+    }
+}
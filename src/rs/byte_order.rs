@@ -0,0 +1,231 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::Ipl3ChecksumError;
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/byte_order.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The byte order of a N64 ROM dump.
+pub enum RomByteOrder {
+    /// The native byte order of the N64. Usually `.z64` files.
+    BigEndian,
+    /// Every pair of bytes is swapped. Usually `.v64` files.
+    ByteSwapped,
+    /// Every word is stored in little endian. Usually `.n64` files.
+    LittleEndian,
+}
+
+impl RomByteOrder {
+    /// Detects the byte order of a N64 ROM by looking at the PI configuration word at the start of its header.
+    ///
+    /// The most significant byte of said word is 0x80 on every ROM, so its position tells the byte order.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in any byte order. It must have a minimum size of 0x4 bytes.
+    ///
+    /// ## Return
+    ///
+    /// * The detected byte order. Possible errors:
+    ///     - `rom_bytes` not being big enough
+    ///     - The PI configuration word not matching any known byte order
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ipl3checksum;
+    /// let bytes = [0x37, 0x80, 0x40, 0x12];
+    /// let order = ipl3checksum::RomByteOrder::detect(&bytes).unwrap();
+    /// assert_eq!(order, ipl3checksum::RomByteOrder::ByteSwapped);
+    /// ```
+    pub fn detect(rom_bytes: &[u8]) -> Result<Self, Ipl3ChecksumError> {
+        if rom_bytes.len() < 4 {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: 4,
+            });
+        }
+
+        match rom_bytes[..4] {
+            [0x80, _, _, _] => Ok(Self::BigEndian),
+            [_, 0x80, _, _] => Ok(Self::ByteSwapped),
+            [_, _, _, 0x80] => Ok(Self::LittleEndian),
+            _ => Err(Ipl3ChecksumError::UnknownByteOrder),
+        }
+    }
+}
+
+/// Converts the bytes of a N64 ROM from the given byte order to big endian, in place.
+///
+/// The conversion is its own inverse, so calling this function on a big endian ROM converts it back to `order`.
+///
+/// An incomplete pair of bytes or word at the end of `rom_bytes` is left untouched.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM to convert.
+/// * `order` - The current byte order of `rom_bytes`.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = [0x40, 0x12, 0x37, 0x80];
+/// ipl3checksum::normalize_byte_order(&mut bytes, ipl3checksum::RomByteOrder::LittleEndian);
+/// assert_eq!(bytes, [0x80, 0x37, 0x12, 0x40]);
+/// ```
+pub fn normalize_byte_order(rom_bytes: &mut [u8], order: RomByteOrder) {
+    match order {
+        RomByteOrder::BigEndian => {}
+        RomByteOrder::ByteSwapped => {
+            for pair in rom_bytes.chunks_exact_mut(2) {
+                pair.swap(0, 1);
+            }
+        }
+        RomByteOrder::LittleEndian => {
+            for word in rom_bytes.chunks_exact_mut(4) {
+                word.reverse();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RomByteOrder;
+    use crate::Ipl3ChecksumError;
+
+    #[test]
+    fn test_byte_order_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, mut bin_bytes| {
+            // The dummy files don't have a PI configuration word
+            bin_bytes[..4].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+
+            let expected = crate::calculate_checksum(&bin_bytes, kind)?;
+
+            for order in [
+                RomByteOrder::BigEndian,
+                RomByteOrder::ByteSwapped,
+                RomByteOrder::LittleEndian,
+            ] {
+                let mut converted = bin_bytes.clone();
+                super::normalize_byte_order(&mut converted, order);
+
+                assert_eq!(RomByteOrder::detect(&converted), Ok(order));
+                assert_eq!(
+                    crate::calculate_checksum_any_order(&converted, kind),
+                    Ok((expected, order))
+                );
+                // The dummy files don't contain a real IPL3
+                assert_eq!(
+                    crate::detect_cic_any_order(&converted),
+                    Err(Ipl3ChecksumError::UnableToDetectCIC)
+                );
+
+                super::normalize_byte_order(&mut converted, order);
+                assert_eq!(converted, bin_bytes);
+            }
+            Ok(())
+        })?;
+
+        assert_eq!(
+            RomByteOrder::detect(&[0x80, 0x37, 0x12]),
+            Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: 3,
+                expected_len: 4
+            })
+        );
+        assert_eq!(
+            RomByteOrder::detect(&[0; 0x1000]),
+            Err(Ipl3ChecksumError::UnknownByteOrder)
+        );
+
+        let mut bytes = [0x80, 0x37, 0x12, 0x40, 0x0F];
+        super::normalize_byte_order(&mut bytes, RomByteOrder::LittleEndian);
+        assert_eq!(bytes, [0x40, 0x12, 0x37, 0x80, 0x0F]);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::PyByteArray;
+    use std::borrow::Cow;
+
+    #[pyfunction]
+    pub(crate) fn detectByteOrder(
+        rom_bytes: Cow<[u8]>,
+    ) -> Result<Option<super::RomByteOrder>, super::Ipl3ChecksumError> {
+        match super::RomByteOrder::detect(&rom_bytes) {
+            Ok(order) => Ok(Some(order)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                super::Ipl3ChecksumError::UnknownByteOrder => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn normalizeByteOrder(rom_bytes: &PyByteArray, order: super::RomByteOrder) {
+        // Safety: No Python code is run while the bytes are being borrowed
+        super::normalize_byte_order(unsafe { rom_bytes.as_bytes_mut() }, order)
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_detect_byte_order(
+        dst_order: *mut super::RomByteOrder,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+    ) -> super::Ipl3ChecksumError {
+        if dst_order.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match crate::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let order = match super::RomByteOrder::detect(&bytes) {
+            Ok(order) => order,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_order = order };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_normalize_byte_order(
+        rom_bytes_len: usize,
+        rom_bytes: *mut u8,
+        order: super::RomByteOrder,
+    ) -> super::Ipl3ChecksumError {
+        if rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = unsafe { std::slice::from_raw_parts_mut(rom_bytes, rom_bytes_len) };
+        super::normalize_byte_order(bytes, order);
+
+        super::Ipl3ChecksumError::Okay
+    }
+}
@@ -1,16 +1,140 @@
 /* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
 /* SPDX-License-Identifier: MIT */
 
+use crate::checksum_value::Checksum;
 use crate::cickinds::CICKind;
-use crate::{detect, error::Ipl3ChecksumError, utils};
+use crate::params::{CicParams, FinalizationVariant};
+use crate::{
+    byte_order, detect, error::Ipl3ChecksumError, utils, Ipl3Hasher, RomByteOrder, ShortRomPolicy,
+};
 
-fn get_entrypoint_addr(rom_bytes: &[u8], kind: CICKind) -> Result<u32, Ipl3ChecksumError> {
-    let entrypoint_addr: u32 = utils::read_u32(rom_bytes, 8)?;
+pub(crate) const HEADER_IPL3_SIZE: usize = 0x1000;
 
-    Ok(kind.get_entrypoint(entrypoint_addr))
+/// Get how many bytes of the ROM (passed IPL3) to check
+pub(crate) fn get_bytes_to_check(kind: CICKind, entrypoint: u32) -> usize {
+    // IPL3 5101 checks almost 4 times the normal amount depending on the entrypoint
+    if (kind == CICKind::CIC_5101) && (entrypoint == 0x80000400) {
+        0x3FE000 // ~ 3.992 MiB
+    } else {
+        0x100000
+    }
+}
+
+/// Offset of the word used by the CIC_X105 variant when checking the word `i` (counting from the end of the IPL3).
+///
+/// ipl3 6105 copies 0x330 bytes from the ROM's offset 0x000554 (or offset 0x000514 into IPL3) to vram 0xA0000004
+#[inline]
+pub(crate) fn get_x105_table_offset(i: usize) -> usize {
+    (((i & 0x3F) | 0x80) + 0x154) * 4
 }
 
-const HEADER_IPL3_SIZE: usize = 0x1000;
+/// The 0x40 words of the CIC_X105 table actually used by the checksum, indexed by `i & 0x3F`.
+///
+/// `header` must be at least `HEADER_IPL3_SIZE` bytes long.
+pub(crate) fn get_x105_table(header: &[u8]) -> [u32; 0x40] {
+    let mut table = [0; 0x40];
+
+    for (i, entry) in table.iter_mut().enumerate() {
+        let offset = get_x105_table_offset(i);
+        *entry = utils::u32_from_be_chunk(&header[offset..offset + 4]);
+    }
+
+    table
+}
+
+/// The registers used by the IPL3 while calculating the checksum.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ChecksumState {
+    pub(crate) a3: u32,
+    pub(crate) t2: u32,
+    pub(crate) t3: u32,
+    pub(crate) s0: u32,
+    pub(crate) a2: u32,
+    pub(crate) t4: u32,
+}
+
+impl ChecksumState {
+    pub(crate) fn new(kind: CICKind) -> Self {
+        Self::with_seed(kind.get_seed(), kind.get_magic())
+    }
+
+    pub(crate) fn with_seed(seed: u32, magic: u32) -> Self {
+        let v0 = seed.wrapping_mul(magic).wrapping_add(1);
+
+        Self {
+            a3: v0,
+            t2: v0,
+            t3: v0,
+            s0: v0,
+            a2: v0,
+            t4: v0,
+        }
+    }
+
+    /// Process a single word of the checked range.
+    ///
+    /// `x105_word` must be the word from the CIC_X105 table if that variant is being used, `None` otherwise.
+    #[inline]
+    pub(crate) fn update(&mut self, word: u32, x105_word: Option<u32>) {
+        let a1 = self.a3.wrapping_add(word);
+        if a1 < self.a3 {
+            self.t2 = self.t2.wrapping_add(0x1);
+        }
+        self.a3 = a1;
+
+        let a0 = word.rotate_left(word & 0x1F);
+
+        self.t3 ^= word;
+
+        self.s0 = self.s0.wrapping_add(a0);
+        if self.a2 < word {
+            self.a2 ^= self.a3 ^ word;
+        } else {
+            self.a2 ^= a0;
+        }
+
+        match x105_word {
+            Some(t7) => self.t4 = self.t4.wrapping_add(word ^ t7),
+            None => self.t4 = self.t4.wrapping_add(word ^ self.s0),
+        }
+    }
+
+    pub(crate) fn finalize(&self, kind: CICKind) -> Checksum {
+        self.finalize_with(kind.get_finalization_variant())
+    }
+
+    pub(crate) fn finalize_with(&self, variant: FinalizationVariant) -> Checksum {
+        match variant {
+            FinalizationVariant::Add => {
+                let t6 = self.a3 ^ self.t2;
+                let a3 = t6.wrapping_add(self.t3);
+
+                let t8 = self.s0 ^ self.a2;
+                let s0 = t8.wrapping_add(self.t4);
+
+                Checksum(a3, s0)
+            }
+            FinalizationVariant::Mul => {
+                let t6 = self.a3.wrapping_mul(self.t2);
+                let a3 = t6.wrapping_add(self.t3);
+
+                let t8 = self.s0.wrapping_mul(self.a2);
+                let s0 = t8.wrapping_add(self.t4);
+
+                Checksum(a3, s0)
+            }
+            FinalizationVariant::Xor => {
+                let t6 = self.a3 ^ self.t2;
+                let a3 = t6 ^ self.t3;
+
+                let t8 = self.s0 ^ self.a2;
+                let s0 = t8 ^ self.t4;
+
+                Checksum(a3, s0)
+            }
+        }
+    }
+}
 
 /// Calculates the checksum required by an official CIC of a N64 ROM.
 ///
@@ -21,8 +145,7 @@ const HEADER_IPL3_SIZE: usize = 0x1000;
 ///
 /// ## Return
 ///
-/// * If no error happens then the calculated checksum is returned, stored as a tuple
-///   containing two 32-bits words.
+/// * If no error happens then the calculated checksum is returned.
 ///
 /// ## Examples
 ///
@@ -31,104 +154,158 @@ const HEADER_IPL3_SIZE: usize = 0x1000;
 /// let bytes = vec![0; 0x101000];
 /// let kind = ipl3checksum::CICKind::CIC_6102_7101;
 /// let checksum = ipl3checksum::calculate_checksum(&bytes, kind).unwrap();
-/// println!("{:08X} {:08X}", checksum.0, checksum.1);
+/// println!("{}", checksum);
+/// ```
+pub fn calculate_checksum(rom_bytes: &[u8], kind: CICKind) -> Result<Checksum, Ipl3ChecksumError> {
+    calculate_checksum_with_params(rom_bytes, CicParams::for_rom(kind, rom_bytes)?)
+}
+
+/// Calculates the checksum of a N64 ROM using custom parameters instead of the ones of an official CIC.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of
+///   `0x1000 + params.bytes_to_check` bytes.
+/// * `params` - The parameters used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x101000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let params = ipl3checksum::CicParams {
+///     seed: 0x12,
+///     ..kind.get_params(0x80000400)
+/// };
+/// let checksum = ipl3checksum::calculate_checksum_with_params(&bytes, params).unwrap();
+/// assert_ne!(checksum, ipl3checksum::calculate_checksum(&bytes, kind).unwrap());
 /// ```
-pub fn calculate_checksum(
+pub fn calculate_checksum_with_params(
     rom_bytes: &[u8],
-    kind: CICKind,
-) -> Result<(u32, u32), Ipl3ChecksumError> {
-    let seed = kind.get_seed();
-    let magic = kind.get_magic();
-
-    let v0 = seed.wrapping_mul(magic).wrapping_add(1);
-
-    let mut a3 = v0;
-    let mut t2 = v0;
-    let mut t3 = v0;
-    let mut s0 = v0;
-    let mut a2 = v0;
-    let mut t4 = v0;
-
-    // Get how many bytes of the ROM (passed IPL3) to check
-    let bytes_to_check: u32 =
-        // IPL3 5101 checks almost 4 times the normal amount depending on the entrypoint
-        if (kind == CICKind::CIC_5101) && (get_entrypoint_addr(rom_bytes, kind)? == 0x80000400) {
-            0x3FE000 // ~ 3.992 MiB
-        } else {
-            0x100000
-        };
+    params: CicParams,
+) -> Result<Checksum, Ipl3ChecksumError> {
+    let mut state = ChecksumState::with_seed(params.seed, params.magic);
+    let bytes_to_check = params.bytes_to_check;
 
     // Error if the ROM is not big enough
-    if rom_bytes.len() < bytes_to_check as usize + HEADER_IPL3_SIZE {
+    if rom_bytes.len() < bytes_to_check + HEADER_IPL3_SIZE {
         return Err(Ipl3ChecksumError::BufferNotBigEnough {
             buffer_len: rom_bytes.len(),
-            expected_len: bytes_to_check as usize + HEADER_IPL3_SIZE,
+            expected_len: bytes_to_check + HEADER_IPL3_SIZE,
         });
     }
 
-    let rom_words = utils::read_u32_vec(
-        rom_bytes,
-        0,
-        (bytes_to_check as usize + HEADER_IPL3_SIZE) / 4,
-    )?;
+    let checked_words = rom_bytes[HEADER_IPL3_SIZE..HEADER_IPL3_SIZE + bytes_to_check]
+        .chunks_exact(4)
+        .map(utils::u32_from_be_chunk);
 
-    let words_to_check = bytes_to_check.wrapping_div(4) as usize;
-    for i in 0..words_to_check {
-        let word = rom_words[i + (HEADER_IPL3_SIZE / 4)];
+    // Keep the table check outside of the loop
+    if params.uses_x105_table {
+        let table = get_x105_table(rom_bytes);
 
-        let a1 = a3.wrapping_add(word);
-        if a1 < a3 {
-            t2 = t2.wrapping_add(0x1);
+        for (i, word) in checked_words.enumerate() {
+            state.update(word, Some(table[i & 0x3F]));
         }
-        a3 = a1;
-
-        let a0 = word.rotate_left(word & 0x1F);
-
-        t3 ^= word;
-
-        s0 = s0.wrapping_add(a0);
-        if a2 < word {
-            a2 ^= a3 ^ word;
-        } else {
-            a2 ^= a0;
+    } else {
+        for word in checked_words {
+            state.update(word, None);
         }
+    }
 
-        if kind == CICKind::CIC_X105 {
-            // ipl3 6105 copies 0x330 bytes from the ROM's offset 0x000554 (or offset 0x000514 into IPL3) to vram 0xA0000004
-            let temp = (i & 0x3F) | 0x80;
-            let t7 = rom_words[temp + 0x154];
+    Ok(state.finalize_with(params.finalization))
+}
 
-            t4 = t4.wrapping_add(word ^ t7);
-        } else {
-            t4 = t4.wrapping_add(word ^ s0);
-        }
+/// Calculates the checksum required by an official CIC of a N64 ROM which may be smaller than the range checked by
+/// the CIC.
+///
+/// The bytes past the end of the ROM are filled as `policy` says, including the header and the IPL3 if the ROM is
+/// smaller than them.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+/// * `policy` - How to fill the bytes past the end of the ROM.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - `rom_bytes` not being big enough when using `ShortRomPolicy::Error`
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x80000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let policy = ipl3checksum::ShortRomPolicy::ZeroFill;
+/// let checksum = ipl3checksum::calculate_checksum_with_policy(&bytes, kind, policy).unwrap();
+/// assert_eq!(checksum, ipl3checksum::calculate_checksum(&vec![0; 0x101000], kind).unwrap());
+/// ```
+pub fn calculate_checksum_with_policy(
+    rom_bytes: &[u8],
+    kind: CICKind,
+    policy: ShortRomPolicy,
+) -> Result<Checksum, Ipl3ChecksumError> {
+    if policy == ShortRomPolicy::Error || rom_bytes.len() >= HEADER_IPL3_SIZE {
+        return calculate_checksum_with_params_and_policy(
+            rom_bytes,
+            CicParams::for_rom(kind, rom_bytes)?,
+            policy,
+        );
     }
 
-    match kind {
-        CICKind::CIC_X103 | CICKind::CIC_5101 => {
-            let t6 = a3 ^ t2;
-            a3 = t6.wrapping_add(t3);
+    let header = policy.read_header(rom_bytes);
+    calculate_checksum_with_params_and_policy(rom_bytes, CicParams::for_rom(kind, &header)?, policy)
+}
 
-            let t8 = s0 ^ a2;
-            s0 = t8.wrapping_add(t4);
-        }
-        CICKind::CIC_X106 => {
-            let t6 = a3.wrapping_mul(t2);
-            a3 = t6.wrapping_add(t3);
+/// Calculates the checksum of a N64 ROM which may be smaller than the range checked by the CIC, using custom
+/// parameters instead of the ones of an official CIC.
+///
+/// The bytes past the end of the ROM are filled as `policy` says, including the header and the IPL3 if the ROM is
+/// smaller than them.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `params` - The parameters used to calculate the checksum.
+/// * `policy` - How to fill the bytes past the end of the ROM.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - `rom_bytes` not being big enough when using `ShortRomPolicy::Error`
+pub fn calculate_checksum_with_params_and_policy(
+    rom_bytes: &[u8],
+    params: CicParams,
+    policy: ShortRomPolicy,
+) -> Result<Checksum, Ipl3ChecksumError> {
+    if policy == ShortRomPolicy::Error
+        || rom_bytes.len() >= params.bytes_to_check + HEADER_IPL3_SIZE
+    {
+        return calculate_checksum_with_params(rom_bytes, params);
+    }
 
-            let t8 = s0.wrapping_mul(a2);
-            s0 = t8.wrapping_add(t4);
-        }
-        _ => {
-            let t6 = a3 ^ t2;
-            a3 = t6 ^ t3;
+    let mut state = ChecksumState::with_seed(params.seed, params.magic);
+    let table = if params.uses_x105_table {
+        Some(get_x105_table(&policy.read_header(rom_bytes)))
+    } else {
+        None
+    };
 
-            let t8 = s0 ^ a2;
-            s0 = t8 ^ t4;
-        }
+    for i in 0..params.bytes_to_check / 4 {
+        let word = policy.read_word(rom_bytes, HEADER_IPL3_SIZE + i * 4);
+        state.update(word, table.map(|table| table[i & 0x3F]));
     }
 
-    Ok((a3, s0))
+    Ok(state.finalize_with(params.finalization))
 }
 
 /// Calculates the checksum required by an official CIC of a N64 ROM.
@@ -142,8 +319,7 @@ pub fn calculate_checksum(
 ///
 /// ## Return
 ///
-/// * If no error happens then the calculated checksum is returned, stored as a tuple
-///   containing two 32-bits words. Otherwise, `None` is returned.
+/// * If no error happens then the calculated checksum is returned.
 ///   Possible errors:
 ///     - `rom_bytes` not being big enough
 ///     - Not able to detect the CIC kind
@@ -157,29 +333,328 @@ pub fn calculate_checksum(
 /// /* This will return `None` because there's no ipl3 binary on an array of zeroes */
 /// assert!(checksum.is_err());
 /// ```
-pub fn calculate_checksum_autodetect(rom_bytes: &[u8]) -> Result<(u32, u32), Ipl3ChecksumError> {
+pub fn calculate_checksum_autodetect(rom_bytes: &[u8]) -> Result<Checksum, Ipl3ChecksumError> {
     let kind = detect::detect_cic(rom_bytes)?;
 
     calculate_checksum(rom_bytes, kind)
 }
 
+/// Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then a tuple is returned, containing the checksum that was in the header before
+///   updating it and the newly calculated checksum, in that order.
+///   If an error happens then `rom_bytes` is left untouched.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0; 0x101000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let (old_checksum, new_checksum) = ipl3checksum::fix_checksum(&mut bytes, kind).unwrap();
+/// assert_eq!(old_checksum, ipl3checksum::Checksum(0, 0));
+/// assert_eq!(new_checksum, ipl3checksum::calculate_checksum(&bytes, kind).unwrap());
+/// ```
+pub fn fix_checksum(
+    rom_bytes: &mut [u8],
+    kind: CICKind,
+) -> Result<(Checksum, Checksum), Ipl3ChecksumError> {
+    let new_checksum = calculate_checksum(rom_bytes, kind)?;
+
+    let old_checksum = Checksum::from_header(rom_bytes)?;
+    new_checksum.write_to_header(rom_bytes)?;
+
+    Ok((old_checksum, new_checksum))
+}
+
+/// Calculates the checksum required by an official CIC of a N64 ROM and writes it to the ROM header.
+///
+/// This function will try to autodetect the CIC kind automatically.
+/// If it fails to detect it then an error will be returned.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+///
+/// ## Return
+///
+/// * If no error happens then a tuple is returned, containing the checksum that was in the header before
+///   updating it and the newly calculated checksum, in that order.
+///   If an error happens then `rom_bytes` is left untouched.
+///   Possible errors:
+///     - `rom_bytes` not being big enough
+///     - Not able to detect the CIC kind
+pub fn fix_checksum_autodetect(
+    rom_bytes: &mut [u8],
+) -> Result<(Checksum, Checksum), Ipl3ChecksumError> {
+    let kind = detect::detect_cic(rom_bytes)?;
+
+    fix_checksum(rom_bytes, kind)
+}
+
+/// Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+///
+/// The byte order is detected from the ROM header. The ROM is converted to big endian in small chunks as the checksum
+/// is calculated, so no copy of the whole ROM is made.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum
+///   size of 0x101000 bytes.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum and the byte order of the ROM are returned.
+///   Possible errors:
+///     - `rom_bytes` not being big enough
+///     - Not able to detect the byte order
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0; 0x101000];
+/// bytes[..4].copy_from_slice(&[0x37, 0x80, 0x40, 0x12]);
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let (checksum, order) = ipl3checksum::calculate_checksum_any_order(&bytes, kind).unwrap();
+/// assert_eq!(order, ipl3checksum::RomByteOrder::ByteSwapped);
+/// ```
+pub fn calculate_checksum_any_order(
+    rom_bytes: &[u8],
+    kind: CICKind,
+) -> Result<(Checksum, RomByteOrder), Ipl3ChecksumError> {
+    let order = RomByteOrder::detect(rom_bytes)?;
+
+    if order == RomByteOrder::BigEndian {
+        return Ok((calculate_checksum(rom_bytes, kind)?, order));
+    }
+
+    let mut hasher = Ipl3Hasher::new(kind);
+    let mut buffer = [0; 0x1000];
+
+    for chunk in rom_bytes.chunks(buffer.len()) {
+        let converted = &mut buffer[..chunk.len()];
+        converted.copy_from_slice(chunk);
+        byte_order::normalize_byte_order(converted, order);
+
+        hasher.update(converted);
+        if hasher.is_complete() {
+            break;
+        }
+    }
+
+    Ok((hasher.finalize()?, order))
+}
+
+/// Calculates the checksum required by an official CIC of a N64 ROM of any byte order.
+///
+/// This function will try to autodetect both the byte order and the CIC kind automatically.
+/// If it fails to detect them then an error will be returned.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a minimum
+///   size of 0x101000 bytes.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum and the byte order of the ROM are returned.
+///   Possible errors:
+///     - `rom_bytes` not being big enough
+///     - Not able to detect the byte order
+///     - Not able to detect the CIC kind
+pub fn calculate_checksum_autodetect_any_order(
+    rom_bytes: &[u8],
+) -> Result<(Checksum, RomByteOrder), Ipl3ChecksumError> {
+    let (kind, _) = detect::detect_cic_any_order(rom_bytes)?;
+
+    calculate_checksum_any_order(rom_bytes, kind)
+}
+
+/// Amount of lanes used by `calculate_all_checksums`, rounded up from the amount of CIC kinds so the compiler can
+/// vectorize the loop.
+const LANES: usize = 8;
+
+/// The registers of `ChecksumState` for every CIC kind, so all of them can be calculated on a single pass over the
+/// ROM.
+struct ChecksumLanes {
+    a3: [u32; LANES],
+    t2: [u32; LANES],
+    t3: [u32; LANES],
+    s0: [u32; LANES],
+    a2: [u32; LANES],
+    t4: [u32; LANES],
+    /// All bits set on the lanes that use the CIC_X105 table instead of `s0`.
+    x105_mask: [u32; LANES],
+}
+
+impl ChecksumLanes {
+    fn new(kinds: &[CICKind]) -> Self {
+        let mut lanes = Self {
+            a3: [0; LANES],
+            t2: [0; LANES],
+            t3: [0; LANES],
+            s0: [0; LANES],
+            a2: [0; LANES],
+            t4: [0; LANES],
+            x105_mask: [0; LANES],
+        };
+
+        for (k, kind) in kinds.iter().enumerate() {
+            let state = ChecksumState::new(*kind);
+
+            lanes.a3[k] = state.a3;
+            lanes.t2[k] = state.t2;
+            lanes.t3[k] = state.t3;
+            lanes.s0[k] = state.s0;
+            lanes.a2[k] = state.a2;
+            lanes.t4[k] = state.t4;
+            if *kind == CICKind::CIC_X105 {
+                lanes.x105_mask[k] = u32::MAX;
+            }
+        }
+
+        lanes
+    }
+
+    /// Same as `ChecksumState::update`, but without branches so every lane can be processed at the same time.
+    #[inline]
+    fn update(&mut self, word: u32, x105_word: u32) {
+        let a0 = word.rotate_left(word & 0x1F);
+
+        for k in 0..LANES {
+            let a1 = self.a3[k].wrapping_add(word);
+            self.t2[k] = self.t2[k].wrapping_add((a1 < self.a3[k]) as u32);
+            self.a3[k] = a1;
+
+            self.t3[k] ^= word;
+
+            self.s0[k] = self.s0[k].wrapping_add(a0);
+            self.a2[k] ^= if self.a2[k] < word { a1 ^ word } else { a0 };
+
+            let t7 = (x105_word & self.x105_mask[k]) | (self.s0[k] & !self.x105_mask[k]);
+            self.t4[k] = self.t4[k].wrapping_add(word ^ t7);
+        }
+    }
+
+    fn get_state(&self, k: usize) -> ChecksumState {
+        ChecksumState {
+            a3: self.a3[k],
+            t2: self.t2[k],
+            t3: self.t3[k],
+            s0: self.s0[k],
+            a2: self.a2[k],
+            t4: self.t4[k],
+        }
+    }
+}
+
+/// Calculates the checksum of a N64 ROM for every CIC kind at the same time.
+///
+/// This is equivalent to calling `calculate_checksum` once per CIC kind, but the ROM is only traversed once.
+/// Useful when the CIC kind can't be detected.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+///
+/// ## Return
+///
+/// * An array containing every CIC kind paired with the result of calculating the checksum with said kind.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x101000];
+/// for (kind, checksum) in ipl3checksum::calculate_all_checksums(&bytes) {
+///     assert_eq!(checksum, ipl3checksum::calculate_checksum(&bytes, kind));
+/// }
+/// ```
+pub fn calculate_all_checksums(
+    rom_bytes: &[u8],
+) -> [(CICKind, Result<Checksum, Ipl3ChecksumError>); 7] {
+    let kinds = CICKind::ALL;
+
+    // Every kind checks at least this amount of bytes
+    let common_bytes_to_check = get_bytes_to_check(CICKind::CIC_6102_7101, 0);
+
+    if rom_bytes.len() < HEADER_IPL3_SIZE + common_bytes_to_check {
+        // Nothing to calculate, let `calculate_checksum` produce the appropriate error for each kind
+        return kinds.map(|kind| (kind, calculate_checksum(rom_bytes, kind)));
+    }
+
+    let mut lanes = ChecksumLanes::new(&kinds);
+    let table = get_x105_table(rom_bytes);
+
+    for (i, chunk) in rom_bytes[HEADER_IPL3_SIZE..HEADER_IPL3_SIZE + common_bytes_to_check]
+        .chunks_exact(4)
+        .enumerate()
+    {
+        lanes.update(utils::u32_from_be_chunk(chunk), table[i & 0x3F]);
+    }
+
+    let mut k = 0;
+    kinds.map(|kind| {
+        let mut state = lanes.get_state(k);
+        k += 1;
+
+        // CIC_5101 may check way more bytes than the rest
+        if kind == CICKind::CIC_5101 {
+            let bytes_to_check = match CicParams::for_rom(kind, rom_bytes) {
+                Ok(params) => params.bytes_to_check,
+                Err(e) => return (kind, Err(e)),
+            };
+
+            if rom_bytes.len() < HEADER_IPL3_SIZE + bytes_to_check {
+                return (
+                    kind,
+                    Err(Ipl3ChecksumError::BufferNotBigEnough {
+                        buffer_len: rom_bytes.len(),
+                        expected_len: HEADER_IPL3_SIZE + bytes_to_check,
+                    }),
+                );
+            }
+
+            for chunk in rom_bytes
+                [HEADER_IPL3_SIZE + common_bytes_to_check..HEADER_IPL3_SIZE + bytes_to_check]
+                .chunks_exact(4)
+            {
+                state.update(utils::u32_from_be_chunk(chunk), None);
+            }
+        }
+
+        (kind, Ok(state.finalize(kind)))
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{cickinds::CICKind, utils};
+    use crate::{cickinds::CICKind, Checksum};
     use std::fs;
+    use std::path::Path;
 
     #[test]
     fn test_dummy_files() -> Result<(), super::Ipl3ChecksumError> {
-        for path_result in fs::read_dir("tests/dummytests").unwrap() {
-            let ipl3_folder = path_result.unwrap();
-            let folder_name = ipl3_folder.file_name();
+        // There's a folder of dummy files for every kind, named after it
+        for kind in CICKind::iter() {
+            let ipl3_folder = Path::new("tests/dummytests").join(kind.to_string());
 
-            println!("{:?}", folder_name);
+            println!("{:?}", ipl3_folder);
 
-            let kind = CICKind::from_name(folder_name.to_str().unwrap()).unwrap();
-            println!("CIC Kind: {:?}", kind);
+            let folder_name = ipl3_folder.file_name().unwrap().to_str().unwrap();
+            assert_eq!(folder_name.parse::<CICKind>()?, kind);
+            println!("CIC Kind: {}", kind);
 
-            for bin_path_result in fs::read_dir(ipl3_folder.path()).unwrap() {
+            for bin_path_result in fs::read_dir(&ipl3_folder).unwrap() {
                 let bin_path = bin_path_result.unwrap();
 
                 println!("{:?}", bin_path);
@@ -190,23 +665,17 @@ mod tests {
 
                 println!("    Calculating checksum...");
                 let checksum = super::calculate_checksum(&bin_bytes, kind).unwrap();
-                println!("Used CIC Kind: {:?}", kind);
+                println!("Used CIC Kind: {}", kind);
 
-                println!(
-                    "    Calculated checksum is: 0x{:08X} 0x{:08X}",
-                    checksum.0, checksum.1
-                );
+                println!("    Calculated checksum is: {}", checksum);
 
                 println!("    Checking checksum...");
-                let bin_checksum = utils::read_u32_vec(&bin_bytes, 0x10, 2)?;
+                let bin_checksum = Checksum::from_header(&bin_bytes)?;
 
-                println!(
-                    "    Expected checksum is: 0x{:08X} 0x{:08X}",
-                    bin_checksum[0], bin_checksum[1]
-                );
+                println!("    Expected checksum is: {}", bin_checksum);
 
-                assert_eq!(checksum.0, bin_checksum[0]);
-                assert_eq!(checksum.1, bin_checksum[1]);
+                assert_eq!(checksum, bin_checksum);
+                assert!(checksum.matches_header(&bin_bytes));
 
                 println!("    {:?} OK", bin_path);
 
@@ -215,6 +684,82 @@ mod tests {
 
             println!();
         }
+
+        for name in CICKind::VALID_NAMES {
+            let kind: CICKind = name.parse()?;
+            assert_eq!(kind.to_string().parse(), Ok(kind));
+        }
+        for value in [
+            6101, 6102, 7101, 7102, 6103, 7103, 6105, 7105, 6106, 7106, 5101,
+        ] {
+            let kind = CICKind::try_from(value)?;
+            assert_eq!(value.to_string().parse(), Ok(kind));
+        }
+        assert!(CICKind::try_from(6104).is_err());
+        assert!("CIC_6104".parse::<CICKind>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_checksum() -> Result<(), super::Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, original_bytes| {
+            let expected = super::calculate_checksum(&original_bytes, kind)?;
+
+            let mut bin_bytes = original_bytes.clone();
+            bin_bytes[0x10..0x18].fill(0xAA);
+
+            // The dummy files don't contain a real IPL3
+            assert_eq!(
+                super::fix_checksum_autodetect(&mut bin_bytes),
+                Err(super::Ipl3ChecksumError::UnableToDetectCIC)
+            );
+
+            let (old_checksum, new_checksum) = super::fix_checksum(&mut bin_bytes, kind)?;
+            assert_eq!(old_checksum, Checksum(0xAAAAAAAA, 0xAAAAAAAA));
+            assert_eq!(new_checksum, expected);
+            assert_eq!(bin_bytes, original_bytes);
+
+            assert_eq!(
+                super::fix_checksum(&mut bin_bytes, kind)?,
+                (expected, expected)
+            );
+            Ok(())
+        })?;
+
+        // Errors should leave the ROM untouched
+        let mut bytes = vec![0xAA; 0x100000];
+        assert!(super::fix_checksum(&mut bytes, CICKind::CIC_6102_7101).is_err());
+        assert!(bytes.iter().all(|x| *x == 0xAA));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_checksums() -> Result<(), super::Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|_, _, bin_bytes| {
+            for (kind, checksum) in super::calculate_all_checksums(&bin_bytes) {
+                assert_eq!(checksum, super::calculate_checksum(&bin_bytes, kind));
+            }
+
+            // Too short for CIC_5101's extended range, but not for the rest
+            for (kind, checksum) in super::calculate_all_checksums(&bin_bytes[..0x101000]) {
+                assert_eq!(
+                    checksum,
+                    super::calculate_checksum(&bin_bytes[..0x101000], kind)
+                );
+            }
+            Ok(())
+        })?;
+
+        for len in [0, 0x1000, 0x100FFF] {
+            let bytes = vec![0; len];
+
+            for (kind, checksum) in super::calculate_all_checksums(&bytes) {
+                assert_eq!(checksum, super::calculate_checksum(&bytes, kind));
+            }
+        }
+
         Ok(())
     }
 }
@@ -223,6 +768,7 @@ mod tests {
 #[allow(non_snake_case)]
 pub(crate) mod python_bindings {
     use pyo3::prelude::*;
+    use pyo3::types::PyByteArray;
     use std::borrow::Cow;
 
     /**
@@ -236,7 +782,7 @@ pub(crate) mod python_bindings {
     pub(crate) fn calculateChecksum(
         rom_bytes: Cow<[u8]>,
         kind: super::CICKind,
-    ) -> Result<Option<(u32, u32)>, super::Ipl3ChecksumError> {
+    ) -> Result<Option<super::Checksum>, super::Ipl3ChecksumError> {
         match super::calculate_checksum(&rom_bytes, kind) {
             Ok(checksum) => Ok(Some(checksum)),
             Err(e) => match e {
@@ -249,10 +795,113 @@ pub(crate) mod python_bindings {
         }
     }
 
+    #[pyfunction]
+    pub(crate) fn calculateChecksumWithPolicy(
+        rom_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+        policy: super::ShortRomPolicy,
+    ) -> Result<Option<super::Checksum>, super::Ipl3ChecksumError> {
+        match super::calculate_checksum_with_policy(&rom_bytes, kind, policy) {
+            Ok(checksum) => Ok(Some(checksum)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn calculateChecksumWithParams(
+        rom_bytes: Cow<[u8]>,
+        params: super::CicParams,
+    ) -> Result<Option<super::Checksum>, super::Ipl3ChecksumError> {
+        match super::calculate_checksum_with_params(&rom_bytes, params) {
+            Ok(checksum) => Ok(Some(checksum)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn fixChecksum(
+        rom_bytes: &PyByteArray,
+        kind: super::CICKind,
+    ) -> Result<Option<(super::Checksum, super::Checksum)>, super::Ipl3ChecksumError> {
+        // Safety: No Python code is run while the bytes are being borrowed
+        match super::fix_checksum(unsafe { rom_bytes.as_bytes_mut() }, kind) {
+            Ok(checksums) => Ok(Some(checksums)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn fixChecksumAutodetect(
+        rom_bytes: &PyByteArray,
+    ) -> Result<Option<(super::Checksum, super::Checksum)>, super::Ipl3ChecksumError> {
+        // Safety: No Python code is run while the bytes are being borrowed
+        match super::fix_checksum_autodetect(unsafe { rom_bytes.as_bytes_mut() }) {
+            Ok(checksums) => Ok(Some(checksums)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn calculateChecksumAnyOrder(
+        rom_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+    ) -> Result<Option<(super::Checksum, super::RomByteOrder)>, super::Ipl3ChecksumError> {
+        match super::calculate_checksum_any_order(&rom_bytes, kind) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn calculateChecksumAutodetectAnyOrder(
+        rom_bytes: Cow<[u8]>,
+    ) -> Result<Option<(super::Checksum, super::RomByteOrder)>, super::Ipl3ChecksumError> {
+        match super::calculate_checksum_autodetect_any_order(&rom_bytes) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
     #[pyfunction]
     pub(crate) fn calculateChecksumAutodetect(
         rom_bytes: Cow<[u8]>,
-    ) -> Result<Option<(u32, u32)>, super::Ipl3ChecksumError> {
+    ) -> Result<Option<super::Checksum>, super::Ipl3ChecksumError> {
         match super::calculate_checksum_autodetect(&rom_bytes) {
             Ok(checksum) => Ok(Some(checksum)),
             Err(e) => match e {
@@ -324,4 +973,184 @@ mod c_bindings {
 
         super::Ipl3ChecksumError::Okay
     }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_with_params(
+        dst_checksum: *mut super::Checksum,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        params: super::CicParams,
+    ) -> super::Ipl3ChecksumError {
+        if dst_checksum.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let checksum = match super::calculate_checksum_with_params(&bytes, params) {
+            Ok(chk) => chk,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_with_policy(
+        dst_checksum: *mut super::Checksum,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        kind: super::CICKind,
+        policy: super::ShortRomPolicy,
+    ) -> super::Ipl3ChecksumError {
+        if dst_checksum.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let checksum = match super::calculate_checksum_with_policy(&bytes, kind, policy) {
+            Ok(chk) => chk,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_any_order(
+        dst_checksum: *mut super::Checksum,
+        dst_order: *mut super::RomByteOrder,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        kind: super::CICKind,
+    ) -> super::Ipl3ChecksumError {
+        if dst_checksum.is_null() || dst_order.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let (checksum, order) = match super::calculate_checksum_any_order(&bytes, kind) {
+            Ok(result) => result,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_checksum = checksum };
+        unsafe { *dst_order = order };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_autodetect_any_order(
+        dst_checksum: *mut super::Checksum,
+        dst_order: *mut super::RomByteOrder,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+    ) -> super::Ipl3ChecksumError {
+        if dst_checksum.is_null() || dst_order.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let (checksum, order) = match super::calculate_checksum_autodetect_any_order(&bytes) {
+            Ok(result) => result,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_checksum = checksum };
+        unsafe { *dst_order = order };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_fix_checksum(
+        dst_old_checksum: *mut super::Checksum,
+        dst_new_checksum: *mut super::Checksum,
+        rom_bytes_len: usize,
+        rom_bytes: *mut u8,
+        kind: super::CICKind,
+    ) -> super::Ipl3ChecksumError {
+        if dst_old_checksum.is_null() || dst_new_checksum.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let mut bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let (old_checksum, new_checksum) = match super::fix_checksum(&mut bytes, kind) {
+            Ok(chk) => chk,
+            Err(e) => return e,
+        };
+
+        write_fixed_checksum(rom_bytes, &bytes);
+
+        unsafe { *dst_old_checksum = old_checksum };
+        unsafe { *dst_new_checksum = new_checksum };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_fix_checksum_autodetect(
+        dst_old_checksum: *mut super::Checksum,
+        dst_new_checksum: *mut super::Checksum,
+        rom_bytes_len: usize,
+        rom_bytes: *mut u8,
+    ) -> super::Ipl3ChecksumError {
+        if dst_old_checksum.is_null() || dst_new_checksum.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let mut bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let (old_checksum, new_checksum) = match super::fix_checksum_autodetect(&mut bytes) {
+            Ok(chk) => chk,
+            Err(e) => return e,
+        };
+
+        write_fixed_checksum(rom_bytes, &bytes);
+
+        unsafe { *dst_old_checksum = old_checksum };
+        unsafe { *dst_new_checksum = new_checksum };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    /// Copy the updated checksum from the header of `fixed_bytes` back to the caller's buffer.
+    fn write_fixed_checksum(rom_bytes: *mut u8, fixed_bytes: &[u8]) {
+        for (i, byte) in fixed_bytes.iter().enumerate().take(0x18).skip(0x10) {
+            unsafe { *rom_bytes.add(i) = *byte };
+        }
+    }
 }
@@ -0,0 +1,501 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::{error::Ipl3ChecksumError, utils};
+
+/// Offset of the checksum inside the ROM header.
+pub(crate) const HEADER_CHECKSUM_OFFSET: usize = 0x10;
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/checksum.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// The checksum of a N64 ROM, made of two 32-bits words.
+///
+/// It is stored on the ROM header at offset 0x10 in big endian.
+///
+/// Displayed in the usual `XXXXXXXX XXXXXXXX` form.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum::Checksum;
+/// let checksum: Checksum = "F8CA4DDC 303A4DDC".parse().unwrap();
+/// assert_eq!(checksum, Checksum(0xF8CA4DDC, 0x303A4DDC));
+/// assert_eq!(checksum.to_string(), "F8CA4DDC 303A4DDC");
+/// assert_eq!(u64::from(checksum), 0xF8CA4DDC303A4DDC);
+/// ```
+pub struct Checksum(pub u32, pub u32);
+
+impl Checksum {
+    pub fn new(word0: u32, word1: u32) -> Self {
+        Self(word0, word1)
+    }
+
+    /// Reads the checksum stored on the header of a N64 ROM.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x18 bytes.
+    pub fn from_header(rom_bytes: &[u8]) -> Result<Self, Ipl3ChecksumError> {
+        if rom_bytes.len() < HEADER_CHECKSUM_OFFSET + 8 {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: HEADER_CHECKSUM_OFFSET + 8,
+            });
+        }
+
+        Ok(Self(
+            utils::read_u32(rom_bytes, HEADER_CHECKSUM_OFFSET)?,
+            utils::read_u32(rom_bytes, HEADER_CHECKSUM_OFFSET + 4)?,
+        ))
+    }
+
+    /// Writes this checksum to the header of a N64 ROM.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x18 bytes.
+    pub fn write_to_header(&self, rom_bytes: &mut [u8]) -> Result<(), Ipl3ChecksumError> {
+        if rom_bytes.len() < HEADER_CHECKSUM_OFFSET + 8 {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: HEADER_CHECKSUM_OFFSET + 8,
+            });
+        }
+
+        utils::write_u32(rom_bytes, HEADER_CHECKSUM_OFFSET, self.0)?;
+        utils::write_u32(rom_bytes, HEADER_CHECKSUM_OFFSET + 4, self.1)
+    }
+
+    /// Checks if this checksum is the one stored on the header of a N64 ROM.
+    ///
+    /// Returns `false` if the ROM is too small to contain a header.
+    pub fn matches_header(&self, rom_bytes: &[u8]) -> bool {
+        Self::from_header(rom_bytes) == Ok(*self)
+    }
+
+    /// The checksum as it is stored on the ROM header.
+    pub fn to_be_bytes(&self) -> [u8; 8] {
+        u64::from(*self).to_be_bytes()
+    }
+
+    pub fn from_be_bytes(bytes: [u8; 8]) -> Self {
+        Self::from(u64::from_be_bytes(bytes))
+    }
+}
+
+impl core::fmt::Display for Checksum {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:08X} {:08X}", self.0, self.1)
+    }
+}
+
+impl core::str::FromStr for Checksum {
+    type Err = Ipl3ChecksumError;
+
+    /// Parses either two whitespace separated words (`XXXXXXXX XXXXXXXX`) or a single 64-bits value
+    /// (`XXXXXXXXXXXXXXXX`), written in hexadecimal. A `0x` prefix is allowed on each value.
+    ///
+    /// The single value form must have all of its 16 digits, to avoid mistaking a lone word for a checksum.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_hex(value: &str, min_digits: usize, max_digits: usize) -> Option<u64> {
+            let value = value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+                .unwrap_or(value);
+
+            if value.len() < min_digits
+                || value.len() > max_digits
+                || !value.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return None;
+            }
+            u64::from_str_radix(value, 16).ok()
+        }
+
+        let mut parts = s.split_whitespace();
+        let parsed = match (parts.next(), parts.next(), parts.next()) {
+            (Some(single), None, None) => parse_hex(single, 16, 16).map(Self::from),
+            (Some(word0), Some(word1), None) => {
+                match (parse_hex(word0, 1, 8), parse_hex(word1, 1, 8)) {
+                    (Some(word0), Some(word1)) => Some(Self(word0 as u32, word1 as u32)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        parsed.ok_or(Ipl3ChecksumError::InvalidChecksumString)
+    }
+}
+
+impl From<u64> for Checksum {
+    /// The first word is taken from the upper 32 bits.
+    fn from(value: u64) -> Self {
+        Self((value >> 32) as u32, value as u32)
+    }
+}
+
+impl From<Checksum> for u64 {
+    fn from(value: Checksum) -> Self {
+        ((value.0 as u64) << 32) | (value.1 as u64)
+    }
+}
+
+impl From<(u32, u32)> for Checksum {
+    fn from(value: (u32, u32)) -> Self {
+        Self(value.0, value.1)
+    }
+}
+
+impl From<Checksum> for (u32, u32) {
+    fn from(value: Checksum) -> Self {
+        (value.0, value.1)
+    }
+}
+
+impl PartialEq<(u32, u32)> for Checksum {
+    fn eq(&self, other: &(u32, u32)) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+/// Human readable formats store the checksum in the `XXXXXXXX XXXXXXXX` form, while binary formats store it as a
+/// `u64`. Both forms are accepted when deserializing from a human readable format.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use core::fmt;
+
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    use super::Checksum;
+
+    impl Serialize for Checksum {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                serializer.serialize_u64(u64::from(*self))
+            }
+        }
+    }
+
+    struct ChecksumVisitor;
+
+    impl<'de> Visitor<'de> for ChecksumVisitor {
+        type Value = Checksum;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a checksum string or a 64-bits integer")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse().map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(Checksum::from(v))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Checksum {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(ChecksumVisitor)
+            } else {
+                deserializer.deserialize_u64(ChecksumVisitor)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checksum;
+    use crate::Ipl3ChecksumError;
+
+    #[test]
+    fn test_checksum_conversions() {
+        let checksum = Checksum(0x12345678, 0x9ABCDEF0);
+
+        assert_eq!(checksum.to_string(), "12345678 9ABCDEF0");
+        assert_eq!(u64::from(checksum), 0x123456789ABCDEF0);
+        assert_eq!(Checksum::from(0x123456789ABCDEF0), checksum);
+        assert_eq!(
+            checksum.to_be_bytes(),
+            [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0]
+        );
+        assert_eq!(Checksum::from_be_bytes(checksum.to_be_bytes()), checksum);
+
+        for s in [
+            "12345678 9ABCDEF0",
+            "0x12345678  0x9abcdef0",
+            "  123456789ABCDEF0\n",
+            "0x123456789abcdef0",
+        ] {
+            assert_eq!(s.parse(), Ok(checksum), "{:?}", s);
+        }
+        for s in [
+            "",
+            "12345678",
+            "123456789 9ABCDEF0",
+            "1234567G 9ABCDEF0",
+            "12345678 9ABCDEF0 0",
+            "0x",
+            "+1234567 9ABCDEF0",
+        ] {
+            assert_eq!(
+                s.parse::<Checksum>(),
+                Err(Ipl3ChecksumError::InvalidChecksumString),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_checksum_header() {
+        let mut header = [0; 0x40];
+        header[0x10..0x18].copy_from_slice(&[0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0]);
+
+        let checksum = Checksum::from_header(&header).unwrap();
+        assert_eq!(checksum, Checksum(0x12345678, 0x9ABCDEF0));
+        assert!(checksum.matches_header(&header));
+        assert!(!Checksum(0, 0).matches_header(&header));
+        assert!(!checksum.matches_header(&header[..0x14]));
+
+        Checksum(0xAAAAAAAA, 0x55555555)
+            .write_to_header(&mut header)
+            .unwrap();
+        assert_eq!(
+            Checksum::from_header(&header),
+            Ok(Checksum(0xAAAAAAAA, 0x55555555))
+        );
+        assert!(Checksum(0, 0).write_to_header(&mut header[..0x17]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checksum_serde() {
+        let checksum = Checksum(0x12345678, 0x9ABCDEF0);
+
+        let json = serde_json::to_string(&checksum).unwrap();
+        assert_eq!(json, "\"12345678 9ABCDEF0\"");
+        assert_eq!(serde_json::from_str::<Checksum>(&json).unwrap(), checksum);
+        assert_eq!(
+            serde_json::from_str::<Checksum>("1311768467463790320").unwrap(),
+            checksum
+        );
+        assert!(serde_json::from_str::<Checksum>("\"12345678\"").is_err());
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+// pyo3's `#[new]` expansion trips this lint on newer compilers
+#[allow(unknown_lints, non_local_definitions)]
+mod python_bindings {
+    use pyo3::exceptions::PyIndexError;
+    use pyo3::prelude::*;
+    use pyo3::pyclass::CompareOp;
+    use pyo3::types::PyBytes;
+    use std::borrow::Cow;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use crate::Ipl3ChecksumError;
+
+    /**
+     * We use a `Cow` instead of a plain &[u8] the latter only allows Python's
+     * `bytes` objects, while Cow allows for both `bytes` and `bytearray`.
+     * This is important because an argument typed as `bytes` allows to pass a
+     * `bytearray` object too.
+     */
+
+    #[pymethods]
+    impl super::Checksum {
+        #[new]
+        pub fn py_new(word0: u32, word1: u32) -> Self {
+            Self::new(word0, word1)
+        }
+
+        #[getter]
+        pub fn word0(&self) -> u32 {
+            self.0
+        }
+
+        #[getter]
+        pub fn word1(&self) -> u32 {
+            self.1
+        }
+
+        #[staticmethod]
+        pub fn fromHeader(rom_bytes: Cow<[u8]>) -> Result<Option<Self>, Ipl3ChecksumError> {
+            match Self::from_header(&rom_bytes) {
+                Ok(checksum) => Ok(Some(checksum)),
+                Err(e) => match e {
+                    Ipl3ChecksumError::BufferNotBigEnough { .. } => Ok(None),
+                    _ => Err(e),
+                },
+            }
+        }
+
+        #[staticmethod]
+        pub fn fromString(s: &str) -> Option<Self> {
+            s.parse().ok()
+        }
+
+        #[staticmethod]
+        pub fn fromU64(value: u64) -> Self {
+            Self::from(value)
+        }
+
+        pub fn toU64(&self) -> u64 {
+            u64::from(*self)
+        }
+
+        #[staticmethod]
+        pub fn fromBeBytes(bytes: [u8; 8]) -> Self {
+            Self::from_be_bytes(bytes)
+        }
+
+        pub fn toBeBytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+            PyBytes::new(py, &self.to_be_bytes())
+        }
+
+        pub fn matchesHeader(&self, rom_bytes: Cow<[u8]>) -> bool {
+            self.matches_header(&rom_bytes)
+        }
+
+        fn __str__(&self) -> String {
+            self.to_string()
+        }
+
+        fn __repr__(&self) -> String {
+            format!("Checksum(0x{:08X}, 0x{:08X})", self.0, self.1)
+        }
+
+        fn __hash__(&self) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            self.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        /// Allows comparing against both other `Checksum` objects and `(int, int)` tuples
+        fn __richcmp__(&self, other: &PyAny, op: CompareOp, py: Python<'_>) -> PyObject {
+            let other = if let Ok(other) = other.extract::<Self>() {
+                other
+            } else if let Ok(other) = other.extract::<(u32, u32)>() {
+                Self::from(other)
+            } else {
+                return py.NotImplemented();
+            };
+
+            match op {
+                CompareOp::Eq => (*self == other).into_py(py),
+                CompareOp::Ne => (*self != other).into_py(py),
+                _ => py.NotImplemented(),
+            }
+        }
+
+        /* Allows unpacking and indexing it as if it were a `(int, int)` tuple */
+
+        fn __len__(&self) -> usize {
+            2
+        }
+
+        fn __getitem__(&self, index: isize) -> PyResult<u32> {
+            match index {
+                0 | -2 => Ok(self.0),
+                1 | -1 => Ok(self.1),
+                _ => Err(PyIndexError::new_err("Checksum index out of range")),
+            }
+        }
+
+        fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+            let tuple: PyObject = (self.0, self.1).into_py(py);
+            Ok(tuple.as_ref(py).iter()?.into_py(py))
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    use crate::{utils, Checksum, Ipl3ChecksumError};
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_checksum_from_header(
+        dst_checksum: *mut Checksum,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+    ) -> Ipl3ChecksumError {
+        if dst_checksum.is_null() || rom_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(
+            rom_bytes_len.min(super::HEADER_CHECKSUM_OFFSET + 8),
+            rom_bytes,
+        ) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let checksum = match Checksum::from_header(&bytes) {
+            Err(e) => return e,
+            Ok(c) => c,
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_checksum_to_string(
+        checksum: Checksum,
+        dst_str: *mut *mut std::os::raw::c_char,
+    ) -> Ipl3ChecksumError {
+        if dst_str.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let s = match utils::c_bindings::c_string_from_rust_str(&checksum.to_string()) {
+            Err(e) => return e,
+            Ok(s) => s,
+        };
+
+        unsafe { *dst_str = s };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_checksum_from_string(
+        dst_checksum: *mut Checksum,
+        c_str: *const std::os::raw::c_char,
+    ) -> Ipl3ChecksumError {
+        if dst_checksum.is_null() || c_str.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let s = match utils::c_bindings::static_str_from_c_string(c_str) {
+            Err(e) => return e,
+            Ok(s) => s,
+        };
+
+        let checksum = match s.parse::<Checksum>() {
+            Err(e) => return e,
+            Ok(c) => c,
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        Ipl3ChecksumError::Okay
+    }
+}
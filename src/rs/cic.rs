@@ -0,0 +1,651 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+//! Software model of the CIC side of the PIF <-> CIC conversation.
+//!
+//! After a reset the CIC sends, in order:
+//!
+//! 1. A nibble with its region.
+//! 2. The seed, as 6 encoded nibbles.
+//! 3. A single 0 bit, followed by the boot code checksum as 16 encoded nibbles. The first 4 of them are a key
+//!    which doesn't affect the decoded checksum.
+//!
+//! Then it initializes its RAM for the region and reads two nibbles from the PIF, after which it waits for 2-bit
+//! commands. The compare command makes both chips scramble their RAM and exchange one bit per nibble of it, so the
+//! CIC can reset the console if the PIF doesn't agree. The 6105 also accepts a challenge command, after which it
+//! sends two 0xA nibbles, reads a 30 nibbles challenge and answers it.
+//!
+//! https://n64brew.dev/wiki/CIC-NUS
+
+use crate::{cic6105, CICKind, Ipl3ChecksumError};
+
+/// Amount of nibbles used to send the seed.
+pub const CIC_SEED_NIBBLES: usize = 6;
+
+/// Amount of nibbles used to send the boot code checksum, including the 4 key nibbles.
+pub const CIC_CHECKSUM_NIBBLES: usize = 16;
+
+/// Amount of nibbles sent by the CIC before the compare mode: the region, the seed and the checksum.
+pub const CIC_STARTUP_NIBBLES: usize = 1 + CIC_SEED_NIBBLES + CIC_CHECKSUM_NIBBLES;
+
+/// Maximum amount of bits sent by a single compare command.
+pub const CIC_COMPARE_MAX_BITS: usize = 15;
+
+/// The nibbles sent by the 6105 after a challenge command, before reading the challenge.
+pub const CIC_CHALLENGE_HELLO_NIBBLES: [u8; 2] = [0xA, 0xA];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The region of a CIC.
+pub enum CicRegion {
+    Ntsc,
+    Pal,
+}
+
+impl CicRegion {
+    /// The nibble the CIC sends first. Only bit 2 depends on the region.
+    pub fn hello_nibble(&self) -> u8 {
+        match self {
+            Self::Ntsc => 0x1,
+            Self::Pal => 0x5,
+        }
+    }
+
+    /// The region announced by the first nibble sent by the CIC.
+    pub fn from_hello_nibble(nibble: u8) -> Self {
+        if nibble & 0x4 != 0 {
+            Self::Pal
+        } else {
+            Self::Ntsc
+        }
+    }
+
+    /// The 32 nibbles of RAM the CIC starts the compare mode with.
+    ///
+    /// `None` if the values aren't known. They can be provided with `Cic::with_ram` instead.
+    pub fn get_ram_init(&self) -> Option<[u8; 32]> {
+        match self {
+            // Taken from software implementations of the CIC, need to verify
+            Self::Ntsc => Some([
+                0xE, 0x0, 0x9, 0xA, 0x1, 0x8, 0x5, 0xA, 0x1, 0x3, 0xE, 0x1, 0x0, 0xD, 0xE, 0xC,
+                0x0, 0xB, 0x1, 0x4, 0xF, 0x8, 0xB, 0x5, 0x7, 0xC, 0xD, 0x6, 0x1, 0xE, 0x9, 0x8,
+            ]),
+            // Taken from software implementations of the CIC, need to verify
+            Self::Pal => Some([
+                0xE, 0x4, 0xF, 0x5, 0x1, 0x2, 0x1, 0x7, 0x1, 0x9, 0x8, 0x5, 0x7, 0x5, 0xA, 0x0,
+                0xB, 0x1, 0x2, 0x3, 0xF, 0x8, 0x2, 0x7, 0x1, 0x9, 0x8, 0x1, 0x1, 0x5, 0xC, 0xD,
+            ]),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A command sent by the PIF to the CIC during the compare mode.
+pub enum CicCommand {
+    /// `00`: Exchange the bits of the scrambled RAM.
+    Compare,
+    /// `01`: Stop the CIC, which keeps the console in reset.
+    Die,
+    /// `10`: Start the challenge-response exchange of the 6105.
+    Challenge,
+    /// `11`: Acknowledge a reset. The CIC answers with a 0 bit.
+    Reset,
+}
+
+impl CicCommand {
+    /// Parses the 2 bits sent by the PIF, the first one being the most significant.
+    pub fn from_bits(bits: u8) -> Self {
+        match bits & 0x3 {
+            0 => Self::Compare,
+            1 => Self::Die,
+            2 => Self::Challenge,
+            _ => Self::Reset,
+        }
+    }
+
+    /// The 2 bits sent by the PIF for this command.
+    pub fn to_bits(&self) -> u8 {
+        match self {
+            Self::Compare => 0,
+            Self::Die => 1,
+            Self::Challenge => 2,
+            Self::Reset => 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// The bits sent by the CIC during a compare command, one for each bit read from the PIF.
+pub struct CompareBits {
+    bits: [u8; CIC_COMPARE_MAX_BITS],
+    len: usize,
+}
+
+impl CompareBits {
+    /// The sent bits, in order. Each one is either 0 or 1.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bits[..self.len]
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// What the CIC does after receiving a command.
+pub enum CicResponse {
+    /// The bits exchanged by the compare command.
+    Compare(CompareBits),
+    /// The 6105 sent the nibbles of `CIC_CHALLENGE_HELLO_NIBBLES` and waits for the challenge, see
+    /// `CicCompareMode::respond_challenge`.
+    Challenge([u8; 2]),
+    /// The CIC sent a 0 bit acknowledging the reset.
+    Reset,
+    /// The CIC stopped, so the console stays in reset. Every later command gets this response too.
+    Dead,
+}
+
+/// Scrambles the nibbles of `mem` after `index` up to the end of its 16 nibbles block.
+fn encode_round(mem: &mut [u8], index: usize) {
+    let mut a = mem[index];
+    for nibble in &mut mem[index + 1..(index | 0xF) + 1] {
+        a = (a + 1 + *nibble) & 0xF;
+        *nibble = a;
+    }
+}
+
+/// Inverse of `encode_round`.
+fn decode_round(mem: &mut [u8], index: usize) {
+    for i in (index + 1..(index | 0xF) + 1).rev() {
+        mem[i] = mem[i].wrapping_sub(mem[i - 1]).wrapping_sub(1) & 0xF;
+    }
+}
+
+/// Scrambles the 16 nibbles of RAM used by the compare mode.
+///
+/// Taken from software implementations of the CIC, need to verify
+fn compare_round(m: &mut [u8]) {
+    let mut x = m[15];
+    let mut a = x;
+
+    loop {
+        a = (a + m[1] + 1) & 0xF;
+        m[1] = a;
+        a = (a + m[2] + 1) & 0xF;
+        m[2] = a;
+        a = !(a + m[3]) & 0xF;
+        m[3] = a;
+
+        let mut b = 4;
+        a = m[b] + 8;
+        if a < 16 {
+            m[b] = a;
+            b += 1;
+        }
+
+        while b < 16 {
+            a = (a + m[b] + 1) & 0xF;
+            m[b] = a;
+            b += 1;
+        }
+
+        x = (x + 0xF) & 0xF;
+        if x == 0xF {
+            break;
+        }
+    }
+}
+
+/// Decodes the seed nibbles sent by the CIC.
+///
+/// ## Return
+///
+/// * The seed, or `None` if the nibbles don't have the layout sent by the CIC.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let kind = ipl3checksum::CICKind::CIC_X105;
+/// let cic = ipl3checksum::Cic::new(kind, ipl3checksum::CicRegion::Ntsc);
+/// assert_eq!(ipl3checksum::decode_cic_seed(&cic.seed_nibbles()), Some(kind.get_seed()));
+/// ```
+pub fn decode_cic_seed(nibbles: &[u8; CIC_SEED_NIBBLES]) -> Option<u32> {
+    let mut mem = [0; 16];
+    mem[0xA..].copy_from_slice(nibbles);
+    decode_round(&mut mem, 0xA);
+    decode_round(&mut mem, 0xA);
+
+    if mem[0xA..0xC] != [0xB, 0x5] || mem[0xC..0xE] != mem[0xE..0x10] {
+        return None;
+    }
+
+    Some(((mem[0xC] as u32) << 4) | mem[0xD] as u32)
+}
+
+/// Decodes the boot code checksum nibbles sent by the CIC, after the 0 bit.
+pub fn decode_cic_checksum(nibbles: &[u8; CIC_CHECKSUM_NIBBLES]) -> u64 {
+    let mut mem = *nibbles;
+    for _ in 0..4 {
+        decode_round(&mut mem, 0);
+    }
+
+    mem[4..]
+        .iter()
+        .fold(0, |checksum, nibble| (checksum << 4) | *nibble as u64)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// A CIC, before the compare mode starts.
+pub struct Cic {
+    kind: CICKind,
+    region: CicRegion,
+    checksum: Option<u64>,
+    checksum_key: [u8; 4],
+    ram: Option<[u8; 32]>,
+}
+
+impl Cic {
+    /// A CIC of the given kind and region, with the seed and boot code checksum of the kind.
+    pub fn new(kind: CICKind, region: CicRegion) -> Self {
+        Self {
+            kind,
+            region,
+            checksum: kind.get_bootcode_checksum(),
+            checksum_key: [0; 4],
+            ram: region.get_ram_init(),
+        }
+    }
+
+    /// Sends the given boot code checksum instead of the one of the kind. Only its lower 48 bits are sent.
+    pub fn with_checksum(self, checksum: u64) -> Self {
+        Self {
+            checksum: Some(checksum),
+            ..self
+        }
+    }
+
+    /// Uses the given key nibbles to encode the checksum. They are 0 by default.
+    ///
+    /// Retail CICs don't use fixed values, so the PIF must not depend on them.
+    pub fn with_checksum_key(self, checksum_key: [u8; 4]) -> Self {
+        Self {
+            checksum_key,
+            ..self
+        }
+    }
+
+    /// Starts the compare mode with the given RAM instead of the one of the region.
+    pub fn with_ram(self, ram: [u8; 32]) -> Self {
+        Self {
+            ram: Some(ram),
+            ..self
+        }
+    }
+
+    pub fn kind(&self) -> CICKind {
+        self.kind
+    }
+
+    pub fn region(&self) -> CicRegion {
+        self.region
+    }
+
+    /// The first nibble sent by the CIC.
+    pub fn hello_nibble(&self) -> u8 {
+        self.region.hello_nibble()
+    }
+
+    /// The encoded seed, sent after the hello nibble.
+    pub fn seed_nibbles(&self) -> [u8; CIC_SEED_NIBBLES] {
+        let seed = self.kind.get_seed() as u8;
+
+        let mut mem = [0; 16];
+        mem[0xA..].copy_from_slice(&[0xB, 0x5, seed >> 4, seed & 0xF, seed >> 4, seed & 0xF]);
+        encode_round(&mut mem, 0xA);
+        encode_round(&mut mem, 0xA);
+
+        let mut nibbles = [0; CIC_SEED_NIBBLES];
+        nibbles.copy_from_slice(&mem[0xA..]);
+        nibbles
+    }
+
+    /// The encoded boot code checksum, sent after the seed and a 0 bit.
+    ///
+    /// ## Return
+    ///
+    /// * The nibbles, or `UnknownBootcodeChecksum` if the boot code checksum of the kind is not known and none was
+    ///   given with `with_checksum`.
+    pub fn checksum_nibbles(&self) -> Result<[u8; CIC_CHECKSUM_NIBBLES], Ipl3ChecksumError> {
+        let checksum = self
+            .checksum
+            .ok_or(Ipl3ChecksumError::UnknownBootcodeChecksum)?;
+
+        let mut mem = [0; CIC_CHECKSUM_NIBBLES];
+        mem[..4].copy_from_slice(&self.checksum_key);
+        for (i, nibble) in mem[4..].iter_mut().enumerate() {
+            *nibble = ((checksum >> (44 - i * 4)) & 0xF) as u8;
+        }
+        for _ in 0..4 {
+            encode_round(&mut mem, 0);
+        }
+
+        Ok(mem)
+    }
+
+    /// Every nibble sent by the CIC before the compare mode, in order. The 0 bit before the checksum is not
+    /// included.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ipl3checksum;
+    /// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+    /// let cic = ipl3checksum::Cic::new(kind, ipl3checksum::CicRegion::Pal);
+    /// let stream = cic.startup_nibbles().unwrap();
+    ///
+    /// assert_eq!(ipl3checksum::CicRegion::from_hello_nibble(stream[0]), ipl3checksum::CicRegion::Pal);
+    /// assert_eq!(ipl3checksum::decode_cic_seed(stream[1..7].try_into().unwrap()), Some(kind.get_seed()));
+    /// assert_eq!(
+    ///     ipl3checksum::decode_cic_checksum(stream[7..].try_into().unwrap()),
+    ///     kind.get_bootcode_checksum().unwrap()
+    /// );
+    /// ```
+    pub fn startup_nibbles(&self) -> Result<[u8; CIC_STARTUP_NIBBLES], Ipl3ChecksumError> {
+        let mut nibbles = [0; CIC_STARTUP_NIBBLES];
+
+        nibbles[0] = self.hello_nibble();
+        nibbles[1..1 + CIC_SEED_NIBBLES].copy_from_slice(&self.seed_nibbles());
+        nibbles[1 + CIC_SEED_NIBBLES..].copy_from_slice(&self.checksum_nibbles()?);
+
+        Ok(nibbles)
+    }
+
+    /// Initializes the RAM and reads the two nibbles sent by the PIF, entering the compare mode.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pif_nibbles` - The nibbles sent by the PIF after the checksum.
+    ///
+    /// ## Return
+    ///
+    /// * The CIC in compare mode, or `UnknownCicRam` if the RAM of the region is not known and none was given with
+    ///   `with_ram`.
+    pub fn start_compare_mode(
+        &self,
+        pif_nibbles: [u8; 2],
+    ) -> Result<CicCompareMode, Ipl3ChecksumError> {
+        let mut mem = self.ram.ok_or(Ipl3ChecksumError::UnknownCicRam)?;
+
+        mem[0x01] = pif_nibbles[0] & 0xF;
+        mem[0x11] = pif_nibbles[1] & 0xF;
+
+        Ok(CicCompareMode {
+            kind: self.kind,
+            region: self.region,
+            mem,
+            dead: false,
+            challenge_pending: false,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// A CIC in compare mode, answering the commands of the PIF.
+///
+/// The PIF side runs the same algorithm, so a second instance started with the same nibbles produces the bits the
+/// PIF is expected to send.
+pub struct CicCompareMode {
+    kind: CICKind,
+    region: CicRegion,
+    mem: [u8; 32],
+    dead: bool,
+    challenge_pending: bool,
+}
+
+impl CicCompareMode {
+    /// The 32 nibbles of RAM of the CIC.
+    pub fn ram(&self) -> &[u8; 32] {
+        &self.mem
+    }
+
+    /// Whether the CIC stopped.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    /// Whether the CIC received a challenge command and waits for the challenge.
+    pub fn is_challenge_pending(&self) -> bool {
+        self.challenge_pending
+    }
+
+    /// Runs a command sent by the PIF.
+    ///
+    /// The challenge command is only understood by the 6105, every other kind stops when receiving it. The real
+    /// CIC reads the challenge right after the command, so a pending challenge is dropped if another command is run
+    /// before `respond_challenge`.
+    pub fn run_command(&mut self, command: CicCommand) -> CicResponse {
+        if self.dead {
+            return CicResponse::Dead;
+        }
+
+        self.challenge_pending = false;
+        match command {
+            CicCommand::Compare => CicResponse::Compare(self.compare()),
+            CicCommand::Challenge if self.kind == CICKind::CIC_X105 => {
+                self.challenge_pending = true;
+                CicResponse::Challenge(CIC_CHALLENGE_HELLO_NIBBLES)
+            }
+            CicCommand::Reset => CicResponse::Reset,
+            CicCommand::Die | CicCommand::Challenge => {
+                self.dead = true;
+                CicResponse::Dead
+            }
+        }
+    }
+
+    /// Answers the 30 nibbles of challenge read after the challenge command and its two 0xA nibbles. The CIC sends
+    /// a 0 bit followed by the returned nibbles, and goes back to waiting for commands.
+    ///
+    /// `None` if no challenge is pending, which is always the case if the CIC is not a 6105 or it stopped.
+    pub fn respond_challenge(
+        &mut self,
+        challenge: &[u8; cic6105::CHALLENGE_NIBBLES],
+    ) -> Option<[u8; cic6105::CHALLENGE_NIBBLES]> {
+        if !self.challenge_pending {
+            return None;
+        }
+        self.challenge_pending = false;
+
+        Some(cic6105::respond(challenge))
+    }
+
+    fn compare(&mut self) -> CompareBits {
+        // Only the upper half of the RAM is sent
+        for _ in 0..3 {
+            compare_round(&mut self.mem[0x10..]);
+        }
+
+        // Never starts at 0
+        let mut index = match self.mem[0x17] & 0xF {
+            0 => 1,
+            index => index as usize,
+        };
+
+        let mut bits = CompareBits {
+            bits: [0; CIC_COMPARE_MAX_BITS],
+            len: 0,
+        };
+        // The NTSC CIC goes up until the end of the RAM, the PAL one goes down until the start
+        while index & 0xF != 0 {
+            bits.bits[bits.len] = self.mem[0x10 | index] & 0x1;
+            bits.len += 1;
+
+            match self.region {
+                CicRegion::Ntsc => index = (index + 1) & 0xF,
+                CicRegion::Pal => index -= 1,
+            }
+        }
+
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cic, CicCommand, CicRegion, CicResponse};
+    use crate::{CICKind, Ipl3ChecksumError};
+
+    #[test]
+    fn test_cic_protocol() -> Result<(), Ipl3ChecksumError> {
+        for kind in CICKind::iter() {
+            for region in [CicRegion::Ntsc, CicRegion::Pal] {
+                let cic = Cic::new(kind, region).with_checksum_key([0x4, 0xD, 0x0, 0x7]);
+                println!("{} {:?}", kind, region);
+
+                assert_eq!(CicRegion::from_hello_nibble(cic.hello_nibble()), region);
+                assert_eq!(
+                    super::decode_cic_seed(&cic.seed_nibbles()),
+                    Some(kind.get_seed())
+                );
+
+                match kind.get_bootcode_checksum() {
+                    Some(checksum) => {
+                        let stream = cic.startup_nibbles()?;
+                        println!("    {:X?}", stream);
+                        assert!(stream.iter().all(|nibble| *nibble < 0x10));
+                        assert_eq!(
+                            super::decode_cic_checksum(stream[7..].try_into().unwrap()),
+                            checksum
+                        );
+
+                        // The key changes the stream, but not the checksum
+                        let other = Cic::new(kind, region).checksum_nibbles()?;
+                        assert_ne!(other[..], stream[7..]);
+                        assert_eq!(super::decode_cic_checksum(&other), checksum);
+                    }
+                    None => {
+                        assert_eq!(
+                            cic.startup_nibbles(),
+                            Err(Ipl3ChecksumError::UnknownBootcodeChecksum)
+                        );
+                        let cic = cic.with_checksum(0x123456789ABC);
+                        assert_eq!(
+                            super::decode_cic_checksum(&cic.checksum_nibbles()?),
+                            0x123456789ABC
+                        );
+                    }
+                }
+            }
+        }
+
+        assert_eq!(super::decode_cic_seed(&[0; 6]), None);
+
+        for bits in 0..4 {
+            assert_eq!(CicCommand::from_bits(bits).to_bits(), bits);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cic_compare_mode() -> Result<(), Ipl3ChecksumError> {
+        let cic = Cic::new(CICKind::CIC_6102_7101, CicRegion::Ntsc);
+        let mut cic_side = cic.start_compare_mode([0x3, 0xA])?;
+        let mut pif_side = cic.start_compare_mode([0x3, 0xA])?;
+
+        for _ in 0..32 {
+            let response = cic_side.run_command(CicCommand::Compare);
+            assert_eq!(response, pif_side.run_command(CicCommand::Compare));
+            let start = cic_side.ram()[0x17];
+
+            let bits = match response {
+                CicResponse::Compare(bits) => bits,
+                _ => panic!("{:?}", response),
+            };
+            assert!(!bits.as_slice().is_empty());
+            assert!(bits.as_slice().iter().all(|bit| *bit <= 1));
+            assert_eq!(bits.as_slice().len(), 16 - (start & 0xF).max(1) as usize);
+        }
+        assert_eq!(cic_side.run_command(CicCommand::Reset), CicResponse::Reset);
+
+        // Different nibbles from the PIF change the bits
+        let mut other = cic.start_compare_mode([0x3, 0xB])?;
+        let mut cic_side = cic.start_compare_mode([0x3, 0xA])?;
+        let differ = (0..8).any(|_| {
+            other.run_command(CicCommand::Compare) != cic_side.run_command(CicCommand::Compare)
+        });
+        assert!(differ);
+
+        // Only the 6105 understands the challenge
+        assert_eq!(
+            cic_side.run_command(CicCommand::Challenge),
+            CicResponse::Dead
+        );
+        assert!(cic_side.is_dead());
+        assert_eq!(cic_side.run_command(CicCommand::Compare), CicResponse::Dead);
+        let mut x105 = Cic::new(CICKind::CIC_X105, CicRegion::Ntsc).start_compare_mode([0, 0])?;
+        // A challenge can only be answered after the command
+        assert_eq!(x105.respond_challenge(&[0x5; 30]), None);
+        assert_eq!(
+            x105.run_command(CicCommand::Challenge),
+            CicResponse::Challenge([0xA, 0xA])
+        );
+        assert!(x105.is_challenge_pending());
+        assert_eq!(
+            x105.respond_challenge(&[0x5; 30]),
+            Some(crate::cic6105::respond(&[0x5; 30]))
+        );
+        assert!(!x105.is_challenge_pending());
+        assert_eq!(x105.respond_challenge(&[0x5; 30]), None);
+        // Other commands drop the pending challenge
+        x105.run_command(CicCommand::Challenge);
+        assert!(matches!(
+            x105.run_command(CicCommand::Compare),
+            CicResponse::Compare(_)
+        ));
+        assert_eq!(x105.respond_challenge(&[0x5; 30]), None);
+        assert_eq!(cic_side.respond_challenge(&[0x5; 30]), None);
+        assert_eq!(x105.run_command(CicCommand::Die), CicResponse::Dead);
+
+        // The PAL CIC starts with its own RAM and goes down it
+        let pal = Cic::new(CICKind::CIC_6102_7101, CicRegion::Pal);
+        let mut cic_side = pal.start_compare_mode([0x3, 0xA])?;
+        let mut pif_side = pal.start_compare_mode([0x3, 0xA])?;
+        assert_ne!(cic_side.ram(), cic.start_compare_mode([0x3, 0xA])?.ram());
+        for _ in 0..32 {
+            let response = cic_side.run_command(CicCommand::Compare);
+            assert_eq!(response, pif_side.run_command(CicCommand::Compare));
+            let start = (cic_side.ram()[0x17] & 0xF).max(1) as usize;
+
+            let bits = match response {
+                CicResponse::Compare(bits) => bits,
+                _ => panic!("{:?}", response),
+            };
+            assert!(bits.as_slice().iter().all(|bit| *bit <= 1));
+            assert_eq!(bits.as_slice().len(), start);
+            let expected: Vec<u8> = (1..=start)
+                .rev()
+                .map(|index| cic_side.ram()[0x10 | index] & 0x1)
+                .collect();
+            assert_eq!(bits.as_slice(), expected);
+        }
+
+        // Both regions scramble the RAM the same way
+        let ram = CicRegion::Ntsc.get_ram_init().unwrap();
+        let mut pal_side = pal.with_ram(ram).start_compare_mode([0x3, 0xA])?;
+        let mut ntsc_side = cic.start_compare_mode([0x3, 0xA])?;
+        if let (CicResponse::Compare(pal_bits), CicResponse::Compare(ntsc_bits)) = (
+            pal_side.run_command(CicCommand::Compare),
+            ntsc_side.run_command(CicCommand::Compare),
+        ) {
+            assert_eq!(pal_side.ram(), ntsc_side.ram());
+            let start = (ntsc_side.ram()[0x17] & 0xF).max(1) as usize;
+            assert_eq!(ntsc_bits.as_slice().len(), 16 - start);
+            assert_eq!(pal_bits.as_slice().len(), start);
+            assert_eq!(pal_bits.as_slice()[0], ntsc_bits.as_slice()[0]);
+        } else {
+            panic!();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+//! Challenge-response algorithm of the CIC 6105 (and 7105).
+//!
+//! Games using this CIC send a challenge to it through the PIF after booting, and check the response to detect
+//! copiers. The challenge and the response are 30 nibbles each, stored on the PIF RAM at 0x30 as 15 bytes.
+//!
+//! https://n64brew.dev/wiki/PIF-NUS#Challenge/Response
+
+/// Amount of nibbles of a challenge, and of its response.
+pub const CHALLENGE_NIBBLES: usize = 30;
+
+const LUT0: [u8; 0x10] = [
+    0x4, 0x7, 0xA, 0x7, 0xE, 0x5, 0xE, 0x1, 0xC, 0xF, 0x8, 0xF, 0x6, 0x3, 0x6, 0x9,
+];
+
+const LUT1: [u8; 0x10] = [
+    0x4, 0x1, 0xA, 0x7, 0xE, 0x5, 0xE, 0x1, 0xC, 0x9, 0x8, 0x5, 0x6, 0x3, 0xC, 0x9,
+];
+
+/// Calculates the response of the CIC 6105 to a challenge.
+///
+/// ## Arguments
+///
+/// * `challenge` - The nibbles of the challenge, one per byte. Only the lower 4 bits of each byte are used.
+///
+/// ## Return
+///
+/// * The nibbles of the response, one per byte.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum::cic6105;
+/// let response = cic6105::respond(&[0; 30]);
+/// assert!(response.iter().all(|nibble| *nibble < 0x10));
+/// ```
+pub fn respond(challenge: &[u8; CHALLENGE_NIBBLES]) -> [u8; CHALLENGE_NIBBLES] {
+    let mut response = [0; CHALLENGE_NIBBLES];
+
+    let mut key: u8 = 0xB;
+    let mut lut = &LUT0;
+    for (nibble, challenge_nibble) in response.iter_mut().zip(challenge) {
+        let value = key.wrapping_add(challenge_nibble.wrapping_mul(5)) & 0xF;
+        *nibble = value;
+        key = lut[value as usize];
+
+        let sign = (value >> 3) & 0x1;
+        let magnitude = if sign == 1 { !value } else { value } & 0x7;
+        let mut use_lut1 = if magnitude % 3 == 1 {
+            sign == 1
+        } else {
+            sign == 0
+        };
+
+        if core::ptr::eq(lut, &LUT1) {
+            match value {
+                0x1 | 0x9 => use_lut1 = true,
+                0xB | 0xE => use_lut1 = false,
+                _ => {}
+            }
+        }
+
+        lut = if use_lut1 { &LUT1 } else { &LUT0 };
+    }
+
+    response
+}
+
+/// Like `respond`, but taking and returning the 15 bytes stored on the PIF RAM, the high nibble of each byte going
+/// first.
+pub fn respond_packed(challenge: &[u8; CHALLENGE_NIBBLES / 2]) -> [u8; CHALLENGE_NIBBLES / 2] {
+    let mut nibbles = [0; CHALLENGE_NIBBLES];
+    for (pair, byte) in nibbles.chunks_exact_mut(2).zip(challenge) {
+        pair[0] = byte >> 4;
+        pair[1] = byte & 0xF;
+    }
+
+    let response = respond(&nibbles);
+
+    let mut packed = [0; CHALLENGE_NIBBLES / 2];
+    for (byte, pair) in packed.iter_mut().zip(response.chunks_exact(2)) {
+        *byte = (pair[0] << 4) | pair[1];
+    }
+    packed
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::PyBytes;
+    use std::borrow::Cow;
+
+    #[pyfunction]
+    pub(crate) fn cic6105Respond<'py>(
+        py: Python<'py>,
+        challenge: Cow<[u8]>,
+    ) -> Option<&'py PyBytes> {
+        let challenge = challenge.as_ref().try_into().ok()?;
+
+        Some(PyBytes::new(py, &super::respond(challenge)))
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    use crate::Ipl3ChecksumError;
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_cic6105_respond(
+        dst_response: *mut [u8; super::CHALLENGE_NIBBLES],
+        challenge: *const [u8; super::CHALLENGE_NIBBLES],
+    ) -> Ipl3ChecksumError {
+        if dst_response.is_null() || challenge.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let response = super::respond(unsafe { &*challenge });
+
+        unsafe { *dst_response = response };
+
+        Ipl3ChecksumError::Okay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CHALLENGE_NIBBLES;
+
+    /// The algorithm as implemented by the CIC itself, using its carry flag. Used to cross check `respond`.
+    fn respond_carry(challenge: &[u8; CHALLENGE_NIBBLES]) -> [u8; CHALLENGE_NIBBLES] {
+        let mut mem = *challenge;
+        let mut a: u8 = 5;
+        let mut carry = true;
+
+        for nibble in mem.iter_mut() {
+            let value = *nibble & 0xF;
+
+            if value & 1 == 0 {
+                a += 8;
+            }
+            if a & 2 == 0 {
+                a += 4;
+            }
+            a = (a + value) & 0xF;
+            let stored = a;
+
+            if !carry {
+                a += 7;
+            }
+            a = (a + stored) & 0xF;
+            a = a + stored + carry as u8;
+            carry = a >= 0x10;
+            a &= 0xF;
+
+            a = !a & 0xF;
+            *nibble = a;
+        }
+
+        mem
+    }
+
+    #[test]
+    fn test_cic6105_vectors() {
+        let challenge = [
+            0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0x0,
+            0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xA, 0xB, 0xC, 0xD,
+        ];
+        let response = [
+            0xB, 0x4, 0x8, 0xB, 0x9, 0x8, 0xA, 0xB, 0x7, 0xE, 0x8, 0x3, 0x3, 0x8, 0x2, 0x5, 0x5,
+            0xA, 0x2, 0x9, 0xD, 0xC, 0x4, 0x1, 0xF, 0x6, 0x0, 0xB, 0x1, 0x8,
+        ];
+        assert_eq!(super::respond(&challenge), response);
+
+        let challenge = [
+            0x3, 0xA, 0x1, 0x8, 0xF, 0x6, 0xD, 0x4, 0xB, 0x2, 0x9, 0x0, 0x7, 0xE, 0x5, 0xC, 0x3,
+            0xA, 0x1, 0x8, 0xF, 0x6, 0xD, 0x4, 0xB, 0x2, 0x9, 0x0, 0x7, 0xE,
+        ];
+        let response = [
+            0xA, 0xA, 0xD, 0xB, 0xA, 0x6, 0xF, 0xD, 0xA, 0x2, 0x7, 0x1, 0xA, 0xE, 0xF, 0x5, 0x4,
+            0x0, 0x9, 0x1, 0xC, 0x4, 0xF, 0xD, 0xA, 0x2, 0x7, 0x1, 0xA, 0xE,
+        ];
+        assert_eq!(super::respond(&challenge), response);
+
+        let mut zeroes_response = [0x9; CHALLENGE_NIBBLES];
+        for nibble in zeroes_response.iter_mut().skip(1).step_by(2) {
+            *nibble = 0xF;
+        }
+        zeroes_response[0] = 0xB;
+        assert_eq!(super::respond(&[0; CHALLENGE_NIBBLES]), zeroes_response);
+
+        // Only the lower nibble is used
+        assert_eq!(super::respond(&[0xF0; CHALLENGE_NIBBLES]), zeroes_response);
+
+        assert_eq!(
+            super::respond_packed(&[
+                0xA5, 0xB4, 0x87, 0x96, 0xE1, 0xF0, 0xC3, 0xD2, 0x2D, 0x3C, 0x0F, 0x1E, 0x69, 0x78,
+                0x4B
+            ]),
+            [
+                0xDC, 0xD7, 0x92, 0x7F, 0xFE, 0x11, 0xD2, 0xBF, 0x38, 0xB1, 0x7C, 0xB5, 0x34, 0x1F,
+                0xDA
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cic6105_respond() {
+        // Deterministic pseudo random challenges
+        let mut state: u32 = 0x6105;
+        for _ in 0..0x1000 {
+            let mut challenge = [0; CHALLENGE_NIBBLES];
+            for nibble in challenge.iter_mut() {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                *nibble = (state >> 16) as u8 & 0xF;
+            }
+
+            let response = super::respond(&challenge);
+            assert!(response.iter().all(|nibble| *nibble < 0x10));
+            assert_eq!(response, respond_carry(&challenge));
+        }
+    }
+}
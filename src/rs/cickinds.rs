@@ -1,10 +1,13 @@
 /* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
 /* SPDX-License-Identifier: MIT */
 
+use core::fmt;
+use core::str::FromStr;
+
 #[cfg(feature = "python_bindings")]
 use pyo3::prelude::*;
 
-use crate::{checksum, Ipl3ChecksumError};
+use crate::{checksum, Checksum, CicParams, FinalizationVariant, Ipl3ChecksumError};
 
 /* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/cickinds.h` */
 #[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
@@ -27,6 +30,31 @@ pub enum CICKind {
 }
 
 impl CICKind {
+    /// Every CIC kind, in declaration order.
+    pub const ALL: [CICKind; 7] = [
+        Self::CIC_6101,
+        Self::CIC_6102_7101,
+        Self::CIC_7102,
+        Self::CIC_X103,
+        Self::CIC_X105,
+        Self::CIC_X106,
+        Self::CIC_5101,
+    ];
+
+    /// Iterates over every CIC kind, in declaration order.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ipl3checksum::CICKind;
+    /// let names: Vec<String> = CICKind::iter().map(|kind| kind.to_string()).collect();
+    /// assert_eq!(names[1], "CIC_6102_7101");
+    /// assert_eq!(names.len(), CICKind::ALL.len());
+    /// ```
+    pub fn iter() -> impl Iterator<Item = CICKind> {
+        Self::ALL.into_iter()
+    }
+
     /// Seed value set by the PIF ROM before the CPU (and the IPL3) is executed.
     ///
     /// https://n64brew.dev/wiki/PIF-NUS#IPL3_checksum_algorithm
@@ -48,6 +76,57 @@ impl CICKind {
         }
     }
 
+    /// The 48-bit boot code checksum stored on the CIC, which the PIF ROM compares against the one it calculates
+    /// over the IPL3. See `calculate_bootcode_checksum`.
+    ///
+    /// `None` if the value isn't known.
+    ///
+    /// https://n64brew.dev/wiki/PIF-NUS#IPL2
+    pub fn get_bootcode_checksum(&self) -> Option<u64> {
+        match self {
+            Self::CIC_6101 => Some(0x45CC73EE317A),
+            Self::CIC_6102_7101 => Some(0xA536C0F1D859),
+            Self::CIC_7102 => Some(0x44160EC5D9AF),
+            Self::CIC_X103 => Some(0x586FD4709867),
+            Self::CIC_X105 => Some(0x8618A45BC2D3),
+            Self::CIC_X106 => Some(0x2BBAD4E6EB74),
+            Self::CIC_5101 => None,
+        }
+    }
+
+    /// How the IPL3 combines its registers into the final checksum.
+    pub fn get_finalization_variant(&self) -> FinalizationVariant {
+        match self {
+            Self::CIC_X103 | Self::CIC_5101 => FinalizationVariant::Add,
+            Self::CIC_X106 => FinalizationVariant::Mul,
+            _ => FinalizationVariant::Xor,
+        }
+    }
+
+    /// Every parameter used by the IPL3 of this kind to calculate the checksum.
+    ///
+    /// The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+    pub fn get_params(&self, header_entrypoint: u32) -> CicParams {
+        CicParams {
+            seed: self.get_seed(),
+            magic: self.get_magic(),
+            bytes_to_check: checksum::get_bytes_to_check(
+                *self,
+                self.get_entrypoint(header_entrypoint),
+            ),
+            finalization: self.get_finalization_variant(),
+            uses_x105_table: *self == Self::CIC_X105,
+        }
+    }
+
+    /// The minimum size a ROM needs for this kind to checksum it, which is the header, the IPL3 and the checked
+    /// range.
+    ///
+    /// The header entrypoint is only relevant for CIC_5101, which may check more bytes depending on it.
+    pub fn get_min_rom_size(&self, header_entrypoint: u32) -> usize {
+        checksum::HEADER_IPL3_SIZE + self.get_params(header_entrypoint).bytes_to_check
+    }
+
     /// Calculates the actual entrypoint address based on the entrypoint specified on the header.
     ///
     /// CIC 7102 is a notable case since its IPL3 hardcodes it, ignoring the entrypoint from the header.
@@ -85,6 +164,49 @@ impl CICKind {
         }
     }
 
+    /// The MD5 digest of the IPL3 of this kind, as raw bytes.
+    ///
+    /// Same as `get_hash_md5`, but usable without formatting the digest as a string.
+    pub fn get_hash_md5_digest(&self) -> [u8; 16] {
+        match self {
+            Self::CIC_6101 => [
+                0x90, 0x0B, 0x4A, 0x5B, 0x68, 0xED, 0xB7, 0x1F, 0x4C, 0x7E, 0xD5, 0x2A, 0xCD, 0x81,
+                0x4F, 0xC5,
+            ],
+            Self::CIC_6102_7101 => [
+                0xE2, 0x4D, 0xD7, 0x96, 0xB2, 0xFA, 0x16, 0x51, 0x15, 0x21, 0x13, 0x9D, 0x28, 0xC8,
+                0x35, 0x6B,
+            ],
+            Self::CIC_7102 => [
+                0x95, 0x58, 0x94, 0xC2, 0xE4, 0x0A, 0x69, 0x8B, 0xF9, 0x8A, 0x67, 0xB7, 0x8A, 0x4E,
+                0x28, 0xFA,
+            ],
+            Self::CIC_X103 => [
+                0x31, 0x90, 0x38, 0x09, 0x73, 0x46, 0xE1, 0x2C, 0x26, 0xC3, 0xC2, 0x1B, 0x56, 0xF8,
+                0x6F, 0x23,
+            ],
+            Self::CIC_X105 => [
+                0xFF, 0x22, 0xA2, 0x96, 0xE5, 0x5D, 0x34, 0xAB, 0x0A, 0x07, 0x7D, 0xC2, 0xBA, 0x5F,
+                0x57, 0x96,
+            ],
+            Self::CIC_X106 => [
+                0x64, 0x60, 0x38, 0x77, 0x49, 0xAC, 0x0B, 0xD9, 0x25, 0xAA, 0x54, 0x30, 0xBC, 0x78,
+                0x64, 0xFE,
+            ],
+            Self::CIC_5101 => [
+                0x71, 0x1F, 0x8C, 0x3A, 0xC5, 0x4F, 0xC7, 0x0A, 0x42, 0x62, 0x6B, 0xF6, 0xC1, 0x71,
+                0x44, 0x3D,
+            ],
+        }
+    }
+
+    pub fn from_hash_md5_digest(digest: &[u8; 16]) -> Result<Self, Ipl3ChecksumError> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.get_hash_md5_digest() == *digest)
+            .ok_or(Ipl3ChecksumError::UnableToDetectCIC)
+    }
+
     pub fn get_name(&self) -> &'static str {
         match self {
             Self::CIC_6101 => "CIC_6101",
@@ -112,40 +234,44 @@ impl CICKind {
         }
     }
 
+    /// Every valid name for `from_name`
+    pub const VALID_NAMES: [&'static str; 30] = [
+        "CIC_6101",
+        "6101",
+        "CIC_6102_7101",
+        "CIC_6102",
+        "CIC_7101",
+        "6102_7101",
+        "6102",
+        "7101",
+        "CIC_7102",
+        "7102",
+        "CIC_X103",
+        "CIC_6103",
+        "CIC_7103",
+        "X103",
+        "6103",
+        "7103",
+        "CIC_X105",
+        "CIC_6105",
+        "CIC_7105",
+        "X105",
+        "6105",
+        "7105",
+        "CIC_X106",
+        "CIC_6106",
+        "CIC_7106",
+        "X106",
+        "6106",
+        "7106",
+        "CIC_5101",
+        "5101",
+    ];
+
     /// Returns a Vec of valid names for `from_name`
+    #[cfg(feature = "std")]
     pub fn valid_names() -> Vec<&'static str> {
-        vec![
-            "CIC_6101",
-            "6101",
-            "CIC_6102_7101",
-            "CIC_6102",
-            "CIC_7101",
-            "6102_7101",
-            "6102",
-            "7101",
-            "CIC_7102",
-            "7102",
-            "CIC_X103",
-            "CIC_6103",
-            "CIC_7103",
-            "X103",
-            "6103",
-            "7103",
-            "CIC_X105",
-            "CIC_6105",
-            "CIC_7105",
-            "X105",
-            "6105",
-            "7105",
-            "CIC_X106",
-            "CIC_6106",
-            "CIC_7106",
-            "X106",
-            "6106",
-            "7106",
-            "CIC_5101",
-            "5101",
-        ]
+        Self::VALID_NAMES.to_vec()
     }
 
     pub fn from_value(value: usize) -> Result<Self, Ipl3ChecksumError> {
@@ -179,13 +305,142 @@ impl CICKind {
     /// let bytes = vec![0; 0x101000];
     /// let kind = ipl3checksum::CICKind::CIC_6102_7101;
     /// let checksum = kind.calculate_checksum(&bytes).unwrap();
-    /// println!("{:08X} {:08X}", checksum.0, checksum.1);
+    /// println!("{}", checksum);
     /// ```
-    pub fn calculate_checksum(&self, rom_bytes: &[u8]) -> Result<(u32, u32), Ipl3ChecksumError> {
+    pub fn calculate_checksum(&self, rom_bytes: &[u8]) -> Result<Checksum, Ipl3ChecksumError> {
         checksum::calculate_checksum(rom_bytes, *self)
     }
 }
 
+/// Displayed as the name returned by `get_name`.
+impl fmt::Display for CICKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get_name())
+    }
+}
+
+/// Parses every name accepted by `from_name`.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum::CICKind;
+/// assert_eq!("6102".parse(), Ok(CICKind::CIC_6102_7101));
+/// assert_eq!("CIC_7103".parse(), Ok(CICKind::CIC_X103));
+/// assert!("6104".parse::<CICKind>().is_err());
+/// ```
+impl FromStr for CICKind {
+    type Err = Ipl3ChecksumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s)
+    }
+}
+
+/// Converts every number accepted by `from_value`.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum::CICKind;
+/// assert_eq!(CICKind::try_from(7101), Ok(CICKind::CIC_6102_7101));
+/// assert!(CICKind::try_from(6104).is_err());
+/// ```
+impl TryFrom<u32> for CICKind {
+    type Error = Ipl3ChecksumError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::from_value(value as usize)
+    }
+}
+
+/// Serialized as the name returned by `get_name`. Deserializing accepts every name accepted by `from_name`, and the
+/// numbers accepted by `from_value` on human readable formats.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use core::fmt;
+
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::{Deserialize, Serialize, Serializer};
+
+    use super::CICKind;
+
+    impl Serialize for CICKind {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.get_name())
+        }
+    }
+
+    struct CICKindVisitor;
+
+    impl<'de> Visitor<'de> for CICKindVisitor {
+        type Value = CICKind;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a CIC kind name or number")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            CICKind::from_name(v).map_err(|_| E::unknown_variant(v, &CICKind::VALID_NAMES))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            CICKind::from_value(v as usize)
+                .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CICKind {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(CICKindVisitor)
+            } else {
+                deserializer.deserialize_str(CICKindVisitor)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use super::CICKind;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_cickind_serde() {
+        // Every alias and number is accepted for the kind
+        for name in CICKind::VALID_NAMES {
+            let kind: CICKind = serde_json::from_str(&format!("\"{}\"", name)).unwrap();
+            assert_eq!(Ok(kind), CICKind::from_name(name));
+            assert_eq!(
+                serde_json::to_string(&kind).unwrap(),
+                format!("\"{}\"", kind.get_name())
+            );
+        }
+        assert_eq!(
+            serde_json::from_str::<CICKind>("7105").unwrap(),
+            CICKind::CIC_X105
+        );
+        assert!(serde_json::from_str::<CICKind>("\"6104\"").is_err());
+        assert!(serde_json::from_str::<CICKind>("6104").is_err());
+
+        // Formats which aren't self describing can't use `deserialize_any`
+        for kind in CICKind::iter() {
+            let bytes = bincode::serialize(&kind).unwrap();
+            assert_eq!(bincode::deserialize::<CICKind>(&bytes).unwrap(), kind);
+
+            let bytes = bincode::serialize(&(kind, Some(kind), 0x1234u32)).unwrap();
+            assert_eq!(
+                bincode::deserialize::<(CICKind, Option<CICKind>, u32)>(&bytes).unwrap(),
+                (kind, Some(kind), 0x1234)
+            );
+        }
+        let bytes = bincode::serialize("6104").unwrap();
+        assert!(bincode::deserialize::<CICKind>(&bytes).is_err());
+    }
+}
+
 #[cfg(feature = "python_bindings")]
 #[allow(non_snake_case)]
 mod python_bindings {
@@ -211,6 +466,22 @@ mod python_bindings {
             self.get_magic()
         }
 
+        pub fn getBootcodeChecksum(&self) -> Option<u64> {
+            self.get_bootcode_checksum()
+        }
+
+        pub fn getFinalizationVariant(&self) -> super::FinalizationVariant {
+            self.get_finalization_variant()
+        }
+
+        pub fn getParams(&self, header_entrypoint: u32) -> super::CicParams {
+            self.get_params(header_entrypoint)
+        }
+
+        pub fn getMinRomSize(&self, header_entrypoint: u32) -> usize {
+            self.get_min_rom_size(header_entrypoint)
+        }
+
         pub fn getEntrypoint(&self, header_entrypoint: u32) -> u32 {
             self.get_entrypoint(header_entrypoint)
         }
@@ -265,7 +536,7 @@ mod python_bindings {
         pub fn calculateChecksum(
             &self,
             rom_bytes: Cow<[u8]>,
-        ) -> Result<(u32, u32), Ipl3ChecksumError> {
+        ) -> Result<super::Checksum, Ipl3ChecksumError> {
             self.calculate_checksum(&rom_bytes)
         }
     }
@@ -285,6 +556,14 @@ mod c_bindings {
         kind.get_magic()
     }
 
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_cickind_get_min_rom_size(
+        kind: CICKind,
+        header_entrypoint: u32,
+    ) -> usize {
+        kind.get_min_rom_size(header_entrypoint)
+    }
+
     #[no_mangle]
     pub extern "C" fn ipl3checksum_cickind_get_entrypoint(
         kind: CICKind,
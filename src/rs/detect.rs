@@ -1,7 +1,8 @@
 /* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
 /* SPDX-License-Identifier: MIT */
 
-use crate::{cickinds::CICKind, error::Ipl3ChecksumError, utils};
+use crate::checksum::HEADER_IPL3_SIZE;
+use crate::{byte_order, cickinds::CICKind, error::Ipl3ChecksumError, RomByteOrder};
 
 /// Tries to detect an IPL3 binary.
 ///
@@ -23,9 +24,9 @@ pub fn detect_cic_raw(raw_bytes: &[u8]) -> Result<CICKind, Ipl3ChecksumError> {
         });
     }
 
-    let bytes_hash = utils::get_hash_md5(raw_bytes);
+    let bytes_hash = md5::compute(raw_bytes);
 
-    CICKind::from_hash_md5(&bytes_hash)
+    CICKind::from_hash_md5_digest(&bytes_hash.0)
 }
 
 /// Tries to detect an IPL3 in a ROM.
@@ -43,6 +44,39 @@ pub fn detect_cic(rom_bytes: &[u8]) -> Result<CICKind, Ipl3ChecksumError> {
     detect_cic_raw(&rom_bytes[0x40..0x1000])
 }
 
+/// Tries to detect an IPL3 in a ROM of any byte order.
+///
+/// The byte order is detected from the ROM header.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - ROM binary in big endian, byteswapped or little endian format.
+///
+/// ## Return
+///
+/// * The detected CIC kind and the byte order of the ROM. Possible errors:
+///     - `rom_bytes` being smaller than the header and the IPL3
+///     - Not able to detect the byte order
+///     - Not able to detect the CIC kind
+pub fn detect_cic_any_order(
+    rom_bytes: &[u8],
+) -> Result<(CICKind, RomByteOrder), Ipl3ChecksumError> {
+    let order = RomByteOrder::detect(rom_bytes)?;
+
+    if rom_bytes.len() < HEADER_IPL3_SIZE {
+        return Err(Ipl3ChecksumError::BufferNotBigEnough {
+            buffer_len: rom_bytes.len(),
+            expected_len: HEADER_IPL3_SIZE,
+        });
+    }
+
+    let mut header = [0; HEADER_IPL3_SIZE];
+    header.copy_from_slice(&rom_bytes[..HEADER_IPL3_SIZE]);
+    byte_order::normalize_byte_order(&mut header, order);
+
+    Ok((detect_cic(&header)?, order))
+}
+
 #[cfg(feature = "python_bindings")]
 #[allow(non_snake_case)]
 pub(crate) mod python_bindings {
@@ -89,11 +123,29 @@ pub(crate) mod python_bindings {
             },
         }
     }
+
+    #[pyfunction]
+    pub(crate) fn detectCICAnyOrder(
+        rom_bytes: Cow<[u8]>,
+    ) -> Result<Option<(super::CICKind, super::RomByteOrder)>, super::Ipl3ChecksumError> {
+        match super::detect_cic_any_order(&rom_bytes) {
+            Ok(detected) => Ok(Some(detected)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                super::Ipl3ChecksumError::UnknownByteOrder => Ok(None),
+                super::Ipl3ChecksumError::UnableToDetectCIC => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
 }
 
 #[cfg(feature = "c_bindings")]
 mod c_bindings {
-    use crate::{utils, CICKind, Ipl3ChecksumError};
+    use crate::{utils, CICKind, Ipl3ChecksumError, RomByteOrder};
 
     #[no_mangle]
     pub extern "C" fn ipl3checksum_detect_cic_raw(
@@ -144,4 +196,31 @@ mod c_bindings {
 
         Ipl3ChecksumError::Okay
     }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_detect_cic_any_order(
+        dst_kind: *mut CICKind,
+        dst_order: *mut RomByteOrder,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+    ) -> Ipl3ChecksumError {
+        if dst_kind.is_null() || dst_order.is_null() || rom_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let (kind, order) = match super::detect_cic_any_order(&bytes) {
+            Err(e) => return e,
+            Ok(detected) => detected,
+        };
+
+        unsafe { *dst_kind = kind };
+        unsafe { *dst_order = order };
+
+        Ipl3ChecksumError::Okay
+    }
 }
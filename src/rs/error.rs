@@ -1,44 +1,193 @@
 /* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
 /* SPDX-License-Identifier: MIT */
 
+use core::fmt;
+
 /* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/error.h` */
 // repr is kinda complex and I may have got it wrong.
 // I tried to follow the stuff at https://rust-lang.github.io/unsafe-code-guidelines/layout/enums.html
 #[cfg_attr(feature = "c_bindings", repr(C))]
-#[derive(Clone, Debug, PartialEq, Eq, Hash, thiserror::Error)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Ipl3ChecksumError {
     #[cfg(feature = "c_bindings")]
-    #[error("Not an error")]
     Okay,
     #[cfg(feature = "c_bindings")]
-    #[error("Pointer is null")]
     NullPointer,
     #[cfg(feature = "c_bindings")]
-    #[error("Failed to convert a FFI string")]
     StringConversion,
 
-    #[error("Unaligned read at offset 0x{offset:X}. \n (This is probably a library bug, please report me)")]
-    UnalignedRead { offset: usize },
-    #[error("Failed to convert bytes at offset 0x{offset:X} \n (This is probably a library bug, please report me)")]
-    ByteConversion { offset: usize },
-    #[error("Tried to access data out of bounds at offset 0x{offset:X}. Requested bytes: 0x{requested_bytes:X}. Buffer length: 0x{buffer_len:X} \n (This is probably a library bug, please report me)")]
+    UnalignedRead {
+        offset: usize,
+    },
+    ByteConversion {
+        offset: usize,
+    },
     OutOfBounds {
         offset: usize,
         requested_bytes: usize,
         buffer_len: usize,
     },
-    #[error("The input byte buffer is not big enough. It should be at least 0x{expected_len:X} bytes long, but it was 0x{buffer_len:X} bytes")]
     BufferNotBigEnough {
         buffer_len: usize,
         expected_len: usize,
     },
-    #[error("The input byte buffer didn't have the expected size. It should be exactly 0x{expected_len:X} bytes long, but it was 0x{buffer_len:X} bytes")]
     BufferSizeIsWrong {
         buffer_len: usize,
         expected_len: usize,
     },
-    #[error("Unable to detect CIC variant")]
     UnableToDetectCIC,
+    InvalidChecksumString,
+    InvalidForgeRegion {
+        region_offset: usize,
+        region_size: usize,
+    },
+    ForgeRegionTooSmall {
+        region_size: usize,
+        expected_size: usize,
+    },
+    UnableToForgeChecksum,
+    UnknownByteOrder,
+    UnknownBootcodeChecksum,
+    InvalidBootcodeSearch,
+    UnknownCicRam,
+
+    /// Reading a ROM from a file failed. The C bindings can't carry the `std::io::Error` itself.
+    #[cfg(feature = "c_bindings")]
+    IoError,
+}
+
+impl fmt::Display for Ipl3ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "c_bindings")]
+            Self::Okay => write!(f, "Not an error"),
+            #[cfg(feature = "c_bindings")]
+            Self::NullPointer => write!(f, "Pointer is null"),
+            #[cfg(feature = "c_bindings")]
+            Self::StringConversion => write!(f, "Failed to convert a FFI string"),
+            Self::UnalignedRead { offset } => write!(
+                f,
+                "Unaligned read at offset 0x{offset:X}. \n (This is probably a library bug, please report me)"
+            ),
+            Self::ByteConversion { offset } => write!(
+                f,
+                "Failed to convert bytes at offset 0x{offset:X} \n (This is probably a library bug, please report me)"
+            ),
+            Self::OutOfBounds {
+                offset,
+                requested_bytes,
+                buffer_len,
+            } => write!(
+                f,
+                "Tried to access data out of bounds at offset 0x{offset:X}. Requested bytes: 0x{requested_bytes:X}. Buffer length: 0x{buffer_len:X} \n (This is probably a library bug, please report me)"
+            ),
+            Self::BufferNotBigEnough {
+                buffer_len,
+                expected_len,
+            } => write!(
+                f,
+                "The input byte buffer is not big enough. It should be at least 0x{expected_len:X} bytes long, but it was 0x{buffer_len:X} bytes"
+            ),
+            Self::BufferSizeIsWrong {
+                buffer_len,
+                expected_len,
+            } => write!(
+                f,
+                "The input byte buffer didn't have the expected size. It should be exactly 0x{expected_len:X} bytes long, but it was 0x{buffer_len:X} bytes"
+            ),
+            Self::UnableToDetectCIC => write!(f, "Unable to detect CIC variant"),
+            Self::InvalidChecksumString => write!(
+                f,
+                "Invalid checksum string. Expected two 32-bits hexadecimal words or a single 64-bits hexadecimal value"
+            ),
+            Self::InvalidForgeRegion {
+                region_offset,
+                region_size,
+            } => write!(
+                f,
+                "The region at offset 0x{region_offset:X} with size 0x{region_size:X} is not aligned to a word or is not inside the range checked by the CIC"
+            ),
+            Self::ForgeRegionTooSmall {
+                region_size,
+                expected_size,
+            } => write!(
+                f,
+                "The region is not big enough to forge the checksum. It should be at least 0x{expected_size:X} bytes long, but it was 0x{region_size:X} bytes"
+            ),
+            Self::UnableToForgeChecksum => {
+                write!(f, "Unable to find words that produce the target checksum")
+            }
+            Self::UnknownByteOrder => write!(f, "Unable to detect the byte order of the ROM"),
+            Self::UnknownBootcodeChecksum => {
+                write!(f, "The boot code checksum of this CIC kind is not known")
+            }
+            Self::InvalidBootcodeSearch => write!(
+                f,
+                "Invalid boot code search. The offsets must be unique, aligned to a word and inside the IPL3, and the range of candidates must fit in them"
+            ),
+            Self::UnknownCicRam => write!(f, "The initial RAM of the CIC for this region is not known"),
+            #[cfg(feature = "c_bindings")]
+            Self::IoError => write!(f, "Failed to read the ROM"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Ipl3ChecksumError {}
+
+/// Error returned by the functions which read the ROM from a file or a `std::io::Read`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ReadRomError {
+    /// Reading the ROM failed.
+    Io(std::io::Error),
+    /// The ROM was read, but the checksum could not be calculated.
+    Checksum(Ipl3ChecksumError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadRomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Checksum(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadRomError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Checksum(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ReadRomError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Ipl3ChecksumError> for ReadRomError {
+    fn from(err: Ipl3ChecksumError) -> Self {
+        Self::Checksum(err)
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+impl From<ReadRomError> for Ipl3ChecksumError {
+    fn from(err: ReadRomError) -> Self {
+        match err {
+            ReadRomError::Io(_) => Self::IoError,
+            ReadRomError::Checksum(e) => e,
+        }
+    }
 }
 
 #[cfg(feature = "python_bindings")]
@@ -54,6 +203,14 @@ pub(crate) mod python_bindings {
     pyo3::create_exception!(ipl3checksum, BufferNotBigEnough, Ipl3ChecksumError);
     pyo3::create_exception!(ipl3checksum, BufferSizeIsWrong, Ipl3ChecksumError);
     pyo3::create_exception!(ipl3checksum, UnableToDetectCIC, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, InvalidChecksumString, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, InvalidForgeRegion, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, ForgeRegionTooSmall, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, UnableToForgeChecksum, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, UnknownByteOrder, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, UnknownBootcodeChecksum, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, InvalidBootcodeSearch, Ipl3ChecksumError);
+    pyo3::create_exception!(ipl3checksum, UnknownCicRam, Ipl3ChecksumError);
 
     impl std::convert::From<super::Ipl3ChecksumError> for PyErr {
         fn from(err: super::Ipl3ChecksumError) -> PyErr {
@@ -76,12 +233,42 @@ pub(crate) mod python_bindings {
                 super::Ipl3ChecksumError::UnableToDetectCIC => {
                     UnableToDetectCIC::new_err(err.to_string())
                 }
+                super::Ipl3ChecksumError::InvalidChecksumString => {
+                    InvalidChecksumString::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::InvalidForgeRegion { .. } => {
+                    InvalidForgeRegion::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::ForgeRegionTooSmall { .. } => {
+                    ForgeRegionTooSmall::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::UnableToForgeChecksum => {
+                    UnableToForgeChecksum::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::UnknownByteOrder => {
+                    UnknownByteOrder::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::UnknownBootcodeChecksum => {
+                    UnknownBootcodeChecksum::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::InvalidBootcodeSearch => {
+                    InvalidBootcodeSearch::new_err(err.to_string())
+                }
+                super::Ipl3ChecksumError::UnknownCicRam => UnknownCicRam::new_err(err.to_string()),
                 #[cfg(feature = "c_bindings")]
                 super::Ipl3ChecksumError::Okay
                 | super::Ipl3ChecksumError::NullPointer
-                | super::Ipl3ChecksumError::StringConversion => {
-                    Ipl3ChecksumError::new_err(err.to_string())
-                }
+                | super::Ipl3ChecksumError::StringConversion
+                | super::Ipl3ChecksumError::IoError => Ipl3ChecksumError::new_err(err.to_string()),
+            }
+        }
+    }
+
+    impl std::convert::From<super::ReadRomError> for PyErr {
+        fn from(err: super::ReadRomError) -> PyErr {
+            match err {
+                super::ReadRomError::Io(e) => e.into(),
+                super::ReadRomError::Checksum(e) => e.into(),
             }
         }
     }
@@ -0,0 +1,794 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+//! Checksum forging.
+//!
+//! The forged words keep the `a3`, `s0` and `a2` registers exactly as the original words left them, so the rest
+//! of the ROM is processed the same way as before. The remaining registers are only used by the finalization, and
+//! it can be solved for them for every CIC kind:
+//!
+//! * `t2` is left free, it is accounted for when solving `t3`.
+//! * `t3` and `t4` are driven to the values the finalization needs to produce the target checksum.
+//!
+//! The words are built in three parts:
+//!
+//! 1. A random filler word and a second one choosing the low bits of the registers, followed by two words that fix
+//!    `s0 - a3` and `a2 ^ t3`, which the rest of the words can't change. The fillers are rerolled until this is
+//!    possible.
+//! 2. Words that are multiples of 32 and don't exceed `a2`, which move `a3` and `s0` by the same amount and
+//!    `a2` and `t3` by the same xor, taking them to their final values.
+//! 3. Two groups of four words that don't change any register besides `t4` (and `t2`), chosen by a meet in the
+//!    middle search so `t4` ends up with the required value.
+
+use core::ops::Range;
+
+use crate::checksum::{self, ChecksumState, HEADER_IPL3_SIZE};
+use crate::{utils, CICKind, Checksum, CicParams, FinalizationVariant, Ipl3ChecksumError};
+
+/// Amount of words written by `forge_checksum`.
+const FORGE_WORDS: usize = 16;
+
+/// Minimum size in bytes of the region passed to `forge_checksum`.
+pub const FORGE_MIN_REGION_SIZE: usize = FORGE_WORDS * 4;
+
+/// Maximum amount of positions of the region `forge_checksum` tries to write the words at, which bounds the time
+/// spent on targets that can't be reached.
+pub const FORGE_MAX_POSITIONS: usize = 64;
+
+/// Filler words tried on each position of the region before moving to the next one.
+const ATTEMPTS_PER_POSITION: usize = 1 << 16;
+
+/// Amount of candidates on each side of the search for the `t4` words.
+const T4_CANDIDATES: u32 = 1 << 15;
+
+/// Rotations which would need more iterations than this to solve for `s0 - a3` are skipped.
+const MAX_SOLVER_COST: u64 = 1 << 8;
+
+/// Placeholder for the `t4` words, used before they are known.
+const T4_PLACEHOLDER: u32 = 0x20;
+
+/// Inverse of the first half of `ChecksumState::finalize`. Returns the `t3` needed to produce `checksum0`.
+fn required_t3(kind: CICKind, a3: u32, t2: u32, checksum0: u32) -> u32 {
+    match kind.get_finalization_variant() {
+        FinalizationVariant::Add => checksum0.wrapping_sub(a3 ^ t2),
+        FinalizationVariant::Mul => checksum0.wrapping_sub(a3.wrapping_mul(t2)),
+        FinalizationVariant::Xor => checksum0 ^ a3 ^ t2,
+    }
+}
+
+/// Inverse of the second half of `ChecksumState::finalize`. Returns the `t4` needed to produce `checksum1`.
+fn required_t4(kind: CICKind, s0: u32, a2: u32, checksum1: u32) -> u32 {
+    match kind.get_finalization_variant() {
+        FinalizationVariant::Add => checksum1.wrapping_sub(s0 ^ a2),
+        FinalizationVariant::Mul => checksum1.wrapping_sub(s0.wrapping_mul(a2)),
+        FinalizationVariant::Xor => checksum1 ^ s0 ^ a2,
+    }
+}
+
+/// Multiplicative inverse of an odd number, modulo 2^32.
+fn inverse(value: u32) -> u32 {
+    let mut x = value;
+    for _ in 0..5 {
+        x = x.wrapping_mul(2u32.wrapping_sub(value.wrapping_mul(x)));
+    }
+    x
+}
+
+/// Calls `f` with every word `u` such that `u & 0x1F == shift` and `u.rotate_left(shift) - u == diff`,
+/// until it returns `true`.
+///
+/// Splitting `u` into its top `shift` bits `h` and the rest `l`, the difference is `l * (2^shift - 1) + h * (1 -
+/// 2^(32 - shift))`. Both factors are odd, so either half can be solved from the other one. The half with less
+/// possible values is enumerated.
+fn solve_rotation_difference(diff: u32, shift: u32, mut f: impl FnMut(u32) -> bool) -> bool {
+    if shift == 0 {
+        // Rotating by 0 never changes the word
+        return diff == 0 && f(0xFFFF_FFE0);
+    }
+
+    let low_bits = 32 - shift;
+    let low_factor = (1u32 << shift).wrapping_sub(1);
+    let high_factor = 1u32.wrapping_sub(1 << low_bits);
+
+    let mut check = |high: u64, low: u64| {
+        if high >= 1 << shift || low >= 1 << low_bits {
+            return false;
+        }
+        let u = ((high as u32) << low_bits) | low as u32;
+        u & 0x1F == shift && u.rotate_left(shift).wrapping_sub(u) == diff && f(u)
+    };
+
+    // The low 5 bits of `l` are known if `l` has at least 5 bits
+    let low_count = if low_bits >= 5 {
+        1u64 << (low_bits - 5)
+    } else {
+        1u64 << low_bits
+    };
+
+    if low_count <= 1u64 << shift {
+        let inv = inverse(high_factor);
+        for j in 0..low_count {
+            let low = if low_bits >= 5 {
+                (j << 5) | shift as u64
+            } else {
+                j
+            };
+            let high = diff
+                .wrapping_sub((low as u32).wrapping_mul(low_factor))
+                .wrapping_mul(inv);
+            if check(high as u64, low) {
+                return true;
+            }
+        }
+    } else {
+        let inv = inverse(low_factor);
+        for high in 0..1u64 << shift {
+            let low = diff
+                .wrapping_sub((high as u32).wrapping_mul(high_factor))
+                .wrapping_mul(inv);
+            if check(high, low as u64) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Amount of iterations `solve_rotation_difference` needs for the given shift.
+fn solver_cost(shift: u32) -> u64 {
+    if shift == 0 {
+        return 1;
+    }
+    let low_bits = 32 - shift;
+    let low_count = if low_bits >= 5 {
+        1u64 << (low_bits - 5)
+    } else {
+        1u64 << low_bits
+    };
+    low_count.min(1 << shift)
+}
+
+/// The `i`th candidate for the first word of a `t4` group. Spread over the whole range so the sums they produce
+/// are too.
+fn t4_candidate(i: u32) -> u32 {
+    (i.wrapping_mul(0x9E37_79B9) & 0x03FF_FFFF) << 5
+}
+
+/// Words of the `t4` groups. They add `2^32` to `a3` and `s0` and leave `a2` and `t3` untouched.
+fn t4_group(w: u32) -> [u32; 4] {
+    let v = 0x8000_0000 - w;
+    [w, w, v, v]
+}
+
+struct Forger<'a> {
+    kind: CICKind,
+    table: Option<&'a [u32; 0x40]>,
+}
+
+impl Forger<'_> {
+    #[inline]
+    fn step(&self, state: &mut ChecksumState, word: u32, index: usize) {
+        state.update(word, self.table.map(|table| table[index & 0x3F]));
+    }
+
+    fn run(&self, mut state: ChecksumState, words: &[u32], index: usize) -> ChecksumState {
+        for (i, word) in words.iter().enumerate() {
+            self.step(&mut state, *word, index + i);
+        }
+        state
+    }
+
+    /// How much a `t4` group adds to `t4`.
+    fn t4_group_delta(&self, mut state: ChecksumState, w: u32, index: usize) -> u32 {
+        state.t4 = 0;
+        self.run(state, &t4_group(w), index).t4
+    }
+
+    /// Finds the words `w1` and `w2` such that the `t4` groups at `index1` and `index2` add `needed` to `t4`.
+    fn solve_t4_groups(
+        &self,
+        state: ChecksumState,
+        index1: usize,
+        index2: usize,
+        needed: u32,
+    ) -> Option<(u32, u32)> {
+        let mut deltas: Vec<(u32, u32)> = (1..=T4_CANDIDATES)
+            .map(|i| {
+                let w = t4_candidate(i);
+                (self.t4_group_delta(state, w, index2), w)
+            })
+            .collect();
+        deltas.sort_unstable();
+
+        (1..=T4_CANDIDATES).find_map(|i| {
+            let w1 = t4_candidate(i);
+            let rest = needed.wrapping_sub(self.t4_group_delta(state, w1, index1));
+
+            deltas
+                .binary_search_by(|(delta, _)| delta.cmp(&rest))
+                .ok()
+                .map(|found| (w1, deltas[found].1))
+        })
+    }
+
+    /// Checks the bits of `target` which are tied to the registers the forged words preserve, so targets which
+    /// can't be reached are rejected without searching.
+    fn is_reachable(&self, original_final: ChecksumState, target: Checksum) -> bool {
+        // `a3` and `t3` start equal and every word changes their lowest bit the same way, so they always share it.
+        // If it is 0 then the multiplication can't change the lowest bit of the first word
+        if self.kind.get_finalization_variant() == FinalizationVariant::Mul
+            && original_final.a3 & 1 == 0
+            && target.0 & 1 != 0
+        {
+            return false;
+        }
+
+        // Every word adds `(word ^ t7) - word` to `t4 - a3`, which can't change the bits below the lowest one set
+        // on the table, nor the lowest bit of `t4 - a3`. `a3` is preserved, so those bits of `t4` are too
+        if let Some(table) = self.table {
+            let table_bits = table.iter().fold(0, |acc, word| acc | word);
+            let mask = match table_bits.trailing_zeros() {
+                32 => u32::MAX,
+                zeros => (1 << zeros.max(1)) - 1,
+            };
+            let t4 = required_t4(self.kind, original_final.s0, original_final.a2, target.1);
+            if (t4 ^ original_final.t4) & mask != 0 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Low 5 bits of the value each word is xored with before being added to `t4`, for the words after the
+    /// second filler. All of those words leave `s0` with the same low bits as `s0_end`.
+    fn t4_low_bits(&self, index: usize, s0_end: u32) -> impl Fn(usize) -> u32 + '_ {
+        move |i| match self.table {
+            Some(table) => table[(index + i) & 0x3F] & 0x1F,
+            None => s0_end & 0x1F,
+        }
+    }
+
+    /// Tries to find `FORGE_WORDS` words that take `start` to `original_end`, except for `t2`, `t3` and `t4`,
+    /// such that processing the rest of the ROM produces `target`.
+    fn forge_at(
+        &self,
+        start: ChecksumState,
+        index: usize,
+        original_end: ChecksumState,
+        original_final: ChecksumState,
+        target: Checksum,
+        seed: u32,
+    ) -> Option<[u32; FORGE_WORDS]> {
+        // What the rest of the ROM does to the registers that are not preserved
+        let suffix_t2 = original_final.t2.wrapping_sub(original_end.t2);
+        let suffix_t3 = original_final.t3 ^ original_end.t3;
+        let suffix_t4 = original_final.t4.wrapping_sub(original_end.t4);
+
+        let target_a3 = original_end.a3;
+        let target_s0 = original_end.s0;
+        let target_a2 = original_end.a2;
+        let target_diff = target_s0.wrapping_sub(target_a3);
+        let target_t4 = required_t4(self.kind, original_final.s0, original_final.a2, target.1)
+            .wrapping_sub(suffix_t4);
+
+        // Every word after the one fixing `s0 - a3` is a multiple of 32, so the low bits they add to `t4` are known
+        let low_bits = self.t4_low_bits(index, target_s0);
+        let later_t4_low_bits = (3..FORGE_WORDS).fold(0u32, |acc, i| acc.wrapping_add(low_bits(i)));
+
+        let mut rng = seed | 1;
+
+        for _ in 0..ATTEMPTS_PER_POSITION {
+            // xorshift32
+            rng ^= rng << 13;
+            rng ^= rng >> 17;
+            rng ^= rng << 5;
+            let filler = rng;
+
+            let mut state_filler = start;
+            self.step(&mut state_filler, filler, index);
+
+            // The second filler picks the low bits of the registers. Its low 5 bits `x` select the rotation, and
+            // the bits rotated into the low 5 bits `y` are placed right after them
+            for x in 5..=27u32 {
+                // The word fixing `s0 - a3` also sets the low bits of `a3`, and they can't be changed afterwards
+                let shift = target_a3.wrapping_sub(state_filler.a3).wrapping_sub(x) & 0x1F;
+                if solver_cost(shift) > MAX_SOLVER_COST {
+                    continue;
+                }
+                let y = (state_filler.a2 ^ target_a2 ^ shift) & 0x1F;
+                let low_filler = x | (y << (32 - x));
+
+                let mut state0 = state_filler;
+                self.step(&mut state0, low_filler, index + 1);
+
+                if (state0.a2 ^ target_a2 ^ shift) & 0x1F != 0 {
+                    continue;
+                }
+                let t4_low = state0
+                    .t4
+                    .wrapping_add(shift ^ low_bits(2))
+                    .wrapping_add(later_t4_low_bits);
+                if (t4_low ^ target_t4) & 0x1F != 0 {
+                    continue;
+                }
+
+                // `t3` depends on the final `t2`, so try every amount of carries the forged words may produce
+                for carries in 0..=FORGE_WORDS as u32 {
+                    let target_t2 = start.t2.wrapping_add(carries);
+                    let target_t3 = required_t3(
+                        self.kind,
+                        original_final.a3,
+                        target_t2.wrapping_add(suffix_t2),
+                        target.0,
+                    ) ^ suffix_t3;
+                    let target_xor = target_a2 ^ target_t3;
+
+                    if (state0.a2 ^ state0.t3 ^ target_xor) & 0x1F != 0 {
+                        continue;
+                    }
+
+                    let mut found = None;
+                    solve_rotation_difference(
+                        target_diff.wrapping_sub(state0.s0.wrapping_sub(state0.a3)),
+                        shift,
+                        |u| {
+                            found = self.build(
+                                state0,
+                                index,
+                                [filler, low_filler, u],
+                                target_t2,
+                                target_t3,
+                                target_t4,
+                                original_end,
+                            );
+                            found.is_some()
+                        },
+                    );
+                    if found.is_some() {
+                        return found;
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Builds the rest of the words after the fillers and the word fixing `s0 - a3`.
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        &self,
+        state0: ChecksumState,
+        index: usize,
+        [filler, low_filler, u]: [u32; 3],
+        target_t2: u32,
+        target_t3: u32,
+        target_t4: u32,
+        original_end: ChecksumState,
+    ) -> Option<[u32; FORGE_WORDS]> {
+        let target_xor = original_end.a2 ^ target_t3;
+
+        // Both words must be bigger than `a2`, so they xor `a3` into `a2 ^ t3`
+        if u <= state0.a2 {
+            return None;
+        }
+        let mut state1 = state0;
+        self.step(&mut state1, u, index + 2);
+
+        let v = (state1.a2 ^ state1.t3 ^ target_xor).wrapping_sub(state1.a3);
+        if v <= state1.a2 {
+            return None;
+        }
+        let mut state2 = state1;
+        self.step(&mut state2, v, index + 3);
+
+        // The remaining words must not exceed `a2`, which is easy to guarantee if its top bit is set
+        if state2.a2 < 0x8000_0000 {
+            return None;
+        }
+
+        // Find words adding `add` to `a3` and xoring `a2` by `xor`.
+        // The lowest bit of a sum matches the one of the xor, and all these words are multiples of 32
+        let add = original_end.a3.wrapping_sub(state2.a3);
+        let xor = state2.a2 ^ original_end.a2;
+        if (add ^ xor) & 0x3F != 0 {
+            return None;
+        }
+        let half = (add.wrapping_sub(xor) >> 1) & 0x7FFF_FFFF;
+
+        let assemble = |w1: u32, w2: u32| {
+            let mut words = [0; FORGE_WORDS];
+            words[..6].copy_from_slice(&[filler, low_filler, u, v, half, half]);
+            words[6..10].copy_from_slice(&t4_group(w1));
+            words[10..14].copy_from_slice(&t4_group(w2));
+            words[14..].copy_from_slice(&[xor & 0x7FFF_FFFF, xor & 0x8000_0000]);
+            words
+        };
+
+        let words = assemble(T4_PLACEHOLDER, T4_PLACEHOLDER);
+        let end = self.run(state0, &words[2..], index + 2);
+        if end.a3 != original_end.a3
+            || end.s0 != original_end.s0
+            || end.a2 != original_end.a2
+            || end.t3 != target_t3
+            || end.t2 != target_t2
+        {
+            return None;
+        }
+
+        // The `t4` groups don't change any other register, so swap the placeholders for the needed values
+        let group_state = self.run(state2, &words[4..6], index + 4);
+        let needed = target_t4
+            .wrapping_sub(end.t4)
+            .wrapping_add(self.t4_group_delta(group_state, T4_PLACEHOLDER, index + 6))
+            .wrapping_add(self.t4_group_delta(group_state, T4_PLACEHOLDER, index + 10));
+        let (w1, w2) = self.solve_t4_groups(group_state, index + 6, index + 10, needed)?;
+
+        let words = assemble(w1, w2);
+        let end = self.run(state0, &words[2..], index + 2);
+        if end.t4 != target_t4 {
+            return None;
+        }
+
+        Some(words)
+    }
+}
+
+/// Overwrites words of a N64 ROM so `calculate_checksum` yields the `target` checksum for the given CIC kind.
+///
+/// Only words inside the `region_size` bytes starting at `region_offset` are modified, which must be inside the
+/// range checked by the CIC kind. The ROM header is not modified. `FORGE_MIN_REGION_SIZE` bytes are written, but a
+/// bigger region gives more chances to find a solution.
+///
+/// The data after the region is preserved by keeping part of the checksum state intact, which ties some bits of the
+/// result to the rest of the ROM. Because of this not every target can be reached. For example the lowest bit of the
+/// first word can't be changed for a `CIC_X106` ROM if the sum of its words is even, and the lowest bit of the second
+/// word can never be changed for a `CIC_X105` ROM. Those targets are rejected before searching.
+///
+/// Only the first `FORGE_MAX_POSITIONS` positions of the region are tried, so a failed search takes a bounded amount
+/// of time (a few seconds at most) no matter how big the region is.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+/// * `target` - The checksum the ROM should produce.
+/// * `region_offset` - Offset of the writable region in the ROM. It must be a multiple of 4.
+/// * `region_size` - Size in bytes of the writable region.
+///
+/// ## Return
+///
+/// * If no error happens then the range of bytes that were overwritten is returned.
+///   If an error happens then `rom_bytes` is left untouched.
+///   Possible errors:
+///     - `rom_bytes` not being big enough
+///     - The region not being aligned or not being inside the checked range
+///     - The region being smaller than `FORGE_MIN_REGION_SIZE`
+///     - Not being able to find words that produce the target checksum
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0; 0x101000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let target = ipl3checksum::Checksum(0x12345678, 0x9ABCDEF0);
+/// let written = ipl3checksum::forge_checksum(&mut bytes, kind, target, 0x2000, 0x100).unwrap();
+/// assert!(written.start >= 0x2000 && written.end <= 0x2100);
+/// assert_eq!(ipl3checksum::calculate_checksum(&bytes, kind).unwrap(), target);
+/// ```
+pub fn forge_checksum(
+    rom_bytes: &mut [u8],
+    kind: CICKind,
+    target: Checksum,
+    region_offset: usize,
+    region_size: usize,
+) -> Result<Range<usize>, Ipl3ChecksumError> {
+    // Validates the size of the ROM
+    checksum::calculate_checksum(rom_bytes, kind)?;
+
+    let bytes_to_check = CicParams::for_rom(kind, rom_bytes)?.bytes_to_check;
+
+    let region_end = region_offset.checked_add(region_size);
+    if region_offset % 4 != 0
+        || region_offset < HEADER_IPL3_SIZE
+        || region_end.map_or(true, |end| end > HEADER_IPL3_SIZE + bytes_to_check)
+    {
+        return Err(Ipl3ChecksumError::InvalidForgeRegion {
+            region_offset,
+            region_size,
+        });
+    }
+    if region_size < FORGE_MIN_REGION_SIZE {
+        return Err(Ipl3ChecksumError::ForgeRegionTooSmall {
+            region_size,
+            expected_size: FORGE_MIN_REGION_SIZE,
+        });
+    }
+
+    let table = if kind == CICKind::CIC_X105 {
+        Some(checksum::get_x105_table(rom_bytes))
+    } else {
+        None
+    };
+    let forger = Forger {
+        kind,
+        table: table.as_ref(),
+    };
+
+    fn read_word(rom_bytes: &[u8], index: usize) -> u32 {
+        let offset = HEADER_IPL3_SIZE + index * 4;
+        utils::u32_from_be_chunk(&rom_bytes[offset..offset + 4])
+    }
+
+    let first_index = (region_offset - HEADER_IPL3_SIZE) / 4;
+    let last_index =
+        (first_index + region_size / 4 - FORGE_WORDS).min(first_index + FORGE_MAX_POSITIONS - 1);
+
+    let mut start = ChecksumState::new(kind);
+    for index in 0..first_index {
+        forger.step(&mut start, read_word(rom_bytes, index), index);
+    }
+    let mut original_final = start;
+    for index in first_index..bytes_to_check / 4 {
+        forger.step(&mut original_final, read_word(rom_bytes, index), index);
+    }
+
+    if !forger.is_reachable(original_final, target) {
+        return Err(Ipl3ChecksumError::UnableToForgeChecksum);
+    }
+
+    for index in first_index..=last_index {
+        let mut original_end = start;
+        for i in index..index + FORGE_WORDS {
+            forger.step(&mut original_end, read_word(rom_bytes, i), i);
+        }
+
+        let seed = target.0 ^ target.1.rotate_left(16) ^ (index as u32).wrapping_mul(0x9E37_79B9);
+        if let Some(words) =
+            forger.forge_at(start, index, original_end, original_final, target, seed)
+        {
+            let offset = HEADER_IPL3_SIZE + index * 4;
+            let written = offset..offset + FORGE_WORDS * 4;
+
+            let original_bytes = rom_bytes[written.clone()].to_vec();
+            for (i, word) in words.iter().enumerate() {
+                utils::write_u32(rom_bytes, offset + i * 4, *word)?;
+            }
+
+            if checksum::calculate_checksum(rom_bytes, kind)? == target {
+                return Ok(written);
+            }
+            // Should never happen, but don't leave the ROM modified if it does
+            rom_bytes[written].copy_from_slice(&original_bytes);
+        }
+
+        forger.step(&mut start, read_word(rom_bytes, index), index);
+    }
+
+    Err(Ipl3ChecksumError::UnableToForgeChecksum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Forger;
+    use crate::checksum::{self, ChecksumState, HEADER_IPL3_SIZE};
+    use crate::{cickinds::CICKind, utils, Checksum, CicParams, Ipl3ChecksumError};
+    use std::fs;
+
+    /// Whether `forge_checksum` would search for `target` on `rom_bytes` instead of rejecting it right away.
+    fn is_reachable(
+        rom_bytes: &[u8],
+        kind: CICKind,
+        target: Checksum,
+    ) -> Result<bool, Ipl3ChecksumError> {
+        let table = (kind == CICKind::CIC_X105).then(|| checksum::get_x105_table(rom_bytes));
+        let forger = Forger {
+            kind,
+            table: table.as_ref(),
+        };
+
+        let bytes_to_check = CicParams::for_rom(kind, rom_bytes)?.bytes_to_check;
+        let mut state = ChecksumState::new(kind);
+        for index in 0..bytes_to_check / 4 {
+            let offset = HEADER_IPL3_SIZE + index * 4;
+            forger.step(
+                &mut state,
+                utils::u32_from_be_chunk(&rom_bytes[offset..offset + 4]),
+                index,
+            );
+        }
+
+        Ok(forger.is_reachable(state, target))
+    }
+
+    #[test]
+    fn test_forge_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, original_bytes| {
+            let header_checksum = Checksum::from_header(&original_bytes)?;
+
+            // Keep the lowest bit of each word, which can't always be changed
+            let keep_low_bit = |value: u32, header_word: u32| (value & !1) | (header_word & 1);
+            let targets = [
+                Checksum(
+                    keep_low_bit(0x12345678, header_checksum.0),
+                    keep_low_bit(0x9ABCDEF0, header_checksum.1),
+                ),
+                Checksum(
+                    keep_low_bit(0xFFFFFFFF, header_checksum.0),
+                    keep_low_bit(0, header_checksum.1),
+                ),
+            ];
+
+            for mut target in targets {
+                if kind == CICKind::CIC_X105 {
+                    // The dummy files have no IPL3, so `t4` just adds up the words and can't be changed
+                    target.1 = header_checksum.1;
+                }
+
+                assert!(is_reachable(&original_bytes, kind, target)?);
+
+                let mut bin_bytes = original_bytes.clone();
+                let written = super::forge_checksum(&mut bin_bytes, kind, target, 0x80000, 0x400)?;
+                println!("    {} -> {:X?}", target, written);
+
+                assert!(written.start >= 0x80000 && written.end <= 0x80400);
+                assert_eq!(crate::calculate_checksum(&bin_bytes, kind)?, target);
+                assert_eq!(bin_bytes[..written.start], original_bytes[..written.start]);
+                assert_eq!(bin_bytes[written.end..], original_bytes[written.end..]);
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_forge_unreachable() -> Result<(), Ipl3ChecksumError> {
+        let original_bytes = fs::read("tests/dummytests/CIC_X106/dummy.bin").unwrap();
+        let header_checksum = Checksum::from_header(&original_bytes)?;
+
+        // The sum of the words of the dummy is even, so the lowest bit of the first word is stuck
+        let target = Checksum(header_checksum.0 ^ 1, header_checksum.1);
+        let mut bin_bytes = original_bytes.clone();
+
+        // Rejected before searching
+        assert!(!is_reachable(&bin_bytes, CICKind::CIC_X106, target)?);
+        assert_eq!(
+            super::forge_checksum(&mut bin_bytes, CICKind::CIC_X106, target, 0x1000, 0x100000),
+            Err(Ipl3ChecksumError::UnableToForgeChecksum)
+        );
+        assert_eq!(bin_bytes, original_bytes);
+
+        // The lowest bit of the second word of a X105 ROM can never be changed
+        let original_bytes = fs::read("tests/dummytests/CIC_X105/dummy.bin").unwrap();
+        let header_checksum = Checksum::from_header(&original_bytes)?;
+        let target = Checksum(header_checksum.0, header_checksum.1 ^ 1);
+        let mut bin_bytes = original_bytes.clone();
+
+        // Rejected before searching
+        assert!(!is_reachable(&bin_bytes, CICKind::CIC_X105, target)?);
+        assert_eq!(
+            super::forge_checksum(&mut bin_bytes, CICKind::CIC_X105, target, 0x1000, 0x100000),
+            Err(Ipl3ChecksumError::UnableToForgeChecksum)
+        );
+        assert_eq!(bin_bytes, original_bytes);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_forge_invalid_regions() {
+        let mut bytes = vec![0; 0x101000];
+        let kind = CICKind::CIC_6102_7101;
+        let target = Checksum(0x12345678, 0x9ABCDEF0);
+
+        assert_eq!(
+            super::forge_checksum(&mut bytes, kind, target, 0x2002, 0x100),
+            Err(Ipl3ChecksumError::InvalidForgeRegion {
+                region_offset: 0x2002,
+                region_size: 0x100
+            })
+        );
+        // Inside the header and the IPL3
+        assert!(matches!(
+            super::forge_checksum(&mut bytes, kind, target, 0x800, 0x100),
+            Err(Ipl3ChecksumError::InvalidForgeRegion { .. })
+        ));
+        // Past the checked range
+        assert!(matches!(
+            super::forge_checksum(&mut bytes, kind, target, 0x100F80, 0x100),
+            Err(Ipl3ChecksumError::InvalidForgeRegion { .. })
+        ));
+        assert_eq!(
+            super::forge_checksum(&mut bytes, kind, target, 0x2000, 0x20),
+            Err(Ipl3ChecksumError::ForgeRegionTooSmall {
+                region_size: 0x20,
+                expected_size: super::FORGE_MIN_REGION_SIZE
+            })
+        );
+        assert!(matches!(
+            super::forge_checksum(&mut bytes[..0x1000], kind, target, 0x2000, 0x100),
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+        ));
+
+        // Errors don't modify the ROM
+        assert!(bytes.iter().all(|b| *b == 0));
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::PyByteArray;
+
+    #[pyfunction]
+    pub(crate) fn forgeChecksum(
+        rom_bytes: &PyByteArray,
+        kind: super::CICKind,
+        target: super::Checksum,
+        region_offset: usize,
+        region_size: usize,
+    ) -> Result<Option<(usize, usize)>, super::Ipl3ChecksumError> {
+        // Safety: No Python code is run while the bytes are being borrowed
+        match super::forge_checksum(
+            unsafe { rom_bytes.as_bytes_mut() },
+            kind,
+            target,
+            region_offset,
+            region_size,
+        ) {
+            Ok(written) => Ok(Some((written.start, written.end))),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    #[no_mangle]
+    #[allow(clippy::too_many_arguments)]
+    pub extern "C" fn ipl3checksum_forge_checksum(
+        dst_written_start: *mut usize,
+        dst_written_end: *mut usize,
+        rom_bytes_len: usize,
+        rom_bytes: *mut u8,
+        kind: super::CICKind,
+        target: super::Checksum,
+        region_offset: usize,
+        region_size: usize,
+    ) -> super::Ipl3ChecksumError {
+        if dst_written_start.is_null() || dst_written_end.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let mut bytes =
+            match super::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let written =
+            match super::forge_checksum(&mut bytes, kind, target, region_offset, region_size) {
+                Ok(written) => written,
+                Err(e) => return e,
+            };
+
+        for i in written.clone() {
+            unsafe { *rom_bytes.add(i) = bytes[i] };
+        }
+
+        unsafe { *dst_written_start = written.start };
+        unsafe { *dst_written_end = written.end };
+
+        super::Ipl3ChecksumError::Okay
+    }
+}
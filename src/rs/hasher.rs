@@ -0,0 +1,239 @@
+/* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use crate::checksum::{self, ChecksumState, HEADER_IPL3_SIZE};
+use crate::{checksum_value::Checksum, cickinds::CICKind, error::Ipl3ChecksumError, utils};
+
+/// Incremental version of `calculate_checksum`.
+///
+/// The ROM can be fed in chunks of any size by calling `update` repeatedly,
+/// the checksum is retrieved with `finalize` once enough bytes have been fed.
+///
+/// Bytes after the range checked by the CIC kind are ignored.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x101000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+///
+/// let mut hasher = ipl3checksum::Ipl3Hasher::new(kind);
+/// for chunk in bytes.chunks(0x1234) {
+///     hasher.update(chunk);
+/// }
+/// let checksum = hasher.finalize().unwrap();
+///
+/// assert_eq!(checksum, ipl3checksum::calculate_checksum(&bytes, kind).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ipl3Hasher {
+    kind: CICKind,
+    state: ChecksumState,
+
+    /// The ROM header and the IPL3. Needed to get the entrypoint and the CIC_X105 table.
+    header: [u8; HEADER_IPL3_SIZE],
+    /// Only known after the whole header has been fed.
+    bytes_to_check: Option<usize>,
+
+    /// Amount of bytes fed so far, including the ones that were ignored.
+    bytes_fed: usize,
+    /// Leftover bytes of an incomplete word.
+    pending: [u8; 4],
+    pending_len: usize,
+    words_checked: usize,
+}
+
+impl Ipl3Hasher {
+    pub fn new(kind: CICKind) -> Self {
+        Self {
+            kind,
+            state: ChecksumState::new(kind),
+            header: [0; HEADER_IPL3_SIZE],
+            bytes_to_check: None,
+            bytes_fed: 0,
+            pending: [0; 4],
+            pending_len: 0,
+            words_checked: 0,
+        }
+    }
+
+    pub fn kind(&self) -> CICKind {
+        self.kind
+    }
+
+    /// Feeds the next chunk of the ROM, in big endian format.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        if self.bytes_fed < HEADER_IPL3_SIZE {
+            let count = bytes.len().min(HEADER_IPL3_SIZE - self.bytes_fed);
+
+            self.header[self.bytes_fed..self.bytes_fed + count].copy_from_slice(&bytes[..count]);
+            self.bytes_fed += count;
+            bytes = &bytes[count..];
+
+            if self.bytes_fed < HEADER_IPL3_SIZE {
+                return;
+            }
+            self.bytes_to_check = Some(self.header_bytes_to_check());
+        }
+
+        let words_to_check = match self.bytes_to_check {
+            Some(bytes_to_check) => bytes_to_check / 4,
+            None => return,
+        };
+        self.bytes_fed += bytes.len();
+
+        // Complete a word that was split between chunks
+        if self.pending_len != 0 {
+            let count = bytes.len().min(4 - self.pending_len);
+
+            self.pending[self.pending_len..self.pending_len + count]
+                .copy_from_slice(&bytes[..count]);
+            self.pending_len += count;
+            bytes = &bytes[count..];
+
+            if self.pending_len < 4 {
+                return;
+            }
+            self.pending_len = 0;
+            if self.words_checked < words_to_check {
+                self.update_word(u32::from_be_bytes(self.pending));
+            }
+        }
+
+        let mut words = bytes.chunks_exact(4);
+        for word_bytes in &mut words {
+            if self.words_checked >= words_to_check {
+                return;
+            }
+            self.update_word(utils::u32_from_be_chunk(word_bytes));
+        }
+
+        let remainder = words.remainder();
+        self.pending[..remainder.len()].copy_from_slice(remainder);
+        self.pending_len = remainder.len();
+    }
+
+    /// Whether every byte checked by the CIC kind has already been fed.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.bytes_to_check.is_some() && self.bytes_needed() == 0
+    }
+
+    /// How many more bytes have to be fed to reach the end of the range checked by the CIC kind.
+    ///
+    /// Until the whole header has been fed only the bytes missing from it are counted, since the size of the checked
+    /// range depends on the header.
+    pub(crate) fn bytes_needed(&self) -> usize {
+        match self.bytes_to_check {
+            Some(bytes_to_check) => {
+                (HEADER_IPL3_SIZE + bytes_to_check).saturating_sub(self.bytes_fed)
+            }
+            None => HEADER_IPL3_SIZE - self.bytes_fed,
+        }
+    }
+
+    /// Returns the checksum of the bytes fed so far.
+    ///
+    /// Fails if the fed bytes do not cover the whole range checked by this CIC kind.
+    pub fn finalize(&self) -> Result<Checksum, Ipl3ChecksumError> {
+        let bytes_to_check = match self.bytes_to_check {
+            Some(bytes_to_check) => bytes_to_check,
+            None => {
+                // Mimic the errors `calculate_checksum` would return for a ROM this small
+                let header = &self.header[..self.bytes_fed];
+                let bytes_to_check = if self.kind == CICKind::CIC_5101 {
+                    checksum::get_bytes_to_check(
+                        self.kind,
+                        self.kind.get_entrypoint(utils::read_u32(header, 8)?),
+                    )
+                } else {
+                    checksum::get_bytes_to_check(self.kind, 0)
+                };
+
+                return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: self.bytes_fed,
+                    expected_len: bytes_to_check + HEADER_IPL3_SIZE,
+                });
+            }
+        };
+
+        if self.words_checked * 4 < bytes_to_check {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: self.bytes_fed,
+                expected_len: bytes_to_check + HEADER_IPL3_SIZE,
+            });
+        }
+
+        Ok(self.state.finalize(self.kind))
+    }
+
+    fn header_bytes_to_check(&self) -> usize {
+        // The header is complete at this point, so this read can't fail
+        let entrypoint = utils::u32_from_be_chunk(&self.header[8..12]);
+
+        checksum::get_bytes_to_check(self.kind, self.kind.get_entrypoint(entrypoint))
+    }
+
+    #[inline]
+    fn update_word(&mut self, word: u32) {
+        let x105_word = if self.kind == CICKind::CIC_X105 {
+            let offset = checksum::get_x105_table_offset(self.words_checked);
+            Some(utils::u32_from_be_chunk(&self.header[offset..offset + 4]))
+        } else {
+            None
+        };
+
+        self.state.update(word, x105_word);
+        self.words_checked += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cickinds::CICKind, Ipl3Hasher};
+
+    #[test]
+    fn test_dummy_files_chunked() -> Result<(), super::Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, bin_bytes| {
+            let expected = crate::calculate_checksum(&bin_bytes, kind)?;
+
+            for chunk_size in [1, 3, 4, 7, 0x40, 0x1001, 0x10000] {
+                let mut hasher = Ipl3Hasher::new(kind);
+                for chunk in bin_bytes.chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(hasher.finalize()?, expected, "chunk size: {}", chunk_size);
+            }
+
+            // Missing the last byte of the checked range
+            let mut hasher = Ipl3Hasher::new(kind);
+            hasher.update(&bin_bytes[..bin_bytes.len().min(0x101000) - 1]);
+            if kind != CICKind::CIC_5101 {
+                assert!(hasher.finalize().is_err());
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_short_input_errors() {
+        for kind in [CICKind::CIC_6102_7101, CICKind::CIC_5101] {
+            for len in [0, 4, 0x800, 0x1000, 0x80000] {
+                let bytes = vec![0; len];
+
+                let mut hasher = Ipl3Hasher::new(kind);
+                hasher.update(&bytes);
+
+                assert_eq!(
+                    hasher.finalize(),
+                    crate::calculate_checksum(&bytes, kind),
+                    "kind: {:?}, len: 0x{:X}",
+                    kind,
+                    len
+                );
+            }
+        }
+    }
+}
@@ -1,17 +1,66 @@
 /* SPDX-FileCopyrightText: © 2023-2024 Decompollaborate */
 /* SPDX-License-Identifier: MIT */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+mod batch;
+mod boot_state;
+mod bootcode;
+#[cfg(feature = "std")]
+mod bootcode_search;
+mod byte_order;
 mod checksum;
+mod checksum_value;
+mod cic;
+pub mod cic6105;
 mod cickinds;
 mod detect;
 mod error;
+#[cfg(feature = "std")]
+mod forge;
+mod hasher;
+mod padding;
+mod params;
+#[cfg(feature = "std")]
+mod reader;
+mod rom_header;
+#[cfg(feature = "std")]
+mod rom_info;
+#[cfg(feature = "std")]
+mod seed_search;
+mod short_rom;
 mod utils;
+mod verify;
 pub mod version;
 
+#[cfg(feature = "std")]
+pub use batch::*;
+pub use boot_state::*;
+pub use bootcode::*;
+#[cfg(feature = "std")]
+pub use bootcode_search::*;
+pub use byte_order::*;
 pub use checksum::*;
+pub use checksum_value::*;
+pub use cic::*;
 pub use cickinds::*;
 pub use detect::*;
 pub use error::*;
+#[cfg(feature = "std")]
+pub use forge::*;
+pub use hasher::*;
+pub use padding::*;
+pub use params::*;
+#[cfg(feature = "std")]
+pub use reader::*;
+pub use rom_header::*;
+#[cfg(feature = "std")]
+pub use rom_info::*;
+#[cfg(feature = "std")]
+pub use seed_search::*;
+pub use short_rom::*;
+pub use verify::*;
 
 #[cfg(feature = "python_bindings")]
 mod python_bindings {
@@ -21,6 +70,20 @@ mod python_bindings {
     fn ipl3checksum(py: Python<'_>, m: &PyModule) -> PyResult<()> {
         // Classes
         m.add_class::<super::cickinds::CICKind>()?;
+        m.add_class::<super::byte_order::RomByteOrder>()?;
+        m.add_class::<super::checksum_value::Checksum>()?;
+        m.add_class::<super::rom_header::RomHeader>()?;
+        m.add_class::<super::short_rom::ShortRomPolicy>()?;
+        m.add_class::<super::padding::PadRounding>()?;
+        m.add_class::<super::padding::ResizeReport>()?;
+        m.add_class::<super::verify::DetectionMethod>()?;
+        m.add_class::<super::verify::VerificationReport>()?;
+        m.add_class::<super::params::FinalizationVariant>()?;
+        m.add_class::<super::params::CicParams>()?;
+        m.add_class::<super::boot_state::TvType>()?;
+        m.add_class::<super::boot_state::MemoryWrite>()?;
+        m.add_class::<super::boot_state::BootState>()?;
+        m.add_class::<super::seed_search::CicParameterCandidate>()?;
 
         // Free functions
         m.add_function(wrap_pyfunction!(
@@ -31,6 +94,70 @@ mod python_bindings {
             super::checksum::python_bindings::calculateChecksumAutodetect,
             m
         )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::calculateChecksumWithParams,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::calculateChecksumWithPolicy,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::calculateChecksumAnyOrder,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::calculateChecksumAutodetectAnyOrder,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::reader::python_bindings::calculateChecksumFromPath,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::reader::python_bindings::calculateChecksumAutodetectFromPath,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::fixChecksum,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::checksum::python_bindings::fixChecksumAutodetect,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::padding::python_bindings::paddedRomSize,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::padding::python_bindings::trimmedRomSize,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::padding::python_bindings::padRom,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::padding::python_bindings::trimRom,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::forge::python_bindings::forgeChecksum,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::seed_search::python_bindings::searchCicParameters,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::verify::python_bindings::verifyRom,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::verify::python_bindings::verifyRomWithKind,
+            m
+        )?)?;
         m.add_function(wrap_pyfunction!(
             super::detect::python_bindings::detectCICRaw,
             m
@@ -39,6 +166,30 @@ mod python_bindings {
             super::detect::python_bindings::detectCIC,
             m
         )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::detect::python_bindings::detectCICAnyOrder,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::bootcode::python_bindings::calculateBootcodeChecksum,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::bootcode::python_bindings::validateBootcode,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::cic6105::python_bindings::cic6105Respond,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::byte_order::python_bindings::detectByteOrder,
+            m
+        )?)?;
+        m.add_function(wrap_pyfunction!(
+            super::byte_order::python_bindings::normalizeByteOrder,
+            m
+        )?)?;
 
         // Exceptions
 
@@ -79,6 +230,38 @@ mod python_bindings {
             "UnableToDetectCIC",
             py.get_type::<super::error::python_bindings::UnableToDetectCIC>(),
         )?;
+        child_module.add(
+            "InvalidChecksumString",
+            py.get_type::<super::error::python_bindings::InvalidChecksumString>(),
+        )?;
+        child_module.add(
+            "InvalidForgeRegion",
+            py.get_type::<super::error::python_bindings::InvalidForgeRegion>(),
+        )?;
+        child_module.add(
+            "ForgeRegionTooSmall",
+            py.get_type::<super::error::python_bindings::ForgeRegionTooSmall>(),
+        )?;
+        child_module.add(
+            "UnableToForgeChecksum",
+            py.get_type::<super::error::python_bindings::UnableToForgeChecksum>(),
+        )?;
+        child_module.add(
+            "UnknownByteOrder",
+            py.get_type::<super::error::python_bindings::UnknownByteOrder>(),
+        )?;
+        child_module.add(
+            "UnknownBootcodeChecksum",
+            py.get_type::<super::error::python_bindings::UnknownBootcodeChecksum>(),
+        )?;
+        child_module.add(
+            "InvalidBootcodeSearch",
+            py.get_type::<super::error::python_bindings::InvalidBootcodeSearch>(),
+        )?;
+        child_module.add(
+            "UnknownCicRam",
+            py.get_type::<super::error::python_bindings::UnknownCicRam>(),
+        )?;
 
         parent_module.add_submodule(child_module)?;
         Ok(())
@@ -86,4 +269,32 @@ mod python_bindings {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use crate::cickinds::CICKind;
+    use std::fs;
+    use std::path::Path;
+
+    /// Calls `f` with the kind, path and contents of every dummy ROM under `tests/dummytests`,
+    /// stopping at the first error.
+    pub(crate) fn for_each_dummy_rom<E, F>(mut f: F) -> Result<(), E>
+    where
+        F: FnMut(CICKind, &Path, Vec<u8>) -> Result<(), E>,
+    {
+        for path_result in fs::read_dir("tests/dummytests").unwrap() {
+            let ipl3_folder = path_result.unwrap();
+            let folder_name = ipl3_folder.file_name();
+
+            let kind = CICKind::from_name(folder_name.to_str().unwrap()).unwrap();
+
+            for bin_path_result in fs::read_dir(ipl3_folder.path()).unwrap() {
+                let bin_path = bin_path_result.unwrap().path();
+
+                println!("{:?}", bin_path);
+
+                let bin_bytes = fs::read(&bin_path).unwrap();
+                f(kind, &bin_path, bin_bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
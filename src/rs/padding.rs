@@ -0,0 +1,406 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::{CICKind, CicParams, Ipl3ChecksumError};
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/padding.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How the size of a padded ROM is rounded up.
+pub enum PadRounding {
+    /// No rounding.
+    Exact,
+    /// Round up to the next power of two, like the size of a real cartridge.
+    PowerOfTwo,
+    /// Round up to the next multiple of 1 MiB.
+    MultipleOfMiB,
+}
+
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum", get_all))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The result of padding or trimming a ROM.
+pub struct ResizeReport {
+    /// The size of the ROM before resizing it.
+    pub old_len: usize,
+    /// The size of the ROM after resizing it.
+    pub new_len: usize,
+    /// Whether bytes inside the range checked by the CIC were added, so the checksum on the header has to be fixed
+    /// again.
+    pub checksum_changed: bool,
+}
+
+/// The minimum size the given ROM needs to be checksummed by the CIC kind.
+fn min_rom_size(rom_bytes: &[u8], kind: CICKind) -> Result<usize, Ipl3ChecksumError> {
+    Ok(crate::checksum::HEADER_IPL3_SIZE + CicParams::for_rom(kind, rom_bytes)?.bytes_to_check)
+}
+
+/// Calculates the size `pad_rom` would pad the ROM to.
+///
+/// The result is never smaller than the ROM itself nor than the minimum size needed by the CIC kind, see
+/// `CICKind::get_min_rom_size`.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `kind` - The CIC kind variation the ROM is meant for.
+/// * `target_size` - The desired size. Pass 0 to only pad to the minimum size needed by the CIC kind.
+/// * `rounding` - How the size is rounded up after applying the minimums.
+///
+/// ## Return
+///
+/// * The padded size. Possible errors:
+///     - `rom_bytes` not containing the header entrypoint when `kind` is CIC_5101
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x80000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let size = ipl3checksum::padded_rom_size(&bytes, kind, 0, ipl3checksum::PadRounding::PowerOfTwo).unwrap();
+/// assert_eq!(size, 0x200000);
+/// ```
+pub fn padded_rom_size(
+    rom_bytes: &[u8],
+    kind: CICKind,
+    target_size: usize,
+    rounding: PadRounding,
+) -> Result<usize, Ipl3ChecksumError> {
+    let size = target_size
+        .max(rom_bytes.len())
+        .max(min_rom_size(rom_bytes, kind)?);
+
+    Ok(match rounding {
+        PadRounding::Exact => size,
+        PadRounding::PowerOfTwo => size.next_power_of_two(),
+        PadRounding::MultipleOfMiB => (size + 0xFFFFF) & !0xFFFFF,
+    })
+}
+
+/// Calculates the size `trim_rom` would trim the ROM to.
+///
+/// The trailing run of 0x00 or 0xFF bytes is removed, keeping the size a multiple of 4 and never trimming inside the
+/// range checked by the CIC kind, so the checksum is not affected.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `kind` - The CIC kind variation the ROM is meant for.
+///
+/// ## Return
+///
+/// * The trimmed size. Possible errors:
+///     - `rom_bytes` not containing the header entrypoint when `kind` is CIC_5101
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0xFF; 0x400000];
+/// bytes[0x123456] = 0x01;
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// assert_eq!(ipl3checksum::trimmed_rom_size(&bytes, kind).unwrap(), 0x123458);
+/// ```
+pub fn trimmed_rom_size(rom_bytes: &[u8], kind: CICKind) -> Result<usize, Ipl3ChecksumError> {
+    let min_size = min_rom_size(rom_bytes, kind)?.min(rom_bytes.len());
+
+    let data_end = match rom_bytes.last() {
+        Some(fill @ (0x00 | 0xFF)) => match rom_bytes.iter().rposition(|b| b != fill) {
+            Some(last) => last + 1,
+            None => 0,
+        },
+        _ => rom_bytes.len(),
+    };
+    let aligned_end = ((data_end + 3) & !3).min(rom_bytes.len());
+
+    Ok(aligned_end.max(min_size))
+}
+
+/// Pads a N64 ROM up to the given size, appending `fill` bytes.
+///
+/// The ROM is always padded to at least the minimum size needed by the CIC kind, see `padded_rom_size`.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `kind` - The CIC kind variation the ROM is meant for.
+/// * `target_size` - The desired size. Pass 0 to only pad to the minimum size needed by the CIC kind.
+/// * `rounding` - How the size is rounded up after applying the minimums.
+/// * `fill` - The byte used for padding. Usually 0x00 or 0xFF.
+///
+/// ## Return
+///
+/// * A report of the resize. If its `checksum_changed` is set then the checksum has to be fixed again, for example
+///   with `fix_checksum`. Possible errors:
+///     - `rom_bytes` not containing the header entrypoint when `kind` is CIC_5101
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0; 0x80000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let report = ipl3checksum::pad_rom(&mut bytes, kind, 0, ipl3checksum::PadRounding::MultipleOfMiB, 0xFF).unwrap();
+/// assert_eq!(bytes.len(), 0x200000);
+/// assert!(report.checksum_changed);
+/// ```
+#[cfg(feature = "std")]
+pub fn pad_rom(
+    rom_bytes: &mut Vec<u8>,
+    kind: CICKind,
+    target_size: usize,
+    rounding: PadRounding,
+    fill: u8,
+) -> Result<ResizeReport, Ipl3ChecksumError> {
+    let old_len = rom_bytes.len();
+    let new_len = padded_rom_size(rom_bytes, kind, target_size, rounding)?;
+    let checksum_changed = old_len < min_rom_size(rom_bytes, kind)?;
+
+    rom_bytes.resize(new_len, fill);
+
+    Ok(ResizeReport {
+        old_len,
+        new_len,
+        checksum_changed,
+    })
+}
+
+/// Removes the trailing padding of a N64 ROM.
+///
+/// The bytes checked by the CIC kind are never removed, see `trimmed_rom_size`.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format.
+/// * `kind` - The CIC kind variation the ROM is meant for.
+///
+/// ## Return
+///
+/// * A report of the resize. Its `checksum_changed` is never set. Possible errors:
+///     - `rom_bytes` not containing the header entrypoint when `kind` is CIC_5101
+#[cfg(feature = "std")]
+pub fn trim_rom(rom_bytes: &mut Vec<u8>, kind: CICKind) -> Result<ResizeReport, Ipl3ChecksumError> {
+    let old_len = rom_bytes.len();
+    let new_len = trimmed_rom_size(rom_bytes, kind)?;
+
+    rom_bytes.truncate(new_len);
+
+    Ok(ResizeReport {
+        old_len,
+        new_len,
+        checksum_changed: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PadRounding, ResizeReport};
+    use crate::{cickinds::CICKind, Ipl3ChecksumError, ShortRomPolicy};
+
+    #[test]
+    fn test_padding_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, bin_bytes| {
+            let expected = crate::calculate_checksum(&bin_bytes, kind)?;
+            let min_size = super::min_rom_size(&bin_bytes, kind)?;
+
+            // Padding past the checked range doesn't change the checksum
+            let mut padded = bin_bytes.clone();
+            let report = super::pad_rom(
+                &mut padded,
+                kind,
+                bin_bytes.len() + 1,
+                PadRounding::PowerOfTwo,
+                0xFF,
+            )?;
+            assert_eq!(
+                report,
+                ResizeReport {
+                    old_len: bin_bytes.len(),
+                    new_len: (bin_bytes.len() + 1).next_power_of_two(),
+                    checksum_changed: false,
+                }
+            );
+            assert_eq!(padded.len(), report.new_len);
+            assert_eq!(crate::calculate_checksum(&padded, kind)?, expected);
+
+            let report = super::trim_rom(&mut padded, kind)?;
+            assert!(report.new_len >= min_size && report.new_len <= bin_bytes.len());
+            assert_eq!(report.new_len % 4, 0);
+            assert_eq!(crate::calculate_checksum(&padded, kind)?, expected);
+
+            // Padding a short ROM
+            let mut short = bin_bytes[..0x80000].to_vec();
+            let report = super::pad_rom(&mut short, kind, 0, PadRounding::Exact, 0x00)?;
+            assert_eq!(
+                report,
+                ResizeReport {
+                    old_len: 0x80000,
+                    new_len: min_size,
+                    checksum_changed: true,
+                }
+            );
+            assert_eq!(
+                crate::calculate_checksum(&short, kind),
+                crate::calculate_checksum_with_policy(
+                    &bin_bytes[..0x80000],
+                    kind,
+                    ShortRomPolicy::ZeroFill
+                )
+            );
+            Ok(())
+        })?;
+
+        let kind = CICKind::CIC_6102_7101;
+        assert_eq!(
+            super::padded_rom_size(&[0; 0x1000], kind, 0x180001, PadRounding::MultipleOfMiB),
+            Ok(0x200000)
+        );
+        assert_eq!(
+            super::padded_rom_size(&[0; 0x1000], kind, 0, PadRounding::Exact),
+            Ok(0x101000)
+        );
+        assert_eq!(super::trimmed_rom_size(&[0; 0x1000], kind), Ok(0x1000));
+        assert_eq!(
+            super::trimmed_rom_size(&[0x12; 0x200001], kind),
+            Ok(0x200001)
+        );
+
+        let mut bytes = vec![0xFF; 0x200000];
+        bytes[0x1FFFFE] = 0x00;
+        assert_eq!(super::trimmed_rom_size(&bytes, kind), Ok(0x200000));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+#[allow(unknown_lints, non_local_definitions)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::PyByteArray;
+    use std::borrow::Cow;
+
+    #[pymethods]
+    impl super::ResizeReport {
+        pub fn __repr__(&self) -> String {
+            format!("{:?}", self)
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (rom_bytes, kind, target_size=0, rounding=super::PadRounding::Exact))]
+    pub(crate) fn paddedRomSize(
+        rom_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+        target_size: usize,
+        rounding: super::PadRounding,
+    ) -> Result<usize, super::Ipl3ChecksumError> {
+        super::padded_rom_size(&rom_bytes, kind, target_size, rounding)
+    }
+
+    #[pyfunction]
+    pub(crate) fn trimmedRomSize(
+        rom_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+    ) -> Result<usize, super::Ipl3ChecksumError> {
+        super::trimmed_rom_size(&rom_bytes, kind)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (rom_bytes, kind, target_size=0, rounding=super::PadRounding::Exact, fill=0))]
+    pub(crate) fn padRom(
+        rom_bytes: &PyByteArray,
+        kind: super::CICKind,
+        target_size: usize,
+        rounding: super::PadRounding,
+        fill: u8,
+    ) -> PyResult<super::ResizeReport> {
+        let mut bytes = rom_bytes.to_vec();
+        let report = super::pad_rom(&mut bytes, kind, target_size, rounding, fill)?;
+
+        rom_bytes.resize(report.new_len)?;
+        // Safety: No Python code is run while the bytes are being borrowed
+        unsafe { rom_bytes.as_bytes_mut() }.copy_from_slice(&bytes);
+
+        Ok(report)
+    }
+
+    #[pyfunction]
+    pub(crate) fn trimRom(
+        rom_bytes: &PyByteArray,
+        kind: super::CICKind,
+    ) -> PyResult<super::ResizeReport> {
+        let mut bytes = rom_bytes.to_vec();
+        let report = super::trim_rom(&mut bytes, kind)?;
+
+        rom_bytes.resize(report.new_len)?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_padded_rom_size(
+        dst_size: *mut usize,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        kind: super::CICKind,
+        target_size: usize,
+        rounding: super::PadRounding,
+    ) -> super::Ipl3ChecksumError {
+        if dst_size.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match crate::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let size = match super::padded_rom_size(&bytes, kind, target_size, rounding) {
+            Ok(size) => size,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_size = size };
+
+        super::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_trimmed_rom_size(
+        dst_size: *mut usize,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+        kind: super::CICKind,
+    ) -> super::Ipl3ChecksumError {
+        if dst_size.is_null() || rom_bytes.is_null() {
+            return super::Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes =
+            match crate::utils::c_bindings::u8_vec_from_pointer_array(rom_bytes_len, rom_bytes) {
+                Err(e) => return e,
+                Ok(d) => d,
+            };
+
+        let size = match super::trimmed_rom_size(&bytes, kind) {
+            Ok(size) => size,
+            Err(e) => return e,
+        };
+
+        unsafe { *dst_size = size };
+
+        super::Ipl3ChecksumError::Okay
+    }
+}
@@ -0,0 +1,160 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::{utils, CICKind, Ipl3ChecksumError};
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/params.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How the registers are combined into the checksum once every word has been processed.
+pub enum FinalizationVariant {
+    /// `(a3 ^ t2) ^ t3` and `(s0 ^ a2) ^ t4`. Used by most CIC kinds.
+    Xor,
+    /// `(a3 ^ t2) + t3` and `(s0 ^ a2) + t4`. Used by CIC_X103 and CIC_5101.
+    Add,
+    /// `(a3 * t2) + t3` and `(s0 * a2) + t4`. Used by CIC_X106.
+    Mul,
+}
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/params.h` */
+#[cfg_attr(
+    feature = "python_bindings",
+    pyclass(module = "ipl3checksum", get_all, set_all)
+)]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Everything that changes between the checksum algorithms of the different IPL3s.
+///
+/// Every `CICKind` has its own parameters (see `CICKind::get_params`), but custom ones can be used to checksum ROMs
+/// meant for non retail IPL3s with `calculate_checksum_with_params`.
+pub struct CicParams {
+    /// Seed value set by the PIF ROM before the CPU (and the IPL3) is executed.
+    pub seed: u32,
+    /// Magic value hardcoded inside the IPL3.
+    pub magic: u32,
+    /// How many bytes after the IPL3 are checked. It should be a multiple of 4.
+    pub bytes_to_check: usize,
+    /// How the registers are combined into the final checksum.
+    pub finalization: FinalizationVariant,
+    /// Whether each word is mixed with a word of the table at offset 0x750 of the IPL3, like CIC_X105 does.
+    pub uses_x105_table: bool,
+}
+
+impl CicParams {
+    /// Returns the parameters of the given CIC kind, reading the entrypoint from the ROM header only if the kind
+    /// needs it.
+    pub(crate) fn for_rom(kind: CICKind, rom_bytes: &[u8]) -> Result<Self, Ipl3ChecksumError> {
+        let header_entrypoint = if kind == CICKind::CIC_5101 {
+            utils::read_u32(rom_bytes, 8)?
+        } else {
+            0
+        };
+
+        Ok(kind.get_params(header_entrypoint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cickinds::CICKind, CicParams, Ipl3ChecksumError};
+
+    #[test]
+    fn test_params_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, bin_bytes| {
+            let params = CicParams::for_rom(kind, &bin_bytes)?;
+
+            assert_eq!(
+                crate::calculate_checksum_with_params(&bin_bytes, params)?,
+                crate::calculate_checksum(&bin_bytes, kind)?
+            );
+
+            // Any change on the parameters changes the checksum
+            for modified in [
+                CicParams {
+                    seed: params.seed + 1,
+                    ..params
+                },
+                CicParams {
+                    magic: params.magic ^ 0x100,
+                    ..params
+                },
+                CicParams {
+                    bytes_to_check: params.bytes_to_check - 4,
+                    ..params
+                },
+                CicParams {
+                    uses_x105_table: !params.uses_x105_table,
+                    ..params
+                },
+            ] {
+                assert_ne!(
+                    crate::calculate_checksum_with_params(&bin_bytes, modified)?,
+                    crate::calculate_checksum(&bin_bytes, kind)?
+                );
+            }
+            Ok(())
+        })?;
+
+        let params = CICKind::CIC_6102_7101.get_params(0);
+        assert!(matches!(
+            crate::calculate_checksum_with_params(&[0; 0x1000], params),
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+// pyo3's `#[new]` expansion trips this lint on newer compilers
+#[allow(unknown_lints, non_local_definitions)]
+mod python_bindings {
+    use pyo3::prelude::*;
+
+    #[pymethods]
+    impl super::CicParams {
+        #[new]
+        pub fn py_new(
+            seed: u32,
+            magic: u32,
+            bytes_to_check: usize,
+            finalization: super::FinalizationVariant,
+            uses_x105_table: bool,
+        ) -> Self {
+            Self {
+                seed,
+                magic,
+                bytes_to_check,
+                finalization,
+                uses_x105_table,
+            }
+        }
+
+        #[staticmethod]
+        pub fn fromKind(kind: super::CICKind, header_entrypoint: u32) -> Self {
+            kind.get_params(header_entrypoint)
+        }
+
+        pub fn __repr__(&self) -> String {
+            format!("{:?}", self)
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_cickind_get_params(
+        kind: super::CICKind,
+        header_entrypoint: u32,
+    ) -> super::CicParams {
+        kind.get_params(header_entrypoint)
+    }
+}
@@ -0,0 +1,420 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::checksum::HEADER_IPL3_SIZE;
+use crate::{detect, CICKind, Checksum, Ipl3Hasher, ReadRomError};
+
+/// Size of the chunks requested to the reader once the header has been read.
+const READ_CHUNK_SIZE: usize = 0x10000;
+
+/// Reads into `buffer` until it is full or the reader runs out of bytes, returning how many bytes were read.
+fn read_up_to<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+
+    while total < buffer.len() {
+        match reader.read(&mut buffer[total..]) {
+            Ok(0) => break,
+            Ok(read) => total += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(total)
+}
+
+/// Feeds the hasher until it has every byte checked by its CIC kind or the reader runs out of bytes.
+///
+/// Nothing past the checked range is requested to the reader.
+fn feed_hasher<R: Read>(reader: &mut R, hasher: &mut Ipl3Hasher) -> io::Result<()> {
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+
+    loop {
+        let len = hasher.bytes_needed().min(buffer.len());
+        if len == 0 {
+            return Ok(());
+        }
+
+        let read = read_up_to(reader, &mut buffer[..len])?;
+        hasher.update(&buffer[..read]);
+
+        if read < len {
+            // Let `finalize` report how many bytes were missing
+            return Ok(());
+        }
+    }
+}
+
+/// Calculates the checksum required by an official CIC of a N64 ROM, reading the ROM from `reader`.
+///
+/// Only the header, the IPL3 and the range checked by the CIC kind are read, so the reader is left right after said
+/// range. For CIC_5101 the size of this range depends on the header.
+///
+/// ## Arguments
+///
+/// * `reader` - Source of the bytes of the N64 ROM in big endian format. It must provide at least 0x101000 bytes.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The reader failing
+///     - The reader not providing enough bytes
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x101000];
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let checksum = ipl3checksum::calculate_checksum_from_reader(bytes.as_slice(), kind).unwrap();
+/// assert_eq!(checksum, ipl3checksum::calculate_checksum(&bytes, kind).unwrap());
+/// ```
+pub fn calculate_checksum_from_reader<R: Read>(
+    mut reader: R,
+    kind: CICKind,
+) -> Result<Checksum, ReadRomError> {
+    let mut hasher = Ipl3Hasher::new(kind);
+
+    feed_hasher(&mut reader, &mut hasher)?;
+
+    Ok(hasher.finalize()?)
+}
+
+/// Calculates the checksum required by an official CIC of a N64 ROM, reading the ROM from `reader`.
+///
+/// This function will try to autodetect the CIC kind automatically.
+/// If it fails to detect it then an error will be returned.
+///
+/// ## Arguments
+///
+/// * `reader` - Source of the bytes of the N64 ROM in big endian format. It must provide at least 0x101000 bytes.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The reader failing
+///     - The reader not providing enough bytes
+///     - Not able to detect the CIC kind
+pub fn calculate_checksum_autodetect_from_reader<R: Read>(
+    mut reader: R,
+) -> Result<Checksum, ReadRomError> {
+    let mut header = [0; HEADER_IPL3_SIZE];
+
+    let read = read_up_to(&mut reader, &mut header)?;
+    if read < HEADER_IPL3_SIZE {
+        return Err(crate::Ipl3ChecksumError::BufferNotBigEnough {
+            buffer_len: read,
+            expected_len: HEADER_IPL3_SIZE,
+        }
+        .into());
+    }
+
+    let mut hasher = Ipl3Hasher::new(detect::detect_cic(&header)?);
+    hasher.update(&header);
+
+    feed_hasher(&mut reader, &mut hasher)?;
+
+    Ok(hasher.finalize()?)
+}
+
+/// Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+///
+/// Only the header, the IPL3 and the range checked by the CIC kind are read from the file.
+///
+/// ## Arguments
+///
+/// * `path` - Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The file not existing or failing to be read
+///     - The file not being big enough
+pub fn calculate_checksum_from_path<P: AsRef<Path>>(
+    path: P,
+    kind: CICKind,
+) -> Result<Checksum, ReadRomError> {
+    calculate_checksum_from_reader(File::open(path)?, kind)
+}
+
+/// Calculates the checksum required by an official CIC of the N64 ROM at `path`.
+///
+/// This function will try to autodetect the CIC kind automatically.
+/// If it fails to detect it then an error will be returned.
+///
+/// ## Arguments
+///
+/// * `path` - Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The file not existing or failing to be read
+///     - The file not being big enough
+///     - Not able to detect the CIC kind
+pub fn calculate_checksum_autodetect_from_path<P: AsRef<Path>>(
+    path: P,
+) -> Result<Checksum, ReadRomError> {
+    calculate_checksum_autodetect_from_reader(File::open(path)?)
+}
+
+/// Calculates the checksum required by an official CIC of the N64 ROM at `path`, accessing the file through a memory
+/// map instead of reading it.
+///
+/// The OS only loads the pages of the file that the checksum touches.
+///
+/// The file must not be modified by other processes while the checksum is calculated.
+///
+/// ## Arguments
+///
+/// * `path` - Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The file not existing or failing to be mapped
+///     - The file not being big enough
+#[cfg(feature = "mmap")]
+pub fn calculate_checksum_from_path_mmap<P: AsRef<Path>>(
+    path: P,
+    kind: CICKind,
+) -> Result<Checksum, ReadRomError> {
+    let file = File::open(path)?;
+    // Safety: The map is only alive during this call and the caller is told to not modify the file meanwhile
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+
+    Ok(crate::calculate_checksum(&map, kind)?)
+}
+
+/// Calculates the checksum required by an official CIC of the N64 ROM at `path`, accessing the file through a memory
+/// map instead of reading it.
+///
+/// This function will try to autodetect the CIC kind automatically.
+/// If it fails to detect it then an error will be returned.
+///
+/// The file must not be modified by other processes while the checksum is calculated.
+///
+/// ## Arguments
+///
+/// * `path` - Path to the N64 ROM in big endian format. It must be at least 0x101000 bytes long.
+///
+/// ## Return
+///
+/// * If no error happens then the calculated checksum is returned.
+///   Possible errors:
+///     - The file not existing or failing to be mapped
+///     - The file not being big enough
+///     - Not able to detect the CIC kind
+#[cfg(feature = "mmap")]
+pub fn calculate_checksum_autodetect_from_path_mmap<P: AsRef<Path>>(
+    path: P,
+) -> Result<Checksum, ReadRomError> {
+    let file = File::open(path)?;
+    // Safety: The map is only alive during this call and the caller is told to not modify the file meanwhile
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+
+    if map.len() < HEADER_IPL3_SIZE {
+        return Err(crate::Ipl3ChecksumError::BufferNotBigEnough {
+            buffer_len: map.len(),
+            expected_len: HEADER_IPL3_SIZE,
+        }
+        .into());
+    }
+
+    Ok(crate::calculate_checksum_autodetect(&map)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cickinds::CICKind, CicParams, Ipl3ChecksumError, ReadRomError};
+    use std::io::{self, Read};
+
+    /// Hands out a few bytes per call and keeps track of how many bytes were requested in total.
+    struct TrickleReader<'a> {
+        bytes: &'a [u8],
+        consumed: usize,
+    }
+
+    impl<'a> Read for TrickleReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let count = buf.len().min(7).min(self.bytes.len() - self.consumed);
+
+            buf[..count].copy_from_slice(&self.bytes[self.consumed..self.consumed + count]);
+            self.consumed += count;
+            Ok(count)
+        }
+    }
+
+    #[test]
+    fn test_reader_dummy_files() -> Result<(), ReadRomError> {
+        crate::tests::for_each_dummy_rom(|kind, path, bin_bytes| -> Result<(), ReadRomError> {
+            let expected = crate::calculate_checksum(&bin_bytes, kind)?;
+
+            assert_eq!(super::calculate_checksum_from_path(path, kind)?, expected);
+            #[cfg(feature = "mmap")]
+            assert_eq!(
+                super::calculate_checksum_from_path_mmap(path, kind)?,
+                expected
+            );
+
+            let mut reader = TrickleReader {
+                bytes: &bin_bytes,
+                consumed: 0,
+            };
+            assert_eq!(
+                super::calculate_checksum_from_reader(&mut reader, kind)?,
+                expected
+            );
+            // Nothing after the checked range is read
+            let bytes_to_check = CicParams::for_rom(kind, &bin_bytes)?.bytes_to_check;
+            assert_eq!(reader.consumed, 0x1000 + bytes_to_check);
+
+            // The dummy files don't contain a real IPL3
+            assert!(matches!(
+                super::calculate_checksum_autodetect_from_path(path),
+                Err(ReadRomError::Checksum(Ipl3ChecksumError::UnableToDetectCIC))
+            ));
+            Ok(())
+        })?;
+
+        assert!(matches!(
+            super::calculate_checksum_from_reader(&[0; 0x80000][..], CICKind::CIC_6102_7101),
+            Err(ReadRomError::Checksum(
+                Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: 0x80000,
+                    expected_len: 0x101000
+                }
+            ))
+        ));
+        assert!(matches!(
+            super::calculate_checksum_autodetect_from_reader(&[0; 0x800][..]),
+            Err(ReadRomError::Checksum(
+                Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: 0x800,
+                    expected_len: 0x1000
+                }
+            ))
+        ));
+        let error = super::calculate_checksum_from_path(
+            "tests/dummytests/does_not_exist.bin",
+            CICKind::CIC_6102_7101,
+        )
+        .unwrap_err();
+        assert!(matches!(error, ReadRomError::Io(_)));
+        // The io error is kept as the cause
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(
+            source.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::NotFound
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use std::path::PathBuf;
+
+    #[pyfunction]
+    pub(crate) fn calculateChecksumFromPath(
+        path: PathBuf,
+        kind: super::CICKind,
+    ) -> Result<Option<super::Checksum>, super::ReadRomError> {
+        match super::calculate_checksum_from_path(path, kind) {
+            Ok(checksum) => Ok(Some(checksum)),
+            Err(e) => match e {
+                super::ReadRomError::Checksum(crate::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                }) => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn calculateChecksumAutodetectFromPath(
+        path: PathBuf,
+    ) -> Result<Option<super::Checksum>, super::ReadRomError> {
+        match super::calculate_checksum_autodetect_from_path(path) {
+            Ok(checksum) => Ok(Some(checksum)),
+            Err(e) => match e {
+                super::ReadRomError::Checksum(crate::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                }) => Ok(None),
+                super::ReadRomError::Checksum(crate::Ipl3ChecksumError::UnableToDetectCIC) => {
+                    Ok(None)
+                }
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_from_path(
+        dst_checksum: *mut super::Checksum,
+        path: *const std::os::raw::c_char,
+        kind: super::CICKind,
+    ) -> crate::Ipl3ChecksumError {
+        if dst_checksum.is_null() || path.is_null() {
+            return crate::Ipl3ChecksumError::NullPointer;
+        }
+
+        let path = match crate::utils::c_bindings::static_str_from_c_string(path) {
+            Err(e) => return e,
+            Ok(p) => p,
+        };
+
+        let checksum = match super::calculate_checksum_from_path(path, kind) {
+            Ok(chk) => chk,
+            Err(e) => return e.into(),
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        crate::Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_calculate_checksum_autodetect_from_path(
+        dst_checksum: *mut super::Checksum,
+        path: *const std::os::raw::c_char,
+    ) -> crate::Ipl3ChecksumError {
+        if dst_checksum.is_null() || path.is_null() {
+            return crate::Ipl3ChecksumError::NullPointer;
+        }
+
+        let path = match crate::utils::c_bindings::static_str_from_c_string(path) {
+            Err(e) => return e,
+            Ok(p) => p,
+        };
+
+        let checksum = match super::calculate_checksum_autodetect_from_path(path) {
+            Ok(chk) => chk,
+            Err(e) => return e.into(),
+        };
+
+        unsafe { *dst_checksum = checksum };
+
+        crate::Ipl3ChecksumError::Okay
+    }
+}
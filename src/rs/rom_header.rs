@@ -0,0 +1,653 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use core::fmt;
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::{utils, Checksum, Ipl3ChecksumError};
+
+/// Size of the N64 ROM header, in bytes.
+pub const ROM_HEADER_SIZE: usize = 0x40;
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/rom_header.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The header of a N64 ROM, stored on its first 0x40 bytes.
+///
+/// Displayed as a multiline summary of its fields.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let mut bytes = vec![0; 0x101000];
+/// bytes[0x20..0x28].copy_from_slice(b"MY GAME ");
+///
+/// let mut header = ipl3checksum::RomHeader::from_bytes(&bytes).unwrap();
+/// assert_eq!(header.image_name_chars().collect::<String>(), "MY GAME");
+///
+/// header.game_id = *b"MG";
+/// header.write_to_rom(&mut bytes).unwrap();
+/// assert_eq!(&bytes[0x3C..0x3E], b"MG");
+/// ```
+pub struct RomHeader {
+    /// Configuration of the PI bus for the cartridge domain, its most significant byte is always 0x80.
+    pub pi_bsd_domain1_config: u32,
+    /// Value used to compute the CPU clock rate, ignored on retail consoles.
+    pub clock_rate: u32,
+    /// Address where the IPL3 copies the code of the ROM to, and jumps to once it is done. Also known as entrypoint.
+    pub boot_address: u32,
+    /// The libultra release the ROM was built with. The last byte is the revision letter.
+    pub libultra_release: u32,
+    /// The checksum checked by the CIC, also known as CRC1 and CRC2.
+    pub checksum: Checksum,
+    /// Unused.
+    pub reserved0: [u8; 8],
+    /// Name of the game, in ASCII or Shift-JIS, padded with spaces or zeroes.
+    pub image_name: [u8; 20],
+    /// Unused.
+    pub reserved1: [u8; 7],
+    /// The kind of media the game is distributed on, as an ASCII character. For example `N` for cartridges.
+    pub category_code: u8,
+    /// Two ASCII characters that identify the game.
+    pub game_id: [u8; 2],
+    /// The region the game was released on, as an ASCII character. For example `E` for North America.
+    pub destination_code: u8,
+    /// Revision of the ROM, starting at 0.
+    pub rom_version: u8,
+}
+
+impl RomHeader {
+    /// Parses the header of a N64 ROM.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+    pub fn from_bytes(rom_bytes: &[u8]) -> Result<Self, Ipl3ChecksumError> {
+        if rom_bytes.len() < ROM_HEADER_SIZE {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: ROM_HEADER_SIZE,
+            });
+        }
+
+        let mut reserved0 = [0; 8];
+        reserved0.copy_from_slice(&rom_bytes[0x18..0x20]);
+        let mut image_name = [0; 20];
+        image_name.copy_from_slice(&rom_bytes[0x20..0x34]);
+        let mut reserved1 = [0; 7];
+        reserved1.copy_from_slice(&rom_bytes[0x34..0x3B]);
+
+        Ok(Self {
+            pi_bsd_domain1_config: utils::read_u32(rom_bytes, 0x00)?,
+            clock_rate: utils::read_u32(rom_bytes, 0x04)?,
+            boot_address: utils::read_u32(rom_bytes, 0x08)?,
+            libultra_release: utils::read_u32(rom_bytes, 0x0C)?,
+            checksum: Checksum::from_header(rom_bytes)?,
+            reserved0,
+            image_name,
+            reserved1,
+            category_code: rom_bytes[0x3B],
+            game_id: [rom_bytes[0x3C], rom_bytes[0x3D]],
+            destination_code: rom_bytes[0x3E],
+            rom_version: rom_bytes[0x3F],
+        })
+    }
+
+    /// The header as it is stored on the ROM.
+    pub fn to_bytes(&self) -> [u8; ROM_HEADER_SIZE] {
+        let mut bytes = [0; ROM_HEADER_SIZE];
+
+        bytes[0x00..0x04].copy_from_slice(&self.pi_bsd_domain1_config.to_be_bytes());
+        bytes[0x04..0x08].copy_from_slice(&self.clock_rate.to_be_bytes());
+        bytes[0x08..0x0C].copy_from_slice(&self.boot_address.to_be_bytes());
+        bytes[0x0C..0x10].copy_from_slice(&self.libultra_release.to_be_bytes());
+        bytes[0x10..0x18].copy_from_slice(&self.checksum.to_be_bytes());
+        bytes[0x18..0x20].copy_from_slice(&self.reserved0);
+        bytes[0x20..0x34].copy_from_slice(&self.image_name);
+        bytes[0x34..0x3B].copy_from_slice(&self.reserved1);
+        bytes[0x3B] = self.category_code;
+        bytes[0x3C..0x3E].copy_from_slice(&self.game_id);
+        bytes[0x3E] = self.destination_code;
+        bytes[0x3F] = self.rom_version;
+
+        bytes
+    }
+
+    /// Writes this header to a N64 ROM, replacing its first 0x40 bytes.
+    ///
+    /// Keep in mind the checksum is not recalculated, use `fix_checksum` afterwards if the boot address changed.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x40 bytes.
+    pub fn write_to_rom(&self, rom_bytes: &mut [u8]) -> Result<(), Ipl3ChecksumError> {
+        if rom_bytes.len() < ROM_HEADER_SIZE {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: ROM_HEADER_SIZE,
+            });
+        }
+
+        rom_bytes[..ROM_HEADER_SIZE].copy_from_slice(&self.to_bytes());
+
+        Ok(())
+    }
+
+    /// Decodes the image name, without its trailing padding.
+    ///
+    /// Besides ASCII, only the Shift-JIS characters used in practice by image names are supported: half-width
+    /// katakana, full-width alphanumerics, hiragana, katakana and a few punctuation marks. Any other character is
+    /// decoded as U+FFFD.
+    pub fn image_name_chars(&self) -> impl Iterator<Item = char> + '_ {
+        let mut name = &self.image_name[..];
+
+        // Neither of those bytes can be the second byte of a Shift-JIS character
+        while let [rest @ .., 0x00 | b' '] = name {
+            name = rest;
+        }
+
+        ShiftJisChars { bytes: name }
+    }
+
+    /// The category code, game id and destination code, in that order. Usually known as the game code.
+    pub fn game_code(&self) -> [u8; 4] {
+        [
+            self.category_code,
+            self.game_id[0],
+            self.game_id[1],
+            self.destination_code,
+        ]
+    }
+
+    /// Describes the category code, if it is a known one.
+    pub fn category_name(&self) -> Option<&'static str> {
+        match self.category_code {
+            b'N' => Some("Game Pak"),
+            b'D' => Some("64DD Disk"),
+            b'C' => Some("Expandable Game, Game Pak part"),
+            b'E' => Some("Expandable Game, 64DD Disk part"),
+            b'Z' => Some("Aleck64 Game Pak"),
+            _ => None,
+        }
+    }
+
+    /// Describes the destination code, if it is a known one.
+    pub fn destination_name(&self) -> Option<&'static str> {
+        match self.destination_code {
+            b'7' => Some("Beta"),
+            b'A' => Some("Asia"),
+            b'B' => Some("Brazil"),
+            b'C' => Some("China"),
+            b'D' => Some("Germany"),
+            b'E' => Some("North America"),
+            b'F' => Some("France"),
+            b'G' => Some("Gateway 64 (NTSC)"),
+            b'H' => Some("Netherlands"),
+            b'I' => Some("Italy"),
+            b'J' => Some("Japan"),
+            b'K' => Some("Korea"),
+            b'L' => Some("Gateway 64 (PAL)"),
+            b'N' => Some("Canada"),
+            b'P' | b'X' | b'Y' | b'Z' => Some("Europe"),
+            b'S' => Some("Spain"),
+            b'U' => Some("Australia"),
+            b'W' => Some("Scandinavia"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RomHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_ascii(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+            for byte in bytes {
+                if byte.is_ascii_graphic() {
+                    write!(f, "{}", *byte as char)?;
+                } else {
+                    write!(f, "\\x{:02X}", byte)?;
+                }
+            }
+            Ok(())
+        }
+
+        write!(f, "Image name: ")?;
+        for c in self.image_name_chars() {
+            write!(f, "{}", c)?;
+        }
+        writeln!(f)?;
+
+        write!(f, "Game code: ")?;
+        write_ascii(f, &self.game_code())?;
+        writeln!(
+            f,
+            " ({}, {})",
+            self.category_name().unwrap_or("unknown category"),
+            self.destination_name().unwrap_or("unknown destination")
+        )?;
+
+        writeln!(f, "ROM version: {}", self.rom_version)?;
+        writeln!(f, "Checksum: {}", self.checksum)?;
+        writeln!(f, "Boot address: 0x{:08X}", self.boot_address)?;
+
+        write!(f, "libultra release: ")?;
+        let [high, low, version, revision] = self.libultra_release.to_be_bytes();
+        if high == 0 && low == 0 && revision.is_ascii_uppercase() {
+            write!(f, "{}.{}{} ", version / 10, version % 10, revision as char)?;
+        }
+        writeln!(f, "(0x{:08X})", self.libultra_release)?;
+
+        writeln!(f, "Clock rate: 0x{:08X}", self.clock_rate)?;
+        write!(
+            f,
+            "PI BSD domain 1 config: 0x{:08X}",
+            self.pi_bsd_domain1_config
+        )
+    }
+}
+
+/// Decodes the subset of Shift-JIS documented on `RomHeader::image_name_chars`.
+struct ShiftJisChars<'a> {
+    bytes: &'a [u8],
+}
+
+impl Iterator for ShiftJisChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let (&first, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+
+        let decoded = match first {
+            0x00..=0x7F => Some(first as char),
+            // Half-width katakana
+            0xA1..=0xDF => char::from_u32(0xFF61 + (first - 0xA1) as u32),
+            0x81..=0x9F | 0xE0..=0xEF => match self.bytes.split_first() {
+                Some((&second, rest)) => {
+                    self.bytes = rest;
+                    decode_shift_jis_pair(first, second)
+                }
+                None => None,
+            },
+            _ => None,
+        };
+
+        Some(decoded.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+fn decode_shift_jis_pair(first: u8, second: u8) -> Option<char> {
+    if !matches!(second, 0x40..=0x7E | 0x80..=0xFC) {
+        return None;
+    }
+
+    // Convert to the row and cell of the JIS X 0208 table, both starting at 1
+    let mut row = if first <= 0x9F {
+        (first - 0x81) as u32 * 2 + 1
+    } else {
+        (first - 0xC1) as u32 * 2 + 1
+    };
+    let cell = if second >= 0x9F {
+        row += 1;
+        (second - 0x9E) as u32
+    } else if second >= 0x80 {
+        (second - 0x40) as u32
+    } else {
+        (second - 0x3F) as u32
+    };
+
+    let code = match (row, cell) {
+        (1, 1) => 0x3000,  // Ideographic space
+        (1, 2) => 0x3001,  // Ideographic comma
+        (1, 3) => 0x3002,  // Ideographic full stop
+        (1, 6) => 0x30FB,  // Katakana middle dot
+        (1, 10) => 0xFF01, // Full-width exclamation mark
+        (1, 9) => 0xFF1F,  // Full-width question mark
+        (1, 28) => 0x30FC, // Prolonged sound mark
+        // Full-width digits and latin letters
+        (3, 16..=25) => 0xFF10 + cell - 16,
+        (3, 33..=58) => 0xFF21 + cell - 33,
+        (3, 65..=90) => 0xFF41 + cell - 65,
+        (4, 1..=83) => 0x3041 + cell - 1, // Hiragana
+        (5, 1..=86) => 0x30A1 + cell - 1, // Katakana
+        _ => return None,
+    };
+
+    char::from_u32(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RomHeader;
+    use crate::{Checksum, Ipl3ChecksumError};
+
+    fn sample_header() -> [u8; 0x40] {
+        let mut bytes = [0; 0x40];
+
+        bytes[0x00..0x04].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+        bytes[0x04..0x08].copy_from_slice(&[0x00, 0x00, 0x00, 0x0F]);
+        bytes[0x08..0x0C].copy_from_slice(&[0x80, 0x24, 0x60, 0x00]);
+        bytes[0x0C..0x10].copy_from_slice(&[0x00, 0x00, 0x14, 0x44]);
+        bytes[0x10..0x18].copy_from_slice(&[0x63, 0x5A, 0x2B, 0xFF, 0x8B, 0x02, 0x23, 0x26]);
+        bytes[0x20..0x34].copy_from_slice(b"SUPER MARIO 64      ");
+        bytes[0x3B..0x40].copy_from_slice(b"NSME\x01");
+
+        bytes
+    }
+
+    #[test]
+    fn test_rom_header() -> Result<(), Ipl3ChecksumError> {
+        let bytes = sample_header();
+        let header = RomHeader::from_bytes(&bytes)?;
+
+        assert_eq!(header.pi_bsd_domain1_config, 0x80371240);
+        assert_eq!(header.clock_rate, 0xF);
+        assert_eq!(header.boot_address, 0x80246000);
+        assert_eq!(header.libultra_release, 0x1444);
+        assert_eq!(header.checksum, Checksum(0x635A2BFF, 0x8B022326));
+        assert_eq!(
+            header.image_name_chars().collect::<String>(),
+            "SUPER MARIO 64"
+        );
+        assert_eq!(&header.game_code(), b"NSME");
+        assert_eq!(header.category_name(), Some("Game Pak"));
+        assert_eq!(header.destination_name(), Some("North America"));
+        assert_eq!(header.rom_version, 1);
+
+        assert_eq!(header.to_bytes(), bytes);
+
+        let summary = header.to_string();
+        println!("{}", summary);
+        assert!(summary.contains("Image name: SUPER MARIO 64\n"));
+        assert!(summary.contains("Game code: NSME (Game Pak, North America)\n"));
+        assert!(summary.contains("Checksum: 635A2BFF 8B022326\n"));
+        assert!(summary.contains("libultra release: 2.0D (0x00001444)\n"));
+
+        // Modify the header and write it back
+        let mut rom = vec![0xAA; 0x1000];
+        let mut modified = header;
+        modified.rom_version = 2;
+        modified.image_name =
+            *b"\xCA\xDF\xB2\xD9\x82\xA0\x83\x41\x82\x60\x81\x5B\x88\x9F\x00\x00\x00\x00\x00\x00";
+        modified.write_to_rom(&mut rom)?;
+
+        assert_eq!(rom[0x3F], 2);
+        assert!(rom[0x40..].iter().all(|x| *x == 0xAA));
+        let parsed = RomHeader::from_bytes(&rom)?;
+        assert_eq!(parsed, modified);
+        assert_eq!(
+            parsed.image_name_chars().collect::<String>(),
+            "ﾊﾟｲﾙあアＡー\u{FFFD}"
+        );
+
+        assert_eq!(
+            RomHeader::from_bytes(&bytes[..0x3F]),
+            Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: 0x3F,
+                expected_len: 0x40
+            })
+        );
+        assert!(header.write_to_rom(&mut rom[..0x20]).is_err());
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+#[allow(unknown_lints, non_local_definitions)]
+mod python_bindings {
+    use pyo3::prelude::*;
+    use pyo3::types::{PyByteArray, PyBytes};
+    use std::borrow::Cow;
+
+    use crate::Ipl3ChecksumError;
+
+    #[pymethods]
+    impl super::RomHeader {
+        #[staticmethod]
+        pub fn fromBytes(rom_bytes: Cow<[u8]>) -> Result<Option<Self>, Ipl3ChecksumError> {
+            match Self::from_bytes(&rom_bytes) {
+                Ok(header) => Ok(Some(header)),
+                Err(e) => match e {
+                    Ipl3ChecksumError::BufferNotBigEnough { .. } => Ok(None),
+                    _ => Err(e),
+                },
+            }
+        }
+
+        pub fn toBytes<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+            PyBytes::new(py, &self.to_bytes())
+        }
+
+        /// Returns `False` if the ROM is too small to contain a header.
+        pub fn writeToRom(&self, rom_bytes: &PyByteArray) -> Result<bool, Ipl3ChecksumError> {
+            // Safety: No Python code is run while the bytes are being borrowed
+            match self.write_to_rom(unsafe { rom_bytes.as_bytes_mut() }) {
+                Ok(()) => Ok(true),
+                Err(e) => match e {
+                    Ipl3ChecksumError::BufferNotBigEnough { .. } => Ok(false),
+                    _ => Err(e),
+                },
+            }
+        }
+
+        #[getter(pi_bsd_domain1_config)]
+        pub fn get_pi_bsd_domain1_config(&self) -> u32 {
+            self.pi_bsd_domain1_config
+        }
+
+        #[setter(pi_bsd_domain1_config)]
+        pub fn set_pi_bsd_domain1_config(&mut self, value: u32) {
+            self.pi_bsd_domain1_config = value;
+        }
+
+        #[getter(clock_rate)]
+        pub fn get_clock_rate(&self) -> u32 {
+            self.clock_rate
+        }
+
+        #[setter(clock_rate)]
+        pub fn set_clock_rate(&mut self, value: u32) {
+            self.clock_rate = value;
+        }
+
+        #[getter(boot_address)]
+        pub fn get_boot_address(&self) -> u32 {
+            self.boot_address
+        }
+
+        #[setter(boot_address)]
+        pub fn set_boot_address(&mut self, value: u32) {
+            self.boot_address = value;
+        }
+
+        #[getter(libultra_release)]
+        pub fn get_libultra_release(&self) -> u32 {
+            self.libultra_release
+        }
+
+        #[setter(libultra_release)]
+        pub fn set_libultra_release(&mut self, value: u32) {
+            self.libultra_release = value;
+        }
+
+        #[getter(checksum)]
+        pub fn get_checksum(&self) -> super::Checksum {
+            self.checksum
+        }
+
+        #[setter(checksum)]
+        pub fn set_checksum(&mut self, value: super::Checksum) {
+            self.checksum = value;
+        }
+
+        #[getter(category_code)]
+        pub fn get_category_code(&self) -> u8 {
+            self.category_code
+        }
+
+        #[setter(category_code)]
+        pub fn set_category_code(&mut self, value: u8) {
+            self.category_code = value;
+        }
+
+        #[getter(destination_code)]
+        pub fn get_destination_code(&self) -> u8 {
+            self.destination_code
+        }
+
+        #[setter(destination_code)]
+        pub fn set_destination_code(&mut self, value: u8) {
+            self.destination_code = value;
+        }
+
+        #[getter(rom_version)]
+        pub fn get_rom_version(&self) -> u8 {
+            self.rom_version
+        }
+
+        #[setter(rom_version)]
+        pub fn set_rom_version(&mut self, value: u8) {
+            self.rom_version = value;
+        }
+
+        #[getter(image_name)]
+        pub fn get_image_name<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+            PyBytes::new(py, &self.image_name)
+        }
+
+        #[setter(image_name)]
+        pub fn set_image_name(&mut self, value: [u8; 20]) {
+            self.image_name = value;
+        }
+
+        #[getter(game_id)]
+        pub fn get_game_id<'py>(&self, py: Python<'py>) -> &'py PyBytes {
+            PyBytes::new(py, &self.game_id)
+        }
+
+        #[setter(game_id)]
+        pub fn set_game_id(&mut self, value: [u8; 2]) {
+            self.game_id = value;
+        }
+
+        pub fn decodeImageName(&self) -> String {
+            self.image_name_chars().collect()
+        }
+
+        pub fn gameCode(&self) -> String {
+            self.game_code().iter().map(|c| *c as char).collect()
+        }
+
+        pub fn categoryName(&self) -> Option<&'static str> {
+            self.category_name()
+        }
+
+        pub fn destinationName(&self) -> Option<&'static str> {
+            self.destination_name()
+        }
+
+        fn __str__(&self) -> String {
+            self.to_string()
+        }
+
+        fn __repr__(&self) -> String {
+            format!("{:?}", self)
+        }
+    }
+}
+
+#[cfg(feature = "c_bindings")]
+mod c_bindings {
+    use crate::{utils, Ipl3ChecksumError, RomHeader};
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_rom_header_from_bytes(
+        dst_header: *mut RomHeader,
+        rom_bytes_len: usize,
+        rom_bytes: *const u8,
+    ) -> Ipl3ChecksumError {
+        if dst_header.is_null() || rom_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = match utils::c_bindings::u8_vec_from_pointer_array(
+            rom_bytes_len.min(super::ROM_HEADER_SIZE),
+            rom_bytes,
+        ) {
+            Err(e) => return e,
+            Ok(d) => d,
+        };
+
+        let header = match RomHeader::from_bytes(&bytes) {
+            Err(e) => return e,
+            Ok(h) => h,
+        };
+
+        unsafe { *dst_header = header };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_rom_header_write_to_rom(
+        header: RomHeader,
+        rom_bytes_len: usize,
+        rom_bytes: *mut u8,
+    ) -> Ipl3ChecksumError {
+        if rom_bytes.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let bytes = unsafe { std::slice::from_raw_parts_mut(rom_bytes, rom_bytes_len) };
+
+        match header.write_to_rom(bytes) {
+            Err(e) => e,
+            Ok(()) => Ipl3ChecksumError::Okay,
+        }
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_rom_header_to_string(
+        header: RomHeader,
+        dst_str: *mut *mut std::os::raw::c_char,
+    ) -> Ipl3ChecksumError {
+        if dst_str.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let s = match utils::c_bindings::c_string_from_rust_str(&header.to_string()) {
+            Err(e) => return e,
+            Ok(s) => s,
+        };
+
+        unsafe { *dst_str = s };
+
+        Ipl3ChecksumError::Okay
+    }
+
+    #[no_mangle]
+    pub extern "C" fn ipl3checksum_rom_header_decode_image_name(
+        header: RomHeader,
+        dst_str: *mut *mut std::os::raw::c_char,
+    ) -> Ipl3ChecksumError {
+        if dst_str.is_null() {
+            return Ipl3ChecksumError::NullPointer;
+        }
+
+        let name: String = header.image_name_chars().collect();
+        let s = match utils::c_bindings::c_string_from_rust_str(&name) {
+            Err(e) => return e,
+            Ok(s) => s,
+        };
+
+        unsafe { *dst_str = s };
+
+        Ipl3ChecksumError::Okay
+    }
+}
@@ -0,0 +1,258 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use core::fmt;
+use std::borrow::Cow;
+
+use crate::checksum::HEADER_IPL3_SIZE;
+use crate::rom_header::ROM_HEADER_SIZE;
+use crate::{
+    byte_order, detect, verify, CICKind, Checksum, DetectionMethod, Ipl3ChecksumError,
+    RomByteOrder, RomHeader,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Everything this library can tell about a N64 ROM.
+///
+/// Displayed as a multiline summary, meant to triage broken ROMs.
+pub struct RomInfo {
+    /// The parsed ROM header. Its checksum is the one stored on the ROM.
+    pub header: RomHeader,
+    /// The byte order of the ROM dump. Every other field refers to the ROM converted to big endian.
+    pub byte_order: RomByteOrder,
+    /// Size of the ROM in bytes.
+    pub rom_size: usize,
+    /// The MD5 digest of the IPL3. `None` if the ROM is too small to contain one.
+    pub ipl3_md5: Option<[u8; 16]>,
+    /// The CIC kind of the ROM, if one could be determined.
+    pub kind: Option<CICKind>,
+    /// How `kind` was determined. `None` if `kind` is `None`.
+    pub detection_method: Option<DetectionMethod>,
+    /// Seed value set by the PIF ROM for `kind`.
+    pub seed: Option<u32>,
+    /// Magic value hardcoded by the IPL3 of `kind`.
+    pub magic: Option<u32>,
+    /// The address the IPL3 of `kind` actually jumps to, as returned by `CICKind::get_entrypoint`.
+    pub entrypoint: Option<u32>,
+    /// How many bytes after the IPL3 are checked by `kind`.
+    pub bytes_checked: Option<usize>,
+    /// The checksum calculated for `kind`. `None` if there is no `kind` or the ROM is too small for it.
+    pub calculated_checksum: Option<Checksum>,
+}
+
+impl RomInfo {
+    /// Gathers the information of a N64 ROM of any byte order.
+    ///
+    /// The CIC kind is detected as in `verify_rom`. ROMs too small to be checksummed are still described as far as
+    /// possible.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rom_bytes` - The bytes of the N64 ROM in big endian, byteswapped or little endian format. It must have a
+    ///   minimum size of 0x40 bytes.
+    ///
+    /// ## Return
+    ///
+    /// * The gathered `RomInfo`. Possible errors:
+    ///     - `rom_bytes` being smaller than the header
+    ///     - Not able to detect the byte order
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ipl3checksum;
+    /// let mut bytes = vec![0; 0x101000];
+    /// bytes[..4].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+    ///
+    /// let info = ipl3checksum::RomInfo::from_rom(&bytes).unwrap();
+    /// assert_eq!(info.byte_order, ipl3checksum::RomByteOrder::BigEndian);
+    /// /* There's no ipl3 binary on an array of zeroes */
+    /// assert_eq!(info.kind, None);
+    /// println!("{}", info);
+    /// ```
+    pub fn from_rom(rom_bytes: &[u8]) -> Result<Self, Ipl3ChecksumError> {
+        let byte_order = RomByteOrder::detect(rom_bytes)?;
+
+        let rom_bytes = if byte_order == RomByteOrder::BigEndian {
+            Cow::Borrowed(rom_bytes)
+        } else {
+            let mut converted = rom_bytes.to_vec();
+            byte_order::normalize_byte_order(&mut converted, byte_order);
+            Cow::Owned(converted)
+        };
+
+        let header = RomHeader::from_bytes(&rom_bytes)?;
+
+        let ipl3_md5 = if rom_bytes.len() >= HEADER_IPL3_SIZE {
+            Some(md5::compute(&rom_bytes[ROM_HEADER_SIZE..HEADER_IPL3_SIZE]).0)
+        } else {
+            None
+        };
+
+        let (kind, detection_method, calculated_checksum) = match verify::verify_rom(&rom_bytes) {
+            Ok(report) => (
+                report.kind,
+                report.detection_method,
+                report.calculated_checksum,
+            ),
+            // Too small to calculate the checksum, but the IPL3 may still be known
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. }) => match ipl3_md5 {
+                Some(_) => match detect::detect_cic(&rom_bytes) {
+                    Ok(kind) => (Some(kind), Some(DetectionMethod::Ipl3Hash), None),
+                    Err(_) => (None, None, None),
+                },
+                None => (None, None, None),
+            },
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            header,
+            byte_order,
+            rom_size: rom_bytes.len(),
+            ipl3_md5,
+            kind,
+            detection_method,
+            seed: kind.map(|kind| kind.get_seed()),
+            magic: kind.map(|kind| kind.get_magic()),
+            entrypoint: kind.map(|kind| kind.get_entrypoint(header.boot_address)),
+            bytes_checked: kind.map(|kind| kind.get_params(header.boot_address).bytes_to_check),
+            calculated_checksum,
+        })
+    }
+
+    /// How much `CICKind::get_entrypoint` moved the boot address of the header, as a signed offset.
+    pub fn entrypoint_offset(&self) -> Option<i32> {
+        self.entrypoint
+            .map(|entrypoint| entrypoint.wrapping_sub(self.header.boot_address) as i32)
+    }
+
+    /// Whether the calculated checksum matches the one stored on the header.
+    pub fn checksum_matches(&self) -> bool {
+        self.calculated_checksum == Some(self.header.checksum)
+    }
+}
+
+impl fmt::Display for RomInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.header)?;
+
+        writeln!(f, "Byte order: {:?}", self.byte_order)?;
+        writeln!(f, "ROM size: 0x{:X} bytes", self.rom_size)?;
+
+        write!(f, "IPL3 MD5: ")?;
+        match &self.ipl3_md5 {
+            Some(digest) => {
+                for byte in digest {
+                    write!(f, "{:02x}", byte)?;
+                }
+                writeln!(f)?;
+            }
+            None => writeln!(f, "none, the ROM is too small")?,
+        }
+
+        match (self.kind, self.detection_method) {
+            (Some(kind), Some(DetectionMethod::HeaderChecksum)) => writeln!(
+                f,
+                "CIC kind: {} (unknown IPL3, matched by the header checksum)",
+                kind.get_name()
+            )?,
+            (Some(kind), _) => writeln!(f, "CIC kind: {}", kind.get_name())?,
+            (None, _) => writeln!(f, "CIC kind: unknown")?,
+        }
+
+        if let (Some(seed), Some(magic)) = (self.seed, self.magic) {
+            writeln!(f, "Seed: 0x{:02X}", seed)?;
+            writeln!(f, "Magic: 0x{:08X}", magic)?;
+        }
+        if let (Some(entrypoint), Some(offset)) = (self.entrypoint, self.entrypoint_offset()) {
+            write!(f, "Entrypoint: 0x{:08X}", entrypoint)?;
+            match offset {
+                0 => writeln!(f)?,
+                offset if offset < 0 => {
+                    writeln!(f, " (boot address - 0x{:X})", offset.unsigned_abs())?
+                }
+                offset => writeln!(f, " (boot address + 0x{:X})", offset)?,
+            }
+        }
+        if let Some(bytes_checked) = self.bytes_checked {
+            writeln!(f, "Bytes checked: 0x{:X}", bytes_checked)?;
+        }
+
+        writeln!(f, "Checksum in ROM:     {}", self.header.checksum)?;
+        match self.calculated_checksum {
+            Some(calculated_checksum) => {
+                writeln!(f, "Calculated checksum: {}", calculated_checksum)?;
+                if self.checksum_matches() {
+                    write!(f, "Checksum matches")
+                } else {
+                    write!(f, "Checksum doesn't match")
+                }
+            }
+            None => write!(f, "Calculated checksum: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RomInfo;
+    use crate::{cickinds::CICKind, DetectionMethod, Ipl3ChecksumError, RomByteOrder};
+
+    #[test]
+    fn test_rom_info_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, mut bin_bytes| {
+            // The dummy files don't have a PI configuration word
+            bin_bytes[..4].copy_from_slice(&[0x80, 0x37, 0x12, 0x40]);
+
+            let info = RomInfo::from_rom(&bin_bytes)?;
+            println!("{}", info);
+
+            assert_eq!(info.byte_order, RomByteOrder::BigEndian);
+            assert_eq!(info.rom_size, bin_bytes.len());
+            assert_eq!(info.ipl3_md5, Some(md5::compute([0; 0xFC0]).0));
+            // The dummy files don't contain a real IPL3
+            assert_eq!(info.detection_method, Some(DetectionMethod::HeaderChecksum));
+            let detected = info.kind.unwrap();
+            assert_eq!(info.seed, Some(detected.get_seed()));
+            assert_eq!(
+                info.entrypoint,
+                Some(detected.get_entrypoint(info.header.boot_address))
+            );
+            assert!(info.checksum_matches());
+            assert!(info.to_string().contains("Checksum matches"));
+
+            if kind == CICKind::CIC_X103 {
+                assert_eq!(info.entrypoint_offset(), Some(-0x100000));
+                assert!(info.to_string().contains("(boot address - 0x100000)"));
+            }
+
+            // Same information for other byte orders
+            crate::normalize_byte_order(&mut bin_bytes, RomByteOrder::ByteSwapped);
+            let swapped_info = RomInfo::from_rom(&bin_bytes)?;
+            assert_eq!(swapped_info.byte_order, RomByteOrder::ByteSwapped);
+            assert_eq!(swapped_info.header, info.header);
+            assert_eq!(swapped_info.calculated_checksum, info.calculated_checksum);
+
+            // Too small to checksum
+            let short_info = RomInfo::from_rom(&bin_bytes[..0x800])?;
+            assert_eq!(short_info.ipl3_md5, None);
+            assert_eq!(short_info.kind, None);
+            assert_eq!(short_info.calculated_checksum, None);
+            assert!(!short_info.checksum_matches());
+            Ok(())
+        })?;
+
+        assert_eq!(
+            RomInfo::from_rom(&[0; 0x1000]),
+            Err(Ipl3ChecksumError::UnknownByteOrder)
+        );
+        assert!(matches!(
+            RomInfo::from_rom(&[0x80, 0x37, 0x12, 0x40]),
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+        ));
+
+        Ok(())
+    }
+}
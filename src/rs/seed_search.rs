@@ -0,0 +1,342 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use core::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::checksum::{self, ChecksumState, HEADER_IPL3_SIZE};
+use crate::{utils, Checksum, CicParams, FinalizationVariant, Ipl3ChecksumError};
+
+/// Magic values hardcoded by every known IPL3.
+pub const KNOWN_MAGICS: [u32; 2] = [0x5D588B65, 0x6C078965];
+
+const FINALIZATION_VARIANTS: [FinalizationVariant; 3] = [
+    FinalizationVariant::Xor,
+    FinalizationVariant::Add,
+    FinalizationVariant::Mul,
+];
+
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum", get_all))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A combination of CIC parameters which produces the header checksum of every searched ROM.
+pub struct CicParameterCandidate {
+    /// Seed value set by the PIF ROM.
+    pub seed: u32,
+    /// Magic value hardcoded inside the IPL3.
+    pub magic: u32,
+    /// How the registers are combined into the final checksum.
+    pub finalization: FinalizationVariant,
+    /// Whether the IPL3 mixes the CIC_X105 table into the checksum.
+    pub uses_x105_table: bool,
+}
+
+impl CicParameterCandidate {
+    /// The `CicParams` described by this candidate, to checksum other ROMs with it.
+    pub fn to_params(&self, bytes_to_check: usize) -> CicParams {
+        CicParams {
+            seed: self.seed,
+            magic: self.magic,
+            bytes_to_check,
+            finalization: self.finalization,
+            uses_x105_table: self.uses_x105_table,
+        }
+    }
+}
+
+/// The parts of a ROM needed to check a combination of parameters, owned so they can be shared with the worker
+/// threads.
+struct Sample {
+    words: Vec<u32>,
+    table: [u32; 0x40],
+    checksum: Checksum,
+}
+
+impl Sample {
+    fn new(rom_bytes: &[u8], bytes_to_check: usize) -> Result<Self, Ipl3ChecksumError> {
+        let checksum = Checksum::from_header(rom_bytes)?;
+
+        if rom_bytes.len() < bytes_to_check + HEADER_IPL3_SIZE {
+            return Err(Ipl3ChecksumError::BufferNotBigEnough {
+                buffer_len: rom_bytes.len(),
+                expected_len: bytes_to_check + HEADER_IPL3_SIZE,
+            });
+        }
+
+        let words = rom_bytes[HEADER_IPL3_SIZE..HEADER_IPL3_SIZE + bytes_to_check]
+            .chunks_exact(4)
+            .map(utils::u32_from_be_chunk)
+            .collect();
+
+        Ok(Self {
+            words,
+            table: checksum::get_x105_table(rom_bytes),
+            checksum,
+        })
+    }
+
+    fn state(&self, seed: u32, magic: u32, uses_x105_table: bool) -> ChecksumState {
+        let mut state = ChecksumState::with_seed(seed, magic);
+
+        // Keep the table check outside of the loop
+        if uses_x105_table {
+            for (i, word) in self.words.iter().enumerate() {
+                state.update(*word, Some(self.table[i & 0x3F]));
+            }
+        } else {
+            for word in &self.words {
+                state.update(*word, None);
+            }
+        }
+
+        state
+    }
+}
+
+/// Returns every finalization variant that produces the checksum of all the samples with the given parameters.
+fn check_parameters(
+    samples: &[Sample],
+    seed: u32,
+    magic: u32,
+    uses_x105_table: bool,
+) -> Vec<CicParameterCandidate> {
+    let mut variants = FINALIZATION_VARIANTS.to_vec();
+
+    for sample in samples {
+        let state = sample.state(seed, magic, uses_x105_table);
+        variants.retain(|variant| state.finalize_with(*variant) == sample.checksum);
+
+        // Most combinations fail on the first sample, skip the rest for those
+        if variants.is_empty() {
+            break;
+        }
+    }
+
+    variants
+        .into_iter()
+        .map(|finalization| CicParameterCandidate {
+            seed,
+            magic,
+            finalization,
+            uses_x105_table,
+        })
+        .collect()
+}
+
+/// Searches every seed and known magic value, with every finalization variant and with and without the CIC_X105
+/// table, for the combinations that produce the header checksum of all the given ROMs.
+///
+/// Useful to find or confirm the parameters of a CIC whose seed is not known. The search runs on as many threads as
+/// the system provides.
+///
+/// ## Arguments
+///
+/// * `samples` - The bytes of N64 ROMs in big endian format, all of them using the same CIC and with a correct
+///   checksum on their header. More samples narrow down the results.
+/// * `bytes_to_check` - How many bytes after the IPL3 are checked by the CIC. It should be a multiple of 4. Usually
+///   0x100000.
+///
+/// ## Return
+///
+/// * If no error happens then every matching combination is returned, sorted by seed. Possible errors:
+///     - A sample not being big enough
+pub fn search_cic_parameters(
+    samples: &[&[u8]],
+    bytes_to_check: usize,
+) -> Result<Vec<CicParameterCandidate>, Ipl3ChecksumError> {
+    search_cic_parameters_in(samples, bytes_to_check, &KNOWN_MAGICS, 0..=0xFF)
+}
+
+/// Like `search_cic_parameters`, but only searching the given magic values and seeds.
+///
+/// ## Arguments
+///
+/// * `samples` - The bytes of N64 ROMs in big endian format, all of them using the same CIC and with a correct
+///   checksum on their header.
+/// * `bytes_to_check` - How many bytes after the IPL3 are checked by the CIC. It should be a multiple of 4.
+/// * `magics` - The magic values to try.
+/// * `seeds` - The seeds to try.
+///
+/// ## Return
+///
+/// * If no error happens then every matching combination is returned, sorted by seed. Possible errors:
+///     - A sample not being big enough
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let kind = ipl3checksum::CICKind::CIC_6102_7101;
+/// let mut bytes = vec![0; 0x101000];
+/// ipl3checksum::fix_checksum(&mut bytes, kind).unwrap();
+///
+/// let candidates =
+///     ipl3checksum::search_cic_parameters_in(&[&bytes], 0x100000, &[kind.get_magic()], 0x30..=0x4F).unwrap();
+/// assert!(candidates.contains(&ipl3checksum::CicParameterCandidate {
+///     seed: kind.get_seed(),
+///     magic: kind.get_magic(),
+///     finalization: kind.get_finalization_variant(),
+///     uses_x105_table: false,
+/// }));
+/// ```
+pub fn search_cic_parameters_in(
+    samples: &[&[u8]],
+    bytes_to_check: usize,
+    magics: &[u32],
+    seeds: RangeInclusive<u32>,
+) -> Result<Vec<CicParameterCandidate>, Ipl3ChecksumError> {
+    let samples = samples
+        .iter()
+        .map(|rom_bytes| Sample::new(rom_bytes, bytes_to_check))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut jobs = Vec::new();
+    for magic in magics {
+        for seed in seeds.clone() {
+            for uses_x105_table in [false, true] {
+                jobs.push((seed, *magic, uses_x105_table));
+            }
+        }
+    }
+
+    let thread_count = thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+        .min(jobs.len());
+
+    let samples = Arc::new(samples);
+    let jobs = Arc::new(jobs);
+    let next_job = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..thread_count)
+        .map(|_| {
+            let samples = Arc::clone(&samples);
+            let jobs = Arc::clone(&jobs);
+            let next_job = Arc::clone(&next_job);
+
+            thread::spawn(move || {
+                let mut found = Vec::new();
+
+                loop {
+                    let index = next_job.fetch_add(1, Ordering::Relaxed);
+                    let (seed, magic, uses_x105_table) = match jobs.get(index) {
+                        Some(job) => job,
+                        None => break,
+                    };
+
+                    found.extend(check_parameters(&samples, *seed, *magic, *uses_x105_table));
+                }
+
+                found
+            })
+        })
+        .collect();
+
+    let mut candidates = Vec::new();
+    for handle in handles {
+        match handle.join() {
+            Ok(found) => candidates.extend(found),
+            Err(e) => std::panic::resume_unwind(e),
+        }
+    }
+    candidates.sort_unstable();
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CicParameterCandidate;
+    use crate::{cickinds::CICKind, CicParams, Ipl3ChecksumError};
+
+    #[test]
+    fn test_search_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        let mut xor_samples = Vec::new();
+
+        crate::tests::for_each_dummy_rom(|kind, _, bin_bytes| {
+            let bytes_to_check = CicParams::for_rom(kind, &bin_bytes)?.bytes_to_check;
+
+            let seed = kind.get_seed();
+            let candidates = super::search_cic_parameters_in(
+                &[&bin_bytes],
+                bytes_to_check,
+                &super::KNOWN_MAGICS,
+                seed - 4..=seed + 4,
+            )?;
+            println!("    {:X?}", candidates);
+
+            assert!(candidates.contains(&CicParameterCandidate {
+                seed,
+                magic: kind.get_magic(),
+                finalization: kind.get_finalization_variant(),
+                uses_x105_table: kind == CICKind::CIC_X105,
+            }));
+
+            if matches!(
+                kind,
+                CICKind::CIC_6101 | CICKind::CIC_6102_7101 | CICKind::CIC_7102
+            ) {
+                xor_samples.push(bin_bytes);
+            }
+            Ok(())
+        })?;
+
+        // These kinds share their parameters, so they can be searched together
+        let xor_samples: Vec<&[u8]> = xor_samples.iter().map(|bytes| bytes.as_slice()).collect();
+        let candidates = super::search_cic_parameters_in(
+            &xor_samples,
+            0x100000,
+            &super::KNOWN_MAGICS,
+            0x3C..=0x42,
+        )?;
+        assert_eq!(
+            candidates,
+            [CicParameterCandidate {
+                seed: 0x3F,
+                magic: 0x5D588B65,
+                finalization: crate::FinalizationVariant::Xor,
+                uses_x105_table: false,
+            }]
+        );
+
+        assert!(matches!(
+            super::search_cic_parameters(&[&[0; 0x1000]], 0x100000),
+            Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use std::borrow::Cow;
+
+    #[pyfunction]
+    #[pyo3(signature = (roms_bytes, bytes_to_check=0x100000))]
+    pub(crate) fn searchCicParameters(
+        py: Python<'_>,
+        roms_bytes: Vec<Cow<[u8]>>,
+        bytes_to_check: usize,
+    ) -> Result<Option<Vec<super::CicParameterCandidate>>, super::Ipl3ChecksumError> {
+        let samples: Vec<&[u8]> = roms_bytes.iter().map(|bytes| bytes.as_ref()).collect();
+
+        match py.allow_threads(|| super::search_cic_parameters(&samples, bytes_to_check)) {
+            Ok(candidates) => Ok(Some(candidates)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+}
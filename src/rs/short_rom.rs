@@ -0,0 +1,180 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::checksum::HEADER_IPL3_SIZE;
+
+/* This needs to be in sync with the C equivalent at `bindings/c/include/ipl3checksum/short_rom.h` */
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[cfg_attr(feature = "c_bindings", repr(C))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// What to do when a ROM is smaller than the range checked by the CIC.
+///
+/// The IPL3 doesn't know the size of the cartridge, so on real hardware it checksums whatever the bus returns past the
+/// end of the ROM.
+pub enum ShortRomPolicy {
+    /// Fail with `Ipl3ChecksumError::BufferNotBigEnough`, like `calculate_checksum` does.
+    Error,
+    /// Every byte past the end of the ROM reads as 0x00.
+    ZeroFill,
+    /// Every byte past the end of the ROM reads as 0xFF.
+    FfFill,
+    /// Emulates the open bus behaviour of the N64 PI, where reading an unmapped cartridge address returns the low 16
+    /// bits of said address. Each halfword past the end of the ROM reads as the low halfword of its own offset.
+    OpenBus,
+}
+
+impl Default for ShortRomPolicy {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+impl ShortRomPolicy {
+    /// The byte read at `offset` when said offset is past the end of the ROM.
+    ///
+    /// ## Return
+    ///
+    /// * The byte, or `None` for `ShortRomPolicy::Error`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ipl3checksum;
+    /// let policy = ipl3checksum::ShortRomPolicy::OpenBus;
+    /// assert_eq!(policy.fill_byte(0x81234), Some(0x12));
+    /// assert_eq!(policy.fill_byte(0x81235), Some(0x34));
+    /// ```
+    pub fn fill_byte(&self, offset: usize) -> Option<u8> {
+        match self {
+            Self::Error => None,
+            Self::ZeroFill => Some(0x00),
+            Self::FfFill => Some(0xFF),
+            Self::OpenBus => {
+                // The cartridge is mapped at 0x10000000, so the low halfword of the address is the one of the offset
+                let halfword = (offset & 0xFFFE) as u16;
+                Some(halfword.to_be_bytes()[offset & 1])
+            }
+        }
+    }
+
+    /// The big endian word at `offset`, filling the bytes past the end of `rom_bytes`.
+    ///
+    /// Must not be called for `ShortRomPolicy::Error`.
+    #[inline]
+    pub(crate) fn read_word(&self, rom_bytes: &[u8], offset: usize) -> u32 {
+        match rom_bytes.get(offset..offset + 4) {
+            Some(word) => crate::utils::u32_from_be_chunk(word),
+            None => {
+                let mut word = [0; 4];
+                for (i, byte) in word.iter_mut().enumerate() {
+                    *byte = match rom_bytes.get(offset + i) {
+                        Some(b) => *b,
+                        None => self.fill_byte(offset + i).unwrap_or_default(),
+                    };
+                }
+                u32::from_be_bytes(word)
+            }
+        }
+    }
+
+    /// The header and the IPL3 of the ROM, filling the bytes past the end of `rom_bytes`.
+    ///
+    /// Must not be called for `ShortRomPolicy::Error`.
+    pub(crate) fn read_header(&self, rom_bytes: &[u8]) -> [u8; HEADER_IPL3_SIZE] {
+        let mut header = [0; HEADER_IPL3_SIZE];
+
+        for (offset, byte) in header.iter_mut().enumerate() {
+            *byte = match rom_bytes.get(offset) {
+                Some(b) => *b,
+                None => self.fill_byte(offset).unwrap_or_default(),
+            };
+        }
+
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShortRomPolicy;
+    use crate::{cickinds::CICKind, CicParams, Ipl3ChecksumError};
+
+    const POLICIES: [ShortRomPolicy; 3] = [
+        ShortRomPolicy::ZeroFill,
+        ShortRomPolicy::FfFill,
+        ShortRomPolicy::OpenBus,
+    ];
+
+    #[test]
+    fn test_short_rom_dummy_files() -> Result<(), Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, bin_bytes| {
+            let expected = crate::calculate_checksum(&bin_bytes, kind)?;
+            let full_len = 0x1000 + CicParams::for_rom(kind, &bin_bytes)?.bytes_to_check;
+
+            // Big enough ROMs are not affected by the policy
+            for policy in POLICIES {
+                assert_eq!(
+                    crate::calculate_checksum_with_policy(&bin_bytes, kind, policy),
+                    Ok(expected)
+                );
+            }
+
+            for short_len in [0x80002, 0x1000] {
+                let short = &bin_bytes[..short_len];
+
+                assert!(matches!(
+                    crate::calculate_checksum_with_policy(short, kind, ShortRomPolicy::Error),
+                    Err(Ipl3ChecksumError::BufferNotBigEnough { .. })
+                ));
+
+                for policy in POLICIES {
+                    let padded: Vec<u8> = (0..full_len)
+                        .map(|offset| match short.get(offset) {
+                            Some(b) => *b,
+                            None => policy.fill_byte(offset).unwrap(),
+                        })
+                        .collect();
+
+                    assert_eq!(
+                        crate::calculate_checksum_with_policy(short, kind, policy),
+                        crate::calculate_checksum(&padded, kind),
+                        "policy: {:?}, len: 0x{:X}",
+                        policy,
+                        short_len
+                    );
+                }
+            }
+            Ok(())
+        })?;
+
+        // Not even a complete header
+        assert_eq!(
+            crate::calculate_checksum_with_policy(
+                &[],
+                CICKind::CIC_6102_7101,
+                ShortRomPolicy::ZeroFill
+            ),
+            crate::calculate_checksum(&[0; 0x101000], CICKind::CIC_6102_7101)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+#[allow(unknown_lints, non_local_definitions)]
+mod python_bindings {
+    use pyo3::prelude::*;
+
+    #[pymethods]
+    impl super::ShortRomPolicy {
+        pub fn fillByte(&self, offset: usize) -> Option<u8> {
+            self.fill_byte(offset)
+        }
+    }
+}
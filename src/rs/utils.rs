@@ -22,22 +22,35 @@ pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, Ipl3ChecksumE
     }
 }
 
-pub(crate) fn read_u32_vec(
-    bytes: &[u8],
+pub(crate) fn write_u32(
+    bytes: &mut [u8],
     offset: usize,
-    len: usize,
-) -> Result<Vec<u32>, Ipl3ChecksumError> {
-    let mut ret = Vec::with_capacity(len);
+    value: u32,
+) -> Result<(), Ipl3ChecksumError> {
+    if offset % 4 != 0 {
+        return Err(Ipl3ChecksumError::UnalignedRead { offset });
+    }
 
-    for i in 0..len {
-        ret.push(read_u32(bytes, offset + i * 4).unwrap());
+    if offset + 4 > bytes.len() {
+        return Err(Ipl3ChecksumError::OutOfBounds {
+            offset,
+            requested_bytes: 4,
+            buffer_len: bytes.len(),
+        });
     }
 
-    Ok(ret)
+    bytes[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+
+    Ok(())
 }
 
-pub(crate) fn get_hash_md5(bytes: &[u8]) -> String {
-    format!("{:x}", md5::compute(bytes))
+/// Converts a 4 bytes long chunk into a big endian word.
+///
+/// Meant to be used on the chunks produced by `chunks_exact(4)`, so no bound checks are done besides the ones
+/// performed by the indexing itself.
+#[inline]
+pub(crate) fn u32_from_be_chunk(chunk: &[u8]) -> u32 {
+    u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])
 }
 
 #[cfg(feature = "c_bindings")]
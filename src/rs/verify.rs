@@ -0,0 +1,339 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+#[cfg(feature = "python_bindings")]
+use pyo3::prelude::*;
+
+use crate::{checksum, detect, CICKind, Checksum, Ipl3ChecksumError};
+
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// How the CIC kind of a verified ROM was determined.
+pub enum DetectionMethod {
+    /// The IPL3 of the ROM matched the hash of a known IPL3.
+    Ipl3Hash,
+    /// The IPL3 is unknown, but the checksum calculated for this kind matched the one in the header.
+    HeaderChecksum,
+    /// The CIC kind was specified by the caller.
+    Specified,
+}
+
+#[cfg_attr(feature = "python_bindings", pyclass(module = "ipl3checksum", get_all))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The result of verifying the checksum of a N64 ROM.
+pub struct VerificationReport {
+    /// The checksum stored on the ROM header.
+    pub header_checksum: Checksum,
+    /// The CIC kind used to verify the ROM, if one could be determined.
+    pub kind: Option<CICKind>,
+    /// How `kind` was determined. `None` if `kind` is `None`.
+    pub detection_method: Option<DetectionMethod>,
+    /// The checksum calculated for `kind`. `None` if `kind` is `None`.
+    pub calculated_checksum: Option<Checksum>,
+    /// Whether `calculated_checksum` matches `header_checksum`.
+    pub matches: bool,
+    /// On a mismatch, another CIC kind that produces the checksum stored on the header, if any.
+    ///
+    /// If this is set then the ROM data is most likely fine, but its IPL3 does not correspond to the CIC kind
+    /// that was expected.
+    pub alternative_kind: Option<CICKind>,
+}
+
+impl VerificationReport {
+    fn new(
+        header_checksum: Checksum,
+        kind: CICKind,
+        detection_method: DetectionMethod,
+        calculated_checksum: Checksum,
+        all_checksums: &[(CICKind, Result<Checksum, Ipl3ChecksumError>)],
+    ) -> Self {
+        let matches = calculated_checksum == header_checksum;
+        let alternative_kind = if matches {
+            None
+        } else {
+            find_matching_kind(header_checksum, all_checksums)
+        };
+
+        Self {
+            header_checksum,
+            kind: Some(kind),
+            detection_method: Some(detection_method),
+            calculated_checksum: Some(calculated_checksum),
+            matches,
+            alternative_kind,
+        }
+    }
+}
+
+fn find_matching_kind(
+    header_checksum: Checksum,
+    all_checksums: &[(CICKind, Result<Checksum, Ipl3ChecksumError>)],
+) -> Option<CICKind> {
+    all_checksums
+        .iter()
+        .find(|(_, checksum)| checksum.as_ref() == Ok(&header_checksum))
+        .map(|(kind, _)| *kind)
+}
+
+/// Verifies the checksum stored on the header of a N64 ROM.
+///
+/// The CIC kind is detected from the IPL3 of the ROM. If the IPL3 is unknown then the first CIC kind whose
+/// calculated checksum matches the header is used instead.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+///
+/// ## Return
+///
+/// * A `VerificationReport` describing the outcome. A checksum mismatch is not considered an error.
+///
+/// ## Examples
+///
+/// ```
+/// use ipl3checksum;
+/// let bytes = vec![0; 0x101000];
+/// let report = ipl3checksum::verify_rom(&bytes).unwrap();
+/// /* There's no ipl3 binary on an array of zeroes, nor a valid checksum */
+/// assert_eq!(report.kind, None);
+/// assert!(!report.matches);
+/// ```
+pub fn verify_rom(rom_bytes: &[u8]) -> Result<VerificationReport, Ipl3ChecksumError> {
+    let header_checksum = Checksum::from_header(rom_bytes)?;
+    let all_checksums = checksum::calculate_all_checksums(rom_bytes);
+
+    let detected = if rom_bytes.len() >= checksum::HEADER_IPL3_SIZE {
+        detect::detect_cic(rom_bytes)
+    } else {
+        Err(Ipl3ChecksumError::UnableToDetectCIC)
+    };
+
+    match detected {
+        Ok(kind) => verify_with(
+            rom_bytes,
+            header_checksum,
+            kind,
+            DetectionMethod::Ipl3Hash,
+            &all_checksums,
+        ),
+        Err(Ipl3ChecksumError::UnableToDetectCIC) => {
+            if let Some(kind) = find_matching_kind(header_checksum, &all_checksums) {
+                return Ok(VerificationReport::new(
+                    header_checksum,
+                    kind,
+                    DetectionMethod::HeaderChecksum,
+                    header_checksum,
+                    &all_checksums,
+                ));
+            }
+
+            // Every kind checks at least as many bytes as the first one, so if it failed then nothing could be
+            // calculated at all
+            if let (_, Err(e)) = &all_checksums[0] {
+                return Err(e.clone());
+            }
+
+            Ok(VerificationReport {
+                header_checksum,
+                kind: None,
+                detection_method: None,
+                calculated_checksum: None,
+                matches: false,
+                alternative_kind: None,
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Verifies the checksum stored on the header of a N64 ROM, using the given CIC kind instead of detecting it.
+///
+/// ## Arguments
+///
+/// * `rom_bytes` - The bytes of the N64 ROM in big endian format. It must have a minimum size of 0x101000 bytes.
+/// * `kind` - The CIC kind variation used to calculate the checksum.
+///
+/// ## Return
+///
+/// * A `VerificationReport` describing the outcome. A checksum mismatch is not considered an error.
+pub fn verify_rom_with_kind(
+    rom_bytes: &[u8],
+    kind: CICKind,
+) -> Result<VerificationReport, Ipl3ChecksumError> {
+    let header_checksum = Checksum::from_header(rom_bytes)?;
+    let all_checksums = checksum::calculate_all_checksums(rom_bytes);
+
+    verify_with(
+        rom_bytes,
+        header_checksum,
+        kind,
+        DetectionMethod::Specified,
+        &all_checksums,
+    )
+}
+
+fn verify_with(
+    rom_bytes: &[u8],
+    header_checksum: Checksum,
+    kind: CICKind,
+    detection_method: DetectionMethod,
+    all_checksums: &[(CICKind, Result<Checksum, Ipl3ChecksumError>)],
+) -> Result<VerificationReport, Ipl3ChecksumError> {
+    let calculated_checksum = match all_checksums.iter().find(|(k, _)| *k == kind) {
+        Some((_, checksum)) => checksum.clone()?,
+        None => checksum::calculate_checksum(rom_bytes, kind)?,
+    };
+
+    Ok(VerificationReport::new(
+        header_checksum,
+        kind,
+        detection_method,
+        calculated_checksum,
+        all_checksums,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DetectionMethod;
+    use crate::{cickinds::CICKind, Checksum};
+
+    #[test]
+    fn test_verify_dummy_files() -> Result<(), super::Ipl3ChecksumError> {
+        crate::tests::for_each_dummy_rom(|kind, _, mut bin_bytes| {
+            let header_checksum = Checksum::from_header(&bin_bytes)?;
+
+            // The dummy files don't contain a real IPL3, so the kind is found by its checksum
+            let report = super::verify_rom(&bin_bytes)?;
+            assert_eq!(
+                report.detection_method,
+                Some(DetectionMethod::HeaderChecksum)
+            );
+            assert_eq!(
+                report.kind.unwrap().calculate_checksum(&bin_bytes)?,
+                header_checksum
+            );
+            assert!(report.matches);
+
+            let report = super::verify_rom_with_kind(&bin_bytes, kind)?;
+            assert_eq!(report.kind, Some(kind));
+            assert_eq!(report.detection_method, Some(DetectionMethod::Specified));
+            assert_eq!(report.calculated_checksum, Some(header_checksum));
+            assert!(report.matches);
+            assert_eq!(report.alternative_kind, None);
+
+            // Wrong kind
+            let wrong_kind = if kind == CICKind::CIC_X106 {
+                CICKind::CIC_X103
+            } else {
+                CICKind::CIC_X106
+            };
+            let report = super::verify_rom_with_kind(&bin_bytes, wrong_kind)?;
+            assert!(!report.matches);
+            let alternative_kind = report.alternative_kind.unwrap();
+            assert_eq!(
+                alternative_kind.calculate_checksum(&bin_bytes)?,
+                header_checksum
+            );
+
+            // Modified data
+            bin_bytes[0x2000] ^= 0xFF;
+            let report = super::verify_rom_with_kind(&bin_bytes, kind)?;
+            assert!(!report.matches);
+            assert_eq!(report.alternative_kind, None);
+
+            let report = super::verify_rom(&bin_bytes)?;
+            assert_eq!(report.kind, None);
+            assert!(!report.matches);
+            Ok(())
+        })?;
+
+        // Too small to calculate anything
+        assert!(super::verify_rom(&[0; 0x1000]).is_err());
+        assert!(super::verify_rom(&[0; 0x10]).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_verify_serde() -> Result<(), super::Ipl3ChecksumError> {
+        let mut bytes = vec![0; 0x101000];
+        crate::fix_checksum(&mut bytes, CICKind::CIC_X105)?;
+
+        let report = super::verify_rom_with_kind(&bytes, CICKind::CIC_X105)?;
+        let json = serde_json::to_string(&report).unwrap();
+        println!("{}", json);
+        assert!(json.contains("\"kind\":\"CIC_X105\""));
+        assert!(json.contains("\"detection_method\":\"Specified\""));
+        assert_eq!(
+            serde_json::from_str::<super::VerificationReport>(&json).unwrap(),
+            report
+        );
+
+        let error = super::Ipl3ChecksumError::BufferNotBigEnough {
+            buffer_len: 0x10,
+            expected_len: 0x101000,
+        };
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(
+            json,
+            "{\"BufferNotBigEnough\":{\"buffer_len\":16,\"expected_len\":1052672}}"
+        );
+        assert_eq!(
+            serde_json::from_str::<super::Ipl3ChecksumError>(&json).unwrap(),
+            error
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python_bindings")]
+#[allow(non_snake_case)]
+pub(crate) mod python_bindings {
+    use pyo3::prelude::*;
+    use std::borrow::Cow;
+
+    /**
+     * We use a `Cow` instead of a plain &[u8] the latter only allows Python's
+     * `bytes` objects, while Cow allows for both `bytes` and `bytearray`.
+     * This is important because an argument typed as `bytes` allows to pass a
+     * `bytearray` object too.
+     */
+
+    #[pyfunction]
+    pub(crate) fn verifyRom(
+        rom_bytes: Cow<[u8]>,
+    ) -> Result<Option<super::VerificationReport>, super::Ipl3ChecksumError> {
+        match super::verify_rom(&rom_bytes) {
+            Ok(report) => Ok(Some(report)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+
+    #[pyfunction]
+    pub(crate) fn verifyRomWithKind(
+        rom_bytes: Cow<[u8]>,
+        kind: super::CICKind,
+    ) -> Result<Option<super::VerificationReport>, super::Ipl3ChecksumError> {
+        match super::verify_rom_with_kind(&rom_bytes, kind) {
+            Ok(report) => Ok(Some(report)),
+            Err(e) => match e {
+                super::Ipl3ChecksumError::BufferNotBigEnough {
+                    buffer_len: _,
+                    expected_len: _,
+                } => Ok(None),
+                _ => Err(e), // To trigger an exception on Python's side
+            },
+        }
+    }
+}
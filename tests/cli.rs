@@ -0,0 +1,209 @@
+/* SPDX-FileCopyrightText: © 2024 Decompollaborate */
+/* SPDX-License-Identifier: MIT */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use ipl3checksum::{calculate_checksum, CICKind, Checksum};
+
+struct Output {
+    code: i32,
+    stdout: String,
+}
+
+fn run(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_ipl3checksum"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    Output {
+        code: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+    }
+}
+
+fn dummy_files() -> Vec<(CICKind, PathBuf)> {
+    let mut dummies = Vec::new();
+
+    for path_result in fs::read_dir("tests/dummytests").unwrap() {
+        let ipl3_folder = path_result.unwrap();
+        let folder_name = ipl3_folder.file_name();
+
+        let kind = CICKind::from_name(folder_name.to_str().unwrap()).unwrap();
+
+        for bin_path_result in fs::read_dir(ipl3_folder.path()).unwrap() {
+            dummies.push((kind, bin_path_result.unwrap().path()));
+        }
+    }
+
+    dummies.sort_by(|a, b| a.1.cmp(&b.1));
+    dummies
+}
+
+fn header_checksum(path: &Path) -> Checksum {
+    Checksum::from_header(&fs::read(path).unwrap()).unwrap()
+}
+
+/// Copies a file to a temporary path unique to the test.
+fn temp_copy(path: &Path, name: &str) -> PathBuf {
+    let dst = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::copy(path, &dst).unwrap();
+    dst
+}
+
+#[test]
+fn test_cli_check() {
+    for (kind, path) in dummy_files() {
+        let path_str = path.to_str().unwrap();
+        let checksum = header_checksum(&path);
+
+        let output = run(&["check", path_str, "--kind", kind.get_name()]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert!(output
+            .stdout
+            .contains(&format!("Checksum in ROM:     {}\n", checksum)));
+        assert!(output
+            .stdout
+            .contains(&format!("Calculated checksum: {}\n", checksum)));
+        assert!(output.stdout.ends_with("Checksum matches\n"));
+
+        // The dummy files don't contain a real IPL3, so the kind is found by its checksum
+        let output = run(&["check", path_str]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert!(output.stdout.starts_with("Unknown IPL3, kind '"));
+        assert!(output.stdout.ends_with("Checksum matches\n"));
+
+        // Some kinds share the same checksum algorithm, so pick one which gives a different result
+        let rom_bytes = fs::read(&path).unwrap();
+        let wrong_kind = [CICKind::CIC_X106, CICKind::CIC_X103]
+            .into_iter()
+            .find(|k| calculate_checksum(&rom_bytes, *k).unwrap() != checksum)
+            .unwrap();
+        let output = run(&["check", path_str, "-k", wrong_kind.get_name()]);
+        assert_eq!(output.code, 1, "{}", output.stdout);
+        assert!(output.stdout.contains("Checksum doesn't match\n"));
+        assert!(output
+            .stdout
+            .contains("The checksum in the header matches kind '"));
+    }
+}
+
+#[test]
+fn test_cli_sum() {
+    for (kind, path) in dummy_files() {
+        let path_str = path.to_str().unwrap();
+        let checksum = header_checksum(&path);
+
+        let output = run(&["sum", path_str, "--cic", kind.get_name()]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert_eq!(
+            output.stdout,
+            format!("Calculated checksum: {}\n", checksum)
+        );
+
+        // The kind can't be detected without a real IPL3
+        let output = run(&["sum", path_str]);
+        assert_eq!(output.code, 1, "{}", output.stdout);
+        assert_eq!(output.stdout, "Unable to detect CIC kind\n");
+
+        // Write the ROM with the fixed checksum somewhere else
+        let mut broken = fs::read(&path).unwrap();
+        broken[0x10] ^= 0xFF;
+        let broken_path =
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("cli_sum_{}.bin", kind.get_name()));
+        fs::write(&broken_path, &broken).unwrap();
+        let output_path = Path::new(env!("CARGO_TARGET_TMPDIR"))
+            .join(format!("cli_sum_{}_out.bin", kind.get_name()));
+
+        let output = run(&[
+            "sum",
+            broken_path.to_str().unwrap(),
+            "-k",
+            kind.get_name(),
+            "-u",
+            "-o",
+            output_path.to_str().unwrap(),
+        ]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert!(output.stdout.contains(&format!(
+            "Writing updated ROM to '{}'\n",
+            output_path.display()
+        )));
+        assert_eq!(fs::read(&output_path).unwrap(), fs::read(&path).unwrap());
+        assert_eq!(fs::read(&broken_path).unwrap(), broken);
+    }
+}
+
+#[test]
+fn test_cli_detect() {
+    for (_, path) in dummy_files() {
+        let output = run(&["detect", path.to_str().unwrap()]);
+        assert_eq!(output.code, 1, "{}", output.stdout);
+        assert_eq!(output.stdout, "Unable to detect CIC kind\n");
+
+        let output = run(&["detect_cic", path.to_str().unwrap()]);
+        assert_eq!(output.code, 1, "{}", output.stdout);
+    }
+
+    // Too small to contain an IPL3
+    let small_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli_detect_small.bin");
+    fs::write(&small_path, [0; 0x40]).unwrap();
+    let output = run(&["detect", small_path.to_str().unwrap()]);
+    assert_eq!(output.code, 1, "{}", output.stdout);
+}
+
+#[test]
+fn test_cli_fix() {
+    for (kind, path) in dummy_files() {
+        let checksum = header_checksum(&path);
+        let fixed_path = temp_copy(&path, &format!("cli_fix_{}.bin", kind.get_name()));
+        let fixed_str = fixed_path.to_str().unwrap();
+
+        let output = run(&["fix", fixed_str, "-k", kind.get_name()]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert!(output.stdout.ends_with("Checksum already matches\n"));
+
+        let mut broken = fs::read(&path).unwrap();
+        broken[0x17] ^= 0xFF;
+        fs::write(&fixed_path, &broken).unwrap();
+        let broken_checksum = header_checksum(&fixed_path);
+
+        let output = run(&["fix", fixed_str, "-k", kind.get_name()]);
+        assert_eq!(output.code, 0, "{}", output.stdout);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "Checksum in ROM:     {}\nCalculated checksum: {}\nWriting updated ROM to '{}'\n",
+                broken_checksum, checksum, fixed_str
+            )
+        );
+        assert_eq!(fs::read(&fixed_path).unwrap(), fs::read(&path).unwrap());
+
+        // The kind can't be detected without a real IPL3, and the ROM is left untouched
+        fs::write(&fixed_path, &broken).unwrap();
+        let output = run(&["fix", fixed_str]);
+        assert_eq!(output.code, 1, "{}", output.stdout);
+        assert_eq!(output.stdout, "Unable to detect CIC kind\n");
+        assert_eq!(fs::read(&fixed_path).unwrap(), broken);
+    }
+}
+
+#[test]
+fn test_cli_errors() {
+    let (_, path) = &dummy_files()[0];
+    let path_str = path.to_str().unwrap();
+
+    // Invalid arguments are reported by the argument parser
+    assert_eq!(run(&["check", path_str, "-k", "6104"]).code, 2);
+    assert_eq!(run(&["sum"]).code, 2);
+    assert_eq!(run(&["not_a_subcommand"]).code, 2);
+    assert_eq!(run(&[]).code, 2);
+
+    let output = run(&["check", "tests/dummytests/does_not_exist.bin"]);
+    assert_eq!(output.code, 1);
+    assert!(output
+        .stdout
+        .starts_with("Unable to read 'tests/dummytests/does_not_exist.bin'"));
+}